@@ -311,6 +311,9 @@ pub fn make_block_states(input: TokenStream) -> TokenStream {
     let mut from_registry_block_to_block_match = quote! {};
     let mut from_registry_block_to_blockstate_match = quote! {};
     let mut from_registry_block_to_blockstates_match = quote! {};
+    let mut waterlogged_match = quote! {};
+    let mut open_match = quote! {};
+    let mut horizontal_facing_match = quote! {};
 
     for block in &input.block_definitions.blocks {
         let block_property_names = &block
@@ -483,6 +486,9 @@ pub fn make_block_states(input: TokenStream) -> TokenStream {
         //     }
         // }
         let mut from_state_to_block_inner = quote! {};
+        let mut waterlogged_division = None;
+        let mut open_division = None;
+        let mut horizontal_facing_division = None;
         let mut division = 1u32;
         for i in (0..properties_with_name.len()).rev() {
             let PropertyWithNameAndDefault {
@@ -506,10 +512,36 @@ pub fn make_block_states(input: TokenStream) -> TokenStream {
                 #property_name: #conversion_code,
             });
 
+            if property_name == "waterlogged" {
+                waterlogged_division = Some(division);
+            }
+            if property_name == "open" {
+                open_division = Some(division);
+            }
+            if property_name == "facing" && property_struct_name_ident == "FacingCardinal" {
+                horizontal_facing_division = Some(division);
+            }
+
             division *= property_variants_count;
         }
 
         let last_state_id = state_id - 1;
+        if let Some(division) = waterlogged_division {
+            // like the other booleans, true comes first
+            waterlogged_match.extend(quote! {
+                #first_state_id..=#last_state_id => ((self.id - #first_state_id) / #division) % 2 == 0,
+            });
+        }
+        if let Some(division) = open_division {
+            open_match.extend(quote! {
+                #first_state_id..=#last_state_id => ((self.id - #first_state_id) / #division) % 2 == 0,
+            });
+        }
+        if let Some(division) = horizontal_facing_division {
+            horizontal_facing_match.extend(quote! {
+                #first_state_id..=#last_state_id => Some(properties::FacingCardinal::from(((self.id - #first_state_id) / #division) % 4)),
+            });
+        }
         from_state_to_block_match.extend(quote! {
             #first_state_id..=#last_state_id => {
                 let b = b - #first_state_id;
@@ -567,6 +599,9 @@ pub fn make_block_states(input: TokenStream) -> TokenStream {
                 fn as_block_state(&self) -> BlockState {
                     #from_block_to_state_match
                 }
+                fn as_registry_block(&self) -> azalea_registry::Block {
+                    azalea_registry::Block::#block_name_pascal_case
+                }
             }
 
             impl From<#block_struct_name> for BlockState {
@@ -595,6 +630,35 @@ pub fn make_block_states(input: TokenStream) -> TokenStream {
             pub fn max_state() -> u32 {
                 #last_state_id
             }
+
+            /// Whether the block state has a `waterlogged` property that's set
+            /// to true. Blocks that are always underwater, like kelp, aren't
+            /// included.
+            pub fn waterlogged(&self) -> bool {
+                match self.id {
+                    #waterlogged_match
+                    _ => false,
+                }
+            }
+
+            /// Whether the block state has an `open` property that's set to
+            /// true, like an open door, fence gate or trapdoor.
+            pub fn open(&self) -> bool {
+                match self.id {
+                    #open_match
+                    _ => false,
+                }
+            }
+
+            /// The direction of the block state's `facing` property if it can
+            /// only face north, south, west or east, like doors, trapdoors and
+            /// fence gates.
+            pub fn horizontal_facing(&self) -> Option<properties::FacingCardinal> {
+                match self.id {
+                    #horizontal_facing_match
+                    _ => None,
+                }
+            }
         }
 
         pub mod properties {
@@ -2,6 +2,18 @@ pub struct BlockBehavior {
     pub has_collision: bool,
     pub friction: f32,
     pub jump_factor: f32,
+    /// What an entity's horizontal movement is multiplied by while it's
+    /// standing on (or in) the block. This is less than 1 for soul sand and
+    /// honey blocks.
+    pub speed_factor: f32,
+    /// How long it takes to break the block, in "seconds with an ideal tool"
+    /// (vanilla calls this the hardness). This is -1 for unbreakable blocks
+    /// like bedrock.
+    pub destroy_time: f32,
+    pub explosion_resistance: f32,
+    /// Whether the block only drops items (and breaks at full speed) when it's
+    /// mined with the right tool.
+    pub requires_correct_tool_for_drops: bool,
 }
 
 impl Default for BlockBehavior {
@@ -10,6 +22,10 @@ impl Default for BlockBehavior {
             has_collision: true,
             friction: 0.6,
             jump_factor: 1.0,
+            speed_factor: 1.0,
+            destroy_time: 0.,
+            explosion_resistance: 0.,
+            requires_correct_tool_for_drops: false,
         }
     }
 }
@@ -32,4 +48,36 @@ impl BlockBehavior {
         self.jump_factor = jump_factor;
         self
     }
+
+    #[inline]
+    pub fn speed_factor(mut self, speed_factor: f32) -> Self {
+        self.speed_factor = speed_factor;
+        self
+    }
+
+    #[inline]
+    pub fn destroy_time(mut self, destroy_time: f32) -> Self {
+        self.destroy_time = destroy_time;
+        self
+    }
+
+    #[inline]
+    pub fn explosion_resistance(mut self, explosion_resistance: f32) -> Self {
+        self.explosion_resistance = f32::max(0., explosion_resistance);
+        self
+    }
+
+    /// Set both the destroy time and the explosion resistance, like vanilla's
+    /// `strength` method.
+    #[inline]
+    pub fn strength(self, destroy_time: f32, explosion_resistance: f32) -> Self {
+        self.destroy_time(destroy_time)
+            .explosion_resistance(explosion_resistance)
+    }
+
+    #[inline]
+    pub fn requires_correct_tool_for_drops(mut self) -> Self {
+        self.requires_correct_tool_for_drops = true;
+        self
+    }
 }
@@ -0,0 +1,129 @@
+use crate::{blocks, Block, BlockState};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FluidKind {
+    #[default]
+    Empty,
+    Water,
+    Lava,
+}
+
+/// The fluid that's in a block, and how much of it there is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct FluidState {
+    pub kind: FluidKind,
+    /// From 1 to 8, where 8 is a full block. This is 0 if the fluid is empty.
+    pub amount: u8,
+    /// Whether the fluid is flowing down from the block above it.
+    pub falling: bool,
+}
+
+impl FluidState {
+    pub fn is_empty(&self) -> bool {
+        self.kind == FluidKind::Empty
+    }
+
+    /// Whether this is a source block, which is a full block of fluid that
+    /// isn't flowing.
+    pub fn is_source(&self) -> bool {
+        !self.is_empty() && self.amount == 8 && !self.falling
+    }
+
+    /// The height of the fluid in the block, from 0 to 1. This doesn't take
+    /// into account whether there's more fluid above, so a full block of fluid
+    /// is only 8/9 of a block tall.
+    pub fn own_height(&self) -> f32 {
+        self.amount as f32 / 9.
+    }
+
+    fn from_level(kind: FluidKind, level: u8) -> Self {
+        // level 0 is a source, 1 to 7 are flowing away from it, and 8 and above
+        // mean that it's falling
+        if level >= 8 {
+            Self {
+                kind,
+                amount: 8,
+                falling: true,
+            }
+        } else {
+            Self {
+                kind,
+                amount: 8 - level,
+                falling: false,
+            }
+        }
+    }
+}
+
+impl From<BlockState> for FluidState {
+    fn from(state: BlockState) -> Self {
+        if state.waterlogged() {
+            return Self::from_level(FluidKind::Water, 0);
+        }
+
+        let block = Box::<dyn Block>::from(state);
+        if let Some(water) = block.downcast_ref::<blocks::Water>() {
+            return Self::from_level(FluidKind::Water, water.level as u8);
+        }
+        if let Some(lava) = block.downcast_ref::<blocks::Lava>() {
+            return Self::from_level(FluidKind::Lava, lava.level as u8);
+        }
+
+        match block.as_registry_block() {
+            // these blocks are always underwater
+            azalea_registry::Block::BubbleColumn
+            | azalea_registry::Block::Kelp
+            | azalea_registry::Block::KelpPlant
+            | azalea_registry::Block::Seagrass
+            | azalea_registry::Block::TallSeagrass => Self::from_level(FluidKind::Water, 0),
+            _ => Self::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::properties::WaterLevel;
+
+    #[test]
+    fn test_fluid_state_from_water() {
+        let source = FluidState::from(BlockState::from(azalea_registry::Block::Water));
+        assert_eq!(source.kind, FluidKind::Water);
+        assert!(source.is_source());
+
+        let flowing = FluidState::from(
+            blocks::Water {
+                level: WaterLevel::_3,
+            }
+            .as_block_state(),
+        );
+        assert_eq!(flowing.amount, 5);
+        assert!(!flowing.falling);
+
+        let falling = FluidState::from(
+            blocks::Water {
+                level: WaterLevel::_8,
+            }
+            .as_block_state(),
+        );
+        assert!(falling.falling);
+        assert!(!falling.is_source());
+    }
+
+    #[test]
+    fn test_fluid_state_from_waterlogged() {
+        let waterlogged = blocks::OakStairs {
+            waterlogged: true,
+            ..Default::default()
+        }
+        .as_block_state();
+        assert!(waterlogged.waterlogged());
+        assert!(FluidState::from(waterlogged).is_source());
+
+        let dry = BlockState::from(azalea_registry::Block::OakStairs);
+        assert!(!dry.waterlogged());
+        assert!(FluidState::from(dry).is_empty());
+        assert!(FluidState::from(BlockState::from(azalea_registry::Block::Stone)).is_empty());
+    }
+}
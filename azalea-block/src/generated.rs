@@ -1594,29 +1594,29 @@ make_block_states! {
     },
     Blocks => {
         air => BlockBehavior::default(), {},
-        stone => BlockBehavior::default(), {},
-        granite => BlockBehavior::default(), {},
-        polished_granite => BlockBehavior::default(), {},
-        diorite => BlockBehavior::default(), {},
-        polished_diorite => BlockBehavior::default(), {},
-        andesite => BlockBehavior::default(), {},
-        polished_andesite => BlockBehavior::default(), {},
-        grass_block => BlockBehavior::default(), {
+        stone => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        granite => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        polished_granite => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        diorite => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        polished_diorite => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        andesite => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        polished_andesite => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        grass_block => BlockBehavior::default().strength(0.6, 0.6), {
             snowy: false,
         },
-        dirt => BlockBehavior::default(), {},
-        coarse_dirt => BlockBehavior::default(), {},
-        podzol => BlockBehavior::default(), {
+        dirt => BlockBehavior::default().strength(0.5, 0.5), {},
+        coarse_dirt => BlockBehavior::default().strength(0.5, 0.5), {},
+        podzol => BlockBehavior::default().strength(0.5, 0.5), {
             snowy: false,
         },
-        cobblestone => BlockBehavior::default(), {},
-        oak_planks => BlockBehavior::default(), {},
-        spruce_planks => BlockBehavior::default(), {},
-        birch_planks => BlockBehavior::default(), {},
-        jungle_planks => BlockBehavior::default(), {},
-        acacia_planks => BlockBehavior::default(), {},
-        dark_oak_planks => BlockBehavior::default(), {},
-        mangrove_planks => BlockBehavior::default(), {},
+        cobblestone => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        oak_planks => BlockBehavior::default().strength(2., 3.), {},
+        spruce_planks => BlockBehavior::default().strength(2., 3.), {},
+        birch_planks => BlockBehavior::default().strength(2., 3.), {},
+        jungle_planks => BlockBehavior::default().strength(2., 3.), {},
+        acacia_planks => BlockBehavior::default().strength(2., 3.), {},
+        dark_oak_planks => BlockBehavior::default().strength(2., 3.), {},
+        mangrove_planks => BlockBehavior::default().strength(2., 3.), {},
         oak_sapling => BlockBehavior::default(), {
             stage: OakSaplingStage::_0,
         },
@@ -1641,271 +1641,271 @@ make_block_states! {
             stage: MangrovePropaguleStage::_0,
             waterlogged: false,
         },
-        bedrock => BlockBehavior::default(), {},
-        water => BlockBehavior::default(), {
+        bedrock => BlockBehavior::default().strength(-1., 3600000.), {},
+        water => BlockBehavior::default().strength(100., 100.), {
             level: WaterLevel::_0,
         },
-        lava => BlockBehavior::default(), {
+        lava => BlockBehavior::default().strength(100., 100.), {
             level: LavaLevel::_0,
         },
-        sand => BlockBehavior::default(), {},
-        red_sand => BlockBehavior::default(), {},
-        gravel => BlockBehavior::default(), {},
-        gold_ore => BlockBehavior::default(), {},
-        deepslate_gold_ore => BlockBehavior::default(), {},
-        iron_ore => BlockBehavior::default(), {},
-        deepslate_iron_ore => BlockBehavior::default(), {},
-        coal_ore => BlockBehavior::default(), {},
-        deepslate_coal_ore => BlockBehavior::default(), {},
-        nether_gold_ore => BlockBehavior::default(), {},
-        oak_log => BlockBehavior::default(), {
+        sand => BlockBehavior::default().strength(0.5, 0.5), {},
+        red_sand => BlockBehavior::default().strength(0.5, 0.5), {},
+        gravel => BlockBehavior::default().strength(0.6, 0.6), {},
+        gold_ore => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {},
+        deepslate_gold_ore => BlockBehavior::default().strength(4.5, 3.).requires_correct_tool_for_drops(), {},
+        iron_ore => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {},
+        deepslate_iron_ore => BlockBehavior::default().strength(4.5, 3.).requires_correct_tool_for_drops(), {},
+        coal_ore => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {},
+        deepslate_coal_ore => BlockBehavior::default().strength(4.5, 3.).requires_correct_tool_for_drops(), {},
+        nether_gold_ore => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {},
+        oak_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        spruce_log => BlockBehavior::default(), {
+        spruce_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        birch_log => BlockBehavior::default(), {
+        birch_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        jungle_log => BlockBehavior::default(), {
+        jungle_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        acacia_log => BlockBehavior::default(), {
+        acacia_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        dark_oak_log => BlockBehavior::default(), {
+        dark_oak_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        mangrove_log => BlockBehavior::default(), {
+        mangrove_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        mangrove_roots => BlockBehavior::default(), {
+        mangrove_roots => BlockBehavior::default().strength(0.7, 0.7), {
             waterlogged: false,
         },
-        muddy_mangrove_roots => BlockBehavior::default(), {
+        muddy_mangrove_roots => BlockBehavior::default().strength(0.7, 0.7), {
             axis: Axis::Y,
         },
-        stripped_spruce_log => BlockBehavior::default(), {
+        stripped_spruce_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_birch_log => BlockBehavior::default(), {
+        stripped_birch_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_jungle_log => BlockBehavior::default(), {
+        stripped_jungle_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_acacia_log => BlockBehavior::default(), {
+        stripped_acacia_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_dark_oak_log => BlockBehavior::default(), {
+        stripped_dark_oak_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_oak_log => BlockBehavior::default(), {
+        stripped_oak_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_mangrove_log => BlockBehavior::default(), {
+        stripped_mangrove_log => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        oak_wood => BlockBehavior::default(), {
+        oak_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        spruce_wood => BlockBehavior::default(), {
+        spruce_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        birch_wood => BlockBehavior::default(), {
+        birch_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        jungle_wood => BlockBehavior::default(), {
+        jungle_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        acacia_wood => BlockBehavior::default(), {
+        acacia_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        dark_oak_wood => BlockBehavior::default(), {
+        dark_oak_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        mangrove_wood => BlockBehavior::default(), {
+        mangrove_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_oak_wood => BlockBehavior::default(), {
+        stripped_oak_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_spruce_wood => BlockBehavior::default(), {
+        stripped_spruce_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_birch_wood => BlockBehavior::default(), {
+        stripped_birch_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_jungle_wood => BlockBehavior::default(), {
+        stripped_jungle_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_acacia_wood => BlockBehavior::default(), {
+        stripped_acacia_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_dark_oak_wood => BlockBehavior::default(), {
+        stripped_dark_oak_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_mangrove_wood => BlockBehavior::default(), {
+        stripped_mangrove_wood => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        oak_leaves => BlockBehavior::default(), {
+        oak_leaves => BlockBehavior::default().strength(0.2, 0.2), {
             distance: OakLeavesDistance::_7,
             persistent: false,
             waterlogged: false,
         },
-        spruce_leaves => BlockBehavior::default(), {
+        spruce_leaves => BlockBehavior::default().strength(0.2, 0.2), {
             distance: SpruceLeavesDistance::_7,
             persistent: false,
             waterlogged: false,
         },
-        birch_leaves => BlockBehavior::default(), {
+        birch_leaves => BlockBehavior::default().strength(0.2, 0.2), {
             distance: BirchLeavesDistance::_7,
             persistent: false,
             waterlogged: false,
         },
-        jungle_leaves => BlockBehavior::default(), {
+        jungle_leaves => BlockBehavior::default().strength(0.2, 0.2), {
             distance: JungleLeavesDistance::_7,
             persistent: false,
             waterlogged: false,
         },
-        acacia_leaves => BlockBehavior::default(), {
+        acacia_leaves => BlockBehavior::default().strength(0.2, 0.2), {
             distance: AcaciaLeavesDistance::_7,
             persistent: false,
             waterlogged: false,
         },
-        dark_oak_leaves => BlockBehavior::default(), {
+        dark_oak_leaves => BlockBehavior::default().strength(0.2, 0.2), {
             distance: DarkOakLeavesDistance::_7,
             persistent: false,
             waterlogged: false,
         },
-        mangrove_leaves => BlockBehavior::default(), {
+        mangrove_leaves => BlockBehavior::default().strength(0.2, 0.2), {
             distance: MangroveLeavesDistance::_7,
             persistent: false,
             waterlogged: false,
         },
-        azalea_leaves => BlockBehavior::default(), {
+        azalea_leaves => BlockBehavior::default().strength(0.2, 0.2), {
             distance: AzaleaLeavesDistance::_7,
             persistent: false,
             waterlogged: false,
         },
-        flowering_azalea_leaves => BlockBehavior::default(), {
+        flowering_azalea_leaves => BlockBehavior::default().strength(0.2, 0.2), {
             distance: FloweringAzaleaLeavesDistance::_7,
             persistent: false,
             waterlogged: false,
         },
-        sponge => BlockBehavior::default(), {},
-        wet_sponge => BlockBehavior::default(), {},
-        glass => BlockBehavior::default(), {},
-        lapis_ore => BlockBehavior::default(), {},
-        deepslate_lapis_ore => BlockBehavior::default(), {},
-        lapis_block => BlockBehavior::default(), {},
-        dispenser => BlockBehavior::default(), {
+        sponge => BlockBehavior::default().strength(0.6, 0.6), {},
+        wet_sponge => BlockBehavior::default().strength(0.6, 0.6), {},
+        glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        lapis_ore => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {},
+        deepslate_lapis_ore => BlockBehavior::default().strength(4.5, 3.).requires_correct_tool_for_drops(), {},
+        lapis_block => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {},
+        dispenser => BlockBehavior::default().strength(3.5, 3.5).requires_correct_tool_for_drops(), {
             facing: FacingCubic::North,
             triggered: false,
         },
-        sandstone => BlockBehavior::default(), {},
-        chiseled_sandstone => BlockBehavior::default(), {},
-        cut_sandstone => BlockBehavior::default(), {},
-        note_block => BlockBehavior::default(), {
+        sandstone => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {},
+        chiseled_sandstone => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {},
+        cut_sandstone => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {},
+        note_block => BlockBehavior::default().strength(0.8, 0.8), {
             instrument: Sound::Harp,
             note: NoteBlockNote::_0,
             powered: false,
         },
-        white_bed => BlockBehavior::default(), {
+        white_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        orange_bed => BlockBehavior::default(), {
+        orange_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        magenta_bed => BlockBehavior::default(), {
+        magenta_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        light_blue_bed => BlockBehavior::default(), {
+        light_blue_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        yellow_bed => BlockBehavior::default(), {
+        yellow_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        lime_bed => BlockBehavior::default(), {
+        lime_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        pink_bed => BlockBehavior::default(), {
+        pink_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        gray_bed => BlockBehavior::default(), {
+        gray_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        light_gray_bed => BlockBehavior::default(), {
+        light_gray_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        cyan_bed => BlockBehavior::default(), {
+        cyan_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        purple_bed => BlockBehavior::default(), {
+        purple_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        blue_bed => BlockBehavior::default(), {
+        blue_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        brown_bed => BlockBehavior::default(), {
+        brown_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        green_bed => BlockBehavior::default(), {
+        green_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        red_bed => BlockBehavior::default(), {
+        red_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        black_bed => BlockBehavior::default(), {
+        black_bed => BlockBehavior::default().strength(0.2, 0.2), {
             facing: FacingCardinal::North,
             occupied: false,
             part: Part::Foot,
         },
-        powered_rail => BlockBehavior::default(), {
+        powered_rail => BlockBehavior::default().strength(0.7, 0.7), {
             powered: false,
             shape: RailShape::NorthSouth,
             waterlogged: false,
         },
-        detector_rail => BlockBehavior::default(), {
+        detector_rail => BlockBehavior::default().strength(0.7, 0.7), {
             powered: false,
             shape: RailShape::NorthSouth,
             waterlogged: false,
         },
-        sticky_piston => BlockBehavior::default(), {
+        sticky_piston => BlockBehavior::default().strength(1.5, 1.5), {
             extended: false,
             facing: FacingCubic::North,
         },
-        cobweb => BlockBehavior::default(), {},
+        cobweb => BlockBehavior::default().strength(4., 4.).requires_correct_tool_for_drops(), {},
         grass => BlockBehavior::default(), {},
         fern => BlockBehavior::default(), {},
         dead_bush => BlockBehavior::default(), {},
@@ -1913,32 +1913,32 @@ make_block_states! {
         tall_seagrass => BlockBehavior::default(), {
             half: Half::Lower,
         },
-        piston => BlockBehavior::default(), {
+        piston => BlockBehavior::default().strength(1.5, 1.5), {
             extended: false,
             facing: FacingCubic::North,
         },
-        piston_head => BlockBehavior::default(), {
+        piston_head => BlockBehavior::default().strength(1.5, 1.5), {
             kind: PistonType::Normal,
             facing: FacingCubic::North,
             short: false,
         },
-        white_wool => BlockBehavior::default(), {},
-        orange_wool => BlockBehavior::default(), {},
-        magenta_wool => BlockBehavior::default(), {},
-        light_blue_wool => BlockBehavior::default(), {},
-        yellow_wool => BlockBehavior::default(), {},
-        lime_wool => BlockBehavior::default(), {},
-        pink_wool => BlockBehavior::default(), {},
-        gray_wool => BlockBehavior::default(), {},
-        light_gray_wool => BlockBehavior::default(), {},
-        cyan_wool => BlockBehavior::default(), {},
-        purple_wool => BlockBehavior::default(), {},
-        blue_wool => BlockBehavior::default(), {},
-        brown_wool => BlockBehavior::default(), {},
-        green_wool => BlockBehavior::default(), {},
-        red_wool => BlockBehavior::default(), {},
-        black_wool => BlockBehavior::default(), {},
-        moving_piston => BlockBehavior::default(), {
+        white_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        orange_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        magenta_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        light_blue_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        yellow_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        lime_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        pink_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        gray_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        light_gray_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        cyan_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        purple_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        blue_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        brown_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        green_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        red_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        black_wool => BlockBehavior::default().strength(0.8, 0.8), {},
+        moving_piston => BlockBehavior::default().strength(-1., 0.), {
             kind: PistonType::Normal,
             facing: FacingCubic::North,
         },
@@ -1957,15 +1957,15 @@ make_block_states! {
         lily_of_the_valley => BlockBehavior::default(), {},
         brown_mushroom => BlockBehavior::default(), {},
         red_mushroom => BlockBehavior::default(), {},
-        gold_block => BlockBehavior::default(), {},
-        iron_block => BlockBehavior::default(), {},
-        bricks => BlockBehavior::default(), {},
+        gold_block => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        iron_block => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {},
+        bricks => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
         tnt => BlockBehavior::default(), {
             unstable: false,
         },
-        bookshelf => BlockBehavior::default(), {},
-        mossy_cobblestone => BlockBehavior::default(), {},
-        obsidian => BlockBehavior::default(), {},
+        bookshelf => BlockBehavior::default().strength(1.5, 1.5), {},
+        mossy_cobblestone => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        obsidian => BlockBehavior::default().strength(50., 1200.).requires_correct_tool_for_drops(), {},
         torch => BlockBehavior::default(), {},
         wall_torch => BlockBehavior::default(), {
             facing: FacingCardinal::North,
@@ -1979,14 +1979,14 @@ make_block_states! {
             west: false,
         },
         soul_fire => BlockBehavior::default(), {},
-        spawner => BlockBehavior::default(), {},
-        oak_stairs => BlockBehavior::default(), {
+        spawner => BlockBehavior::default().strength(5., 5.).requires_correct_tool_for_drops(), {},
+        oak_stairs => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        chest => BlockBehavior::default(), {
+        chest => BlockBehavior::default().strength(2.5, 2.5), {
             kind: ChestType::Single,
             facing: FacingCardinal::North,
             waterlogged: false,
@@ -1998,137 +1998,137 @@ make_block_states! {
             south: WireSouth::None,
             west: WireWest::None,
         },
-        diamond_ore => BlockBehavior::default(), {},
-        deepslate_diamond_ore => BlockBehavior::default(), {},
-        diamond_block => BlockBehavior::default(), {},
-        crafting_table => BlockBehavior::default(), {},
+        diamond_ore => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {},
+        deepslate_diamond_ore => BlockBehavior::default().strength(4.5, 3.).requires_correct_tool_for_drops(), {},
+        diamond_block => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {},
+        crafting_table => BlockBehavior::default().strength(2.5, 2.5), {},
         wheat => BlockBehavior::default(), {
             age: WheatAge::_0,
         },
-        farmland => BlockBehavior::default(), {
+        farmland => BlockBehavior::default().strength(0.6, 0.6), {
             moisture: FarmlandMoisture::_0,
         },
-        furnace => BlockBehavior::default(), {
+        furnace => BlockBehavior::default().strength(3.5, 3.5).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             lit: false,
         },
-        oak_sign => BlockBehavior::default(), {
+        oak_sign => BlockBehavior::default().strength(1., 1.), {
             rotation: OakSignRotation::_0,
             waterlogged: false,
         },
-        spruce_sign => BlockBehavior::default(), {
+        spruce_sign => BlockBehavior::default().strength(1., 1.), {
             rotation: SpruceSignRotation::_0,
             waterlogged: false,
         },
-        birch_sign => BlockBehavior::default(), {
+        birch_sign => BlockBehavior::default().strength(1., 1.), {
             rotation: BirchSignRotation::_0,
             waterlogged: false,
         },
-        acacia_sign => BlockBehavior::default(), {
+        acacia_sign => BlockBehavior::default().strength(1., 1.), {
             rotation: AcaciaSignRotation::_0,
             waterlogged: false,
         },
-        jungle_sign => BlockBehavior::default(), {
+        jungle_sign => BlockBehavior::default().strength(1., 1.), {
             rotation: JungleSignRotation::_0,
             waterlogged: false,
         },
-        dark_oak_sign => BlockBehavior::default(), {
+        dark_oak_sign => BlockBehavior::default().strength(1., 1.), {
             rotation: DarkOakSignRotation::_0,
             waterlogged: false,
         },
-        mangrove_sign => BlockBehavior::default(), {
+        mangrove_sign => BlockBehavior::default().strength(1., 1.), {
             rotation: MangroveSignRotation::_0,
             waterlogged: false,
         },
-        oak_door => BlockBehavior::default(), {
+        oak_door => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: Half::Lower,
             hinge: Hinge::Left,
             open: false,
             powered: false,
         },
-        ladder => BlockBehavior::default(), {
+        ladder => BlockBehavior::default().strength(0.4, 0.4), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        rail => BlockBehavior::default(), {
+        rail => BlockBehavior::default().strength(0.7, 0.7), {
             shape: Shape::NorthSouth,
             waterlogged: false,
         },
-        cobblestone_stairs => BlockBehavior::default(), {
+        cobblestone_stairs => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        oak_wall_sign => BlockBehavior::default(), {
+        oak_wall_sign => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        spruce_wall_sign => BlockBehavior::default(), {
+        spruce_wall_sign => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        birch_wall_sign => BlockBehavior::default(), {
+        birch_wall_sign => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        acacia_wall_sign => BlockBehavior::default(), {
+        acacia_wall_sign => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        jungle_wall_sign => BlockBehavior::default(), {
+        jungle_wall_sign => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        dark_oak_wall_sign => BlockBehavior::default(), {
+        dark_oak_wall_sign => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        mangrove_wall_sign => BlockBehavior::default(), {
+        mangrove_wall_sign => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        lever => BlockBehavior::default(), {
+        lever => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        stone_pressure_plate => BlockBehavior::default(), {
+        stone_pressure_plate => BlockBehavior::default().strength(0.5, 0.5).requires_correct_tool_for_drops(), {
             powered: false,
         },
-        iron_door => BlockBehavior::default(), {
+        iron_door => BlockBehavior::default().strength(5., 5.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: Half::Lower,
             hinge: Hinge::Left,
             open: false,
             powered: false,
         },
-        oak_pressure_plate => BlockBehavior::default(), {
+        oak_pressure_plate => BlockBehavior::default().strength(0.5, 0.5), {
             powered: false,
         },
-        spruce_pressure_plate => BlockBehavior::default(), {
+        spruce_pressure_plate => BlockBehavior::default().strength(0.5, 0.5), {
             powered: false,
         },
-        birch_pressure_plate => BlockBehavior::default(), {
+        birch_pressure_plate => BlockBehavior::default().strength(0.5, 0.5), {
             powered: false,
         },
-        jungle_pressure_plate => BlockBehavior::default(), {
+        jungle_pressure_plate => BlockBehavior::default().strength(0.5, 0.5), {
             powered: false,
         },
-        acacia_pressure_plate => BlockBehavior::default(), {
+        acacia_pressure_plate => BlockBehavior::default().strength(0.5, 0.5), {
             powered: false,
         },
-        dark_oak_pressure_plate => BlockBehavior::default(), {
+        dark_oak_pressure_plate => BlockBehavior::default().strength(0.5, 0.5), {
             powered: false,
         },
-        mangrove_pressure_plate => BlockBehavior::default(), {
+        mangrove_pressure_plate => BlockBehavior::default().strength(0.5, 0.5), {
             powered: false,
         },
-        redstone_ore => BlockBehavior::default(), {
+        redstone_ore => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {
             lit: false,
         },
-        deepslate_redstone_ore => BlockBehavior::default(), {
+        deepslate_redstone_ore => BlockBehavior::default().strength(4.5, 3.).requires_correct_tool_for_drops(), {
             lit: false,
         },
         redstone_torch => BlockBehavior::default(), {
@@ -2138,58 +2138,58 @@ make_block_states! {
             facing: FacingCardinal::North,
             lit: true,
         },
-        stone_button => BlockBehavior::default(), {
+        stone_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        snow => BlockBehavior::default(), {
+        snow => BlockBehavior::default().strength(0.1, 0.1).requires_correct_tool_for_drops(), {
             layers: SnowLayers::_1,
         },
-        ice => BlockBehavior::default(), {},
-        snow_block => BlockBehavior::default(), {},
-        cactus => BlockBehavior::default(), {
+        ice => BlockBehavior::default().strength(0.5, 0.5), {},
+        snow_block => BlockBehavior::default().strength(0.2, 0.2).requires_correct_tool_for_drops(), {},
+        cactus => BlockBehavior::default().strength(0.4, 0.4), {
             age: CactusAge::_0,
         },
-        clay => BlockBehavior::default(), {},
+        clay => BlockBehavior::default().strength(0.6, 0.6), {},
         sugar_cane => BlockBehavior::default(), {
             age: SugarCaneAge::_0,
         },
-        jukebox => BlockBehavior::default(), {
+        jukebox => BlockBehavior::default().strength(2., 6.), {
             has_record: false,
         },
-        oak_fence => BlockBehavior::default(), {
+        oak_fence => BlockBehavior::default().strength(2., 3.), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        pumpkin => BlockBehavior::default(), {},
-        netherrack => BlockBehavior::default(), {},
-        soul_sand => BlockBehavior::default(), {},
-        soul_soil => BlockBehavior::default(), {},
-        basalt => BlockBehavior::default(), {
+        pumpkin => BlockBehavior::default().strength(1., 1.), {},
+        netherrack => BlockBehavior::default().strength(0.4, 0.4).requires_correct_tool_for_drops(), {},
+        soul_sand => BlockBehavior::default().strength(0.5, 0.5).speed_factor(0.4), {},
+        soul_soil => BlockBehavior::default().strength(0.5, 0.5), {},
+        basalt => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {
             axis: Axis::Y,
         },
-        polished_basalt => BlockBehavior::default(), {
+        polished_basalt => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {
             axis: Axis::Y,
         },
         soul_torch => BlockBehavior::default(), {},
         soul_wall_torch => BlockBehavior::default(), {
             facing: FacingCardinal::North,
         },
-        glowstone => BlockBehavior::default(), {},
-        nether_portal => BlockBehavior::default(), {
+        glowstone => BlockBehavior::default().strength(0.3, 0.3), {},
+        nether_portal => BlockBehavior::default().strength(-1., 0.), {
             axis: AxisXZ::X,
         },
-        carved_pumpkin => BlockBehavior::default(), {
+        carved_pumpkin => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        jack_o_lantern => BlockBehavior::default(), {
+        jack_o_lantern => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        cake => BlockBehavior::default(), {
+        cake => BlockBehavior::default().strength(0.5, 0.5), {
             bites: CakeBites::_0,
         },
         repeater => BlockBehavior::default(), {
@@ -2198,84 +2198,84 @@ make_block_states! {
             locked: false,
             powered: false,
         },
-        white_stained_glass => BlockBehavior::default(), {},
-        orange_stained_glass => BlockBehavior::default(), {},
-        magenta_stained_glass => BlockBehavior::default(), {},
-        light_blue_stained_glass => BlockBehavior::default(), {},
-        yellow_stained_glass => BlockBehavior::default(), {},
-        lime_stained_glass => BlockBehavior::default(), {},
-        pink_stained_glass => BlockBehavior::default(), {},
-        gray_stained_glass => BlockBehavior::default(), {},
-        light_gray_stained_glass => BlockBehavior::default(), {},
-        cyan_stained_glass => BlockBehavior::default(), {},
-        purple_stained_glass => BlockBehavior::default(), {},
-        blue_stained_glass => BlockBehavior::default(), {},
-        brown_stained_glass => BlockBehavior::default(), {},
-        green_stained_glass => BlockBehavior::default(), {},
-        red_stained_glass => BlockBehavior::default(), {},
-        black_stained_glass => BlockBehavior::default(), {},
-        oak_trapdoor => BlockBehavior::default(), {
+        white_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        orange_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        magenta_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        light_blue_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        yellow_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        lime_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        pink_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        gray_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        light_gray_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        cyan_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        purple_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        blue_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        brown_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        green_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        red_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        black_stained_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        oak_trapdoor => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             open: false,
             powered: false,
             waterlogged: false,
         },
-        spruce_trapdoor => BlockBehavior::default(), {
+        spruce_trapdoor => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             open: false,
             powered: false,
             waterlogged: false,
         },
-        birch_trapdoor => BlockBehavior::default(), {
+        birch_trapdoor => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             open: false,
             powered: false,
             waterlogged: false,
         },
-        jungle_trapdoor => BlockBehavior::default(), {
+        jungle_trapdoor => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             open: false,
             powered: false,
             waterlogged: false,
         },
-        acacia_trapdoor => BlockBehavior::default(), {
+        acacia_trapdoor => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             open: false,
             powered: false,
             waterlogged: false,
         },
-        dark_oak_trapdoor => BlockBehavior::default(), {
+        dark_oak_trapdoor => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             open: false,
             powered: false,
             waterlogged: false,
         },
-        mangrove_trapdoor => BlockBehavior::default(), {
+        mangrove_trapdoor => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             open: false,
             powered: false,
             waterlogged: false,
         },
-        stone_bricks => BlockBehavior::default(), {},
-        mossy_stone_bricks => BlockBehavior::default(), {},
-        cracked_stone_bricks => BlockBehavior::default(), {},
-        chiseled_stone_bricks => BlockBehavior::default(), {},
-        packed_mud => BlockBehavior::default(), {},
-        mud_bricks => BlockBehavior::default(), {},
-        infested_stone => BlockBehavior::default(), {},
-        infested_cobblestone => BlockBehavior::default(), {},
-        infested_stone_bricks => BlockBehavior::default(), {},
-        infested_mossy_stone_bricks => BlockBehavior::default(), {},
-        infested_cracked_stone_bricks => BlockBehavior::default(), {},
-        infested_chiseled_stone_bricks => BlockBehavior::default(), {},
-        brown_mushroom_block => BlockBehavior::default(), {
+        stone_bricks => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        mossy_stone_bricks => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        cracked_stone_bricks => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        chiseled_stone_bricks => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        packed_mud => BlockBehavior::default().strength(1., 3.), {},
+        mud_bricks => BlockBehavior::default().strength(1.5, 3.).requires_correct_tool_for_drops(), {},
+        infested_stone => BlockBehavior::default().strength(0.75, 0.75), {},
+        infested_cobblestone => BlockBehavior::default().strength(1., 0.75), {},
+        infested_stone_bricks => BlockBehavior::default().strength(0.75, 0.75), {},
+        infested_mossy_stone_bricks => BlockBehavior::default().strength(0.75, 0.75), {},
+        infested_cracked_stone_bricks => BlockBehavior::default().strength(0.75, 0.75), {},
+        infested_chiseled_stone_bricks => BlockBehavior::default().strength(0.75, 0.75), {},
+        brown_mushroom_block => BlockBehavior::default().strength(0.2, 0.2), {
             down: true,
             east: true,
             north: true,
@@ -2283,7 +2283,7 @@ make_block_states! {
             up: true,
             west: true,
         },
-        red_mushroom_block => BlockBehavior::default(), {
+        red_mushroom_block => BlockBehavior::default().strength(0.2, 0.2), {
             down: true,
             east: true,
             north: true,
@@ -2291,7 +2291,7 @@ make_block_states! {
             up: true,
             west: true,
         },
-        mushroom_stem => BlockBehavior::default(), {
+        mushroom_stem => BlockBehavior::default().strength(0.2, 0.2), {
             down: true,
             east: true,
             north: true,
@@ -2299,25 +2299,25 @@ make_block_states! {
             up: true,
             west: true,
         },
-        iron_bars => BlockBehavior::default(), {
+        iron_bars => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        chain => BlockBehavior::default(), {
+        chain => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {
             axis: Axis::Y,
             waterlogged: false,
         },
-        glass_pane => BlockBehavior::default(), {
+        glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        melon => BlockBehavior::default(), {},
+        melon => BlockBehavior::default().strength(1., 1.), {},
         attached_pumpkin_stem => BlockBehavior::default(), {
             facing: FacingCardinal::North,
         },
@@ -2330,14 +2330,14 @@ make_block_states! {
         melon_stem => BlockBehavior::default(), {
             age: MelonStemAge::_0,
         },
-        vine => BlockBehavior::default(), {
+        vine => BlockBehavior::default().strength(0.2, 0.2), {
             east: false,
             north: false,
             south: false,
             up: false,
             west: false,
         },
-        glow_lichen => BlockBehavior::default(), {
+        glow_lichen => BlockBehavior::default().strength(0.2, 0.2), {
             down: false,
             east: false,
             north: false,
@@ -2346,43 +2346,43 @@ make_block_states! {
             waterlogged: false,
             west: false,
         },
-        oak_fence_gate => BlockBehavior::default(), {
+        oak_fence_gate => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             in_wall: false,
             open: false,
             powered: false,
         },
-        brick_stairs => BlockBehavior::default(), {
+        brick_stairs => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        stone_brick_stairs => BlockBehavior::default(), {
+        stone_brick_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        mud_brick_stairs => BlockBehavior::default(), {
+        mud_brick_stairs => BlockBehavior::default().strength(1.5, 3.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        mycelium => BlockBehavior::default(), {
+        mycelium => BlockBehavior::default().strength(0.6, 0.6), {
             snowy: false,
         },
         lily_pad => BlockBehavior::default(), {},
-        nether_bricks => BlockBehavior::default(), {},
-        nether_brick_fence => BlockBehavior::default(), {
+        nether_bricks => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        nether_brick_fence => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        nether_brick_stairs => BlockBehavior::default(), {
+        nether_brick_stairs => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
@@ -2391,43 +2391,43 @@ make_block_states! {
         nether_wart => BlockBehavior::default(), {
             age: NetherWartAge::_0,
         },
-        enchanting_table => BlockBehavior::default(), {},
-        brewing_stand => BlockBehavior::default(), {
+        enchanting_table => BlockBehavior::default().strength(5., 1200.).requires_correct_tool_for_drops(), {},
+        brewing_stand => BlockBehavior::default().strength(0.5, 0.5).requires_correct_tool_for_drops(), {
             has_bottle: false,
             has_bottle: false,
             has_bottle: false,
         },
-        cauldron => BlockBehavior::default(), {},
-        water_cauldron => BlockBehavior::default(), {
+        cauldron => BlockBehavior::default().strength(2., 2.).requires_correct_tool_for_drops(), {},
+        water_cauldron => BlockBehavior::default().strength(2., 2.).requires_correct_tool_for_drops(), {
             level: WaterCauldronLevel::_1,
         },
-        lava_cauldron => BlockBehavior::default(), {},
-        powder_snow_cauldron => BlockBehavior::default(), {
+        lava_cauldron => BlockBehavior::default().strength(2., 2.).requires_correct_tool_for_drops(), {},
+        powder_snow_cauldron => BlockBehavior::default().strength(2., 2.).requires_correct_tool_for_drops(), {
             level: PowderSnowCauldronLevel::_1,
         },
-        end_portal => BlockBehavior::default(), {},
-        end_portal_frame => BlockBehavior::default(), {
+        end_portal => BlockBehavior::default().strength(-1., 3600000.), {},
+        end_portal_frame => BlockBehavior::default().strength(-1., 3600000.), {
             eye: false,
             facing: FacingCardinal::North,
         },
-        end_stone => BlockBehavior::default(), {},
-        dragon_egg => BlockBehavior::default(), {},
-        redstone_lamp => BlockBehavior::default(), {
+        end_stone => BlockBehavior::default().strength(3., 9.).requires_correct_tool_for_drops(), {},
+        dragon_egg => BlockBehavior::default().strength(3., 9.), {},
+        redstone_lamp => BlockBehavior::default().strength(0.3, 0.3), {
             lit: false,
         },
-        cocoa => BlockBehavior::default(), {
+        cocoa => BlockBehavior::default().strength(0.2, 3.), {
             age: CocoaAge::_0,
             facing: FacingCardinal::North,
         },
-        sandstone_stairs => BlockBehavior::default(), {
+        sandstone_stairs => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        emerald_ore => BlockBehavior::default(), {},
-        deepslate_emerald_ore => BlockBehavior::default(), {},
-        ender_chest => BlockBehavior::default(), {
+        emerald_ore => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {},
+        deepslate_emerald_ore => BlockBehavior::default().strength(4.5, 3.).requires_correct_tool_for_drops(), {},
+        ender_chest => BlockBehavior::default().strength(22.5, 600.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
@@ -2445,31 +2445,31 @@ make_block_states! {
             south: false,
             west: false,
         },
-        emerald_block => BlockBehavior::default(), {},
-        spruce_stairs => BlockBehavior::default(), {
+        emerald_block => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {},
+        spruce_stairs => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        birch_stairs => BlockBehavior::default(), {
+        birch_stairs => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        jungle_stairs => BlockBehavior::default(), {
+        jungle_stairs => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        command_block => BlockBehavior::default(), {
+        command_block => BlockBehavior::default().strength(-1., 3600000.), {
             conditional: false,
             facing: FacingCubic::North,
         },
-        beacon => BlockBehavior::default(), {},
-        cobblestone_wall => BlockBehavior::default(), {
+        beacon => BlockBehavior::default().strength(3., 3.), {},
+        cobblestone_wall => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -2477,7 +2477,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        mossy_cobblestone_wall => BlockBehavior::default(), {
+        mossy_cobblestone_wall => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -2517,95 +2517,95 @@ make_block_states! {
         potatoes => BlockBehavior::default(), {
             age: PotatoesAge::_0,
         },
-        oak_button => BlockBehavior::default(), {
+        oak_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        spruce_button => BlockBehavior::default(), {
+        spruce_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        birch_button => BlockBehavior::default(), {
+        birch_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        jungle_button => BlockBehavior::default(), {
+        jungle_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        acacia_button => BlockBehavior::default(), {
+        acacia_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        dark_oak_button => BlockBehavior::default(), {
+        dark_oak_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        mangrove_button => BlockBehavior::default(), {
+        mangrove_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        skeleton_skull => BlockBehavior::default(), {
+        skeleton_skull => BlockBehavior::default().strength(1., 1.), {
             rotation: SkeletonSkullRotation::_0,
         },
-        skeleton_wall_skull => BlockBehavior::default(), {
+        skeleton_wall_skull => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        wither_skeleton_skull => BlockBehavior::default(), {
+        wither_skeleton_skull => BlockBehavior::default().strength(1., 1.), {
             rotation: WitherSkeletonSkullRotation::_0,
         },
-        wither_skeleton_wall_skull => BlockBehavior::default(), {
+        wither_skeleton_wall_skull => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        zombie_head => BlockBehavior::default(), {
+        zombie_head => BlockBehavior::default().strength(1., 1.), {
             rotation: ZombieHeadRotation::_0,
         },
-        zombie_wall_head => BlockBehavior::default(), {
+        zombie_wall_head => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        player_head => BlockBehavior::default(), {
+        player_head => BlockBehavior::default().strength(1., 1.), {
             rotation: PlayerHeadRotation::_0,
         },
-        player_wall_head => BlockBehavior::default(), {
+        player_wall_head => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        creeper_head => BlockBehavior::default(), {
+        creeper_head => BlockBehavior::default().strength(1., 1.), {
             rotation: CreeperHeadRotation::_0,
         },
-        creeper_wall_head => BlockBehavior::default(), {
+        creeper_wall_head => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        dragon_head => BlockBehavior::default(), {
+        dragon_head => BlockBehavior::default().strength(1., 1.), {
             rotation: DragonHeadRotation::_0,
         },
-        dragon_wall_head => BlockBehavior::default(), {
+        dragon_wall_head => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        anvil => BlockBehavior::default(), {
+        anvil => BlockBehavior::default().strength(5., 1200.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        chipped_anvil => BlockBehavior::default(), {
+        chipped_anvil => BlockBehavior::default().strength(5., 1200.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        damaged_anvil => BlockBehavior::default(), {
+        damaged_anvil => BlockBehavior::default().strength(5., 1200.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        trapped_chest => BlockBehavior::default(), {
+        trapped_chest => BlockBehavior::default().strength(2.5, 2.5), {
             kind: ChestType::Single,
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        light_weighted_pressure_plate => BlockBehavior::default(), {
+        light_weighted_pressure_plate => BlockBehavior::default().strength(0.5, 0.5).requires_correct_tool_for_drops(), {
             power: LightWeightedPressurePlatePower::_0,
         },
-        heavy_weighted_pressure_plate => BlockBehavior::default(), {
+        heavy_weighted_pressure_plate => BlockBehavior::default().strength(0.5, 0.5).requires_correct_tool_for_drops(), {
             power: HeavyWeightedPressurePlatePower::_0,
         },
         comparator => BlockBehavior::default(), {
@@ -2613,251 +2613,251 @@ make_block_states! {
             mode: ComparatorType::Compare,
             powered: false,
         },
-        daylight_detector => BlockBehavior::default(), {
+        daylight_detector => BlockBehavior::default().strength(0.2, 0.2), {
             inverted: false,
             power: DaylightDetectorPower::_0,
         },
-        redstone_block => BlockBehavior::default(), {},
-        nether_quartz_ore => BlockBehavior::default(), {},
-        hopper => BlockBehavior::default(), {
+        redstone_block => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {},
+        nether_quartz_ore => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {},
+        hopper => BlockBehavior::default().strength(3., 4.8).requires_correct_tool_for_drops(), {
             enabled: true,
             facing: Facing::Down,
         },
-        quartz_block => BlockBehavior::default(), {},
-        chiseled_quartz_block => BlockBehavior::default(), {},
-        quartz_pillar => BlockBehavior::default(), {
+        quartz_block => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {},
+        chiseled_quartz_block => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {},
+        quartz_pillar => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {
             axis: Axis::Y,
         },
-        quartz_stairs => BlockBehavior::default(), {
+        quartz_stairs => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        activator_rail => BlockBehavior::default(), {
+        activator_rail => BlockBehavior::default().strength(0.7, 0.7), {
             powered: false,
             shape: RailShape::NorthSouth,
             waterlogged: false,
         },
-        dropper => BlockBehavior::default(), {
+        dropper => BlockBehavior::default().strength(3.5, 3.5).requires_correct_tool_for_drops(), {
             facing: FacingCubic::North,
             triggered: false,
         },
-        white_terracotta => BlockBehavior::default(), {},
-        orange_terracotta => BlockBehavior::default(), {},
-        magenta_terracotta => BlockBehavior::default(), {},
-        light_blue_terracotta => BlockBehavior::default(), {},
-        yellow_terracotta => BlockBehavior::default(), {},
-        lime_terracotta => BlockBehavior::default(), {},
-        pink_terracotta => BlockBehavior::default(), {},
-        gray_terracotta => BlockBehavior::default(), {},
-        light_gray_terracotta => BlockBehavior::default(), {},
-        cyan_terracotta => BlockBehavior::default(), {},
-        purple_terracotta => BlockBehavior::default(), {},
-        blue_terracotta => BlockBehavior::default(), {},
-        brown_terracotta => BlockBehavior::default(), {},
-        green_terracotta => BlockBehavior::default(), {},
-        red_terracotta => BlockBehavior::default(), {},
-        black_terracotta => BlockBehavior::default(), {},
-        white_stained_glass_pane => BlockBehavior::default(), {
+        white_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        orange_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        magenta_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        light_blue_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        yellow_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        lime_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        pink_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        gray_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        light_gray_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        cyan_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        purple_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        blue_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        brown_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        green_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        red_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        black_terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        white_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        orange_stained_glass_pane => BlockBehavior::default(), {
+        orange_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        magenta_stained_glass_pane => BlockBehavior::default(), {
+        magenta_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        light_blue_stained_glass_pane => BlockBehavior::default(), {
+        light_blue_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        yellow_stained_glass_pane => BlockBehavior::default(), {
+        yellow_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        lime_stained_glass_pane => BlockBehavior::default(), {
+        lime_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        pink_stained_glass_pane => BlockBehavior::default(), {
+        pink_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        gray_stained_glass_pane => BlockBehavior::default(), {
+        gray_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        light_gray_stained_glass_pane => BlockBehavior::default(), {
+        light_gray_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        cyan_stained_glass_pane => BlockBehavior::default(), {
+        cyan_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        purple_stained_glass_pane => BlockBehavior::default(), {
+        purple_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        blue_stained_glass_pane => BlockBehavior::default(), {
+        blue_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        brown_stained_glass_pane => BlockBehavior::default(), {
+        brown_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        green_stained_glass_pane => BlockBehavior::default(), {
+        green_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        red_stained_glass_pane => BlockBehavior::default(), {
+        red_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        black_stained_glass_pane => BlockBehavior::default(), {
+        black_stained_glass_pane => BlockBehavior::default().strength(0.3, 0.3), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        acacia_stairs => BlockBehavior::default(), {
+        acacia_stairs => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        dark_oak_stairs => BlockBehavior::default(), {
+        dark_oak_stairs => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        mangrove_stairs => BlockBehavior::default(), {
+        mangrove_stairs => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
         slime_block => BlockBehavior::default(), {},
-        barrier => BlockBehavior::default(), {},
-        light => BlockBehavior::default(), {
+        barrier => BlockBehavior::default().strength(-1., 3600000.8), {},
+        light => BlockBehavior::default().strength(-1., 3600000.8), {
             level: LightLevel::_15,
             waterlogged: false,
         },
-        iron_trapdoor => BlockBehavior::default(), {
+        iron_trapdoor => BlockBehavior::default().strength(5., 5.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             open: false,
             powered: false,
             waterlogged: false,
         },
-        prismarine => BlockBehavior::default(), {},
-        prismarine_bricks => BlockBehavior::default(), {},
-        dark_prismarine => BlockBehavior::default(), {},
-        prismarine_stairs => BlockBehavior::default(), {
+        prismarine => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        prismarine_bricks => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        dark_prismarine => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        prismarine_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        prismarine_brick_stairs => BlockBehavior::default(), {
+        prismarine_brick_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        dark_prismarine_stairs => BlockBehavior::default(), {
+        dark_prismarine_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        prismarine_slab => BlockBehavior::default(), {
+        prismarine_slab => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        prismarine_brick_slab => BlockBehavior::default(), {
+        prismarine_brick_slab => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        dark_prismarine_slab => BlockBehavior::default(), {
+        dark_prismarine_slab => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        sea_lantern => BlockBehavior::default(), {},
-        hay_block => BlockBehavior::default(), {
+        sea_lantern => BlockBehavior::default().strength(0.3, 0.3), {},
+        hay_block => BlockBehavior::default().strength(0.5, 0.5), {
             axis: Axis::Y,
         },
-        white_carpet => BlockBehavior::default(), {},
-        orange_carpet => BlockBehavior::default(), {},
-        magenta_carpet => BlockBehavior::default(), {},
-        light_blue_carpet => BlockBehavior::default(), {},
-        yellow_carpet => BlockBehavior::default(), {},
-        lime_carpet => BlockBehavior::default(), {},
-        pink_carpet => BlockBehavior::default(), {},
-        gray_carpet => BlockBehavior::default(), {},
-        light_gray_carpet => BlockBehavior::default(), {},
-        cyan_carpet => BlockBehavior::default(), {},
-        purple_carpet => BlockBehavior::default(), {},
-        blue_carpet => BlockBehavior::default(), {},
-        brown_carpet => BlockBehavior::default(), {},
-        green_carpet => BlockBehavior::default(), {},
-        red_carpet => BlockBehavior::default(), {},
-        black_carpet => BlockBehavior::default(), {},
-        terracotta => BlockBehavior::default(), {},
-        coal_block => BlockBehavior::default(), {},
-        packed_ice => BlockBehavior::default(), {},
+        white_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        orange_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        magenta_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        light_blue_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        yellow_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        lime_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        pink_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        gray_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        light_gray_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        cyan_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        purple_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        blue_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        brown_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        green_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        red_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        black_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        terracotta => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        coal_block => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {},
+        packed_ice => BlockBehavior::default().strength(0.5, 0.5), {},
         sunflower => BlockBehavior::default(), {
             half: Half::Lower,
         },
@@ -2876,313 +2876,313 @@ make_block_states! {
         large_fern => BlockBehavior::default(), {
             half: Half::Lower,
         },
-        white_banner => BlockBehavior::default(), {
+        white_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: WhiteBannerRotation::_0,
         },
-        orange_banner => BlockBehavior::default(), {
+        orange_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: OrangeBannerRotation::_0,
         },
-        magenta_banner => BlockBehavior::default(), {
+        magenta_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: MagentaBannerRotation::_0,
         },
-        light_blue_banner => BlockBehavior::default(), {
+        light_blue_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: LightBlueBannerRotation::_0,
         },
-        yellow_banner => BlockBehavior::default(), {
+        yellow_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: YellowBannerRotation::_0,
         },
-        lime_banner => BlockBehavior::default(), {
+        lime_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: LimeBannerRotation::_0,
         },
-        pink_banner => BlockBehavior::default(), {
+        pink_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: PinkBannerRotation::_0,
         },
-        gray_banner => BlockBehavior::default(), {
+        gray_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: GrayBannerRotation::_0,
         },
-        light_gray_banner => BlockBehavior::default(), {
+        light_gray_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: LightGrayBannerRotation::_0,
         },
-        cyan_banner => BlockBehavior::default(), {
+        cyan_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: CyanBannerRotation::_0,
         },
-        purple_banner => BlockBehavior::default(), {
+        purple_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: PurpleBannerRotation::_0,
         },
-        blue_banner => BlockBehavior::default(), {
+        blue_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: BlueBannerRotation::_0,
         },
-        brown_banner => BlockBehavior::default(), {
+        brown_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: BrownBannerRotation::_0,
         },
-        green_banner => BlockBehavior::default(), {
+        green_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: GreenBannerRotation::_0,
         },
-        red_banner => BlockBehavior::default(), {
+        red_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: RedBannerRotation::_0,
         },
-        black_banner => BlockBehavior::default(), {
+        black_banner => BlockBehavior::default().strength(1., 1.), {
             rotation: BlackBannerRotation::_0,
         },
-        white_wall_banner => BlockBehavior::default(), {
+        white_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        orange_wall_banner => BlockBehavior::default(), {
+        orange_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        magenta_wall_banner => BlockBehavior::default(), {
+        magenta_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        light_blue_wall_banner => BlockBehavior::default(), {
+        light_blue_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        yellow_wall_banner => BlockBehavior::default(), {
+        yellow_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        lime_wall_banner => BlockBehavior::default(), {
+        lime_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        pink_wall_banner => BlockBehavior::default(), {
+        pink_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        gray_wall_banner => BlockBehavior::default(), {
+        gray_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        light_gray_wall_banner => BlockBehavior::default(), {
+        light_gray_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        cyan_wall_banner => BlockBehavior::default(), {
+        cyan_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        purple_wall_banner => BlockBehavior::default(), {
+        purple_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        blue_wall_banner => BlockBehavior::default(), {
+        blue_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        brown_wall_banner => BlockBehavior::default(), {
+        brown_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        green_wall_banner => BlockBehavior::default(), {
+        green_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        red_wall_banner => BlockBehavior::default(), {
+        red_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        black_wall_banner => BlockBehavior::default(), {
+        black_wall_banner => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
         },
-        red_sandstone => BlockBehavior::default(), {},
-        chiseled_red_sandstone => BlockBehavior::default(), {},
-        cut_red_sandstone => BlockBehavior::default(), {},
-        red_sandstone_stairs => BlockBehavior::default(), {
+        red_sandstone => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {},
+        chiseled_red_sandstone => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {},
+        cut_red_sandstone => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {},
+        red_sandstone_stairs => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        oak_slab => BlockBehavior::default(), {
+        oak_slab => BlockBehavior::default().strength(2., 3.), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        spruce_slab => BlockBehavior::default(), {
+        spruce_slab => BlockBehavior::default().strength(2., 3.), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        birch_slab => BlockBehavior::default(), {
+        birch_slab => BlockBehavior::default().strength(2., 3.), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        jungle_slab => BlockBehavior::default(), {
+        jungle_slab => BlockBehavior::default().strength(2., 3.), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        acacia_slab => BlockBehavior::default(), {
+        acacia_slab => BlockBehavior::default().strength(2., 3.), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        dark_oak_slab => BlockBehavior::default(), {
+        dark_oak_slab => BlockBehavior::default().strength(2., 3.), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        mangrove_slab => BlockBehavior::default(), {
+        mangrove_slab => BlockBehavior::default().strength(2., 3.), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        stone_slab => BlockBehavior::default(), {
+        stone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        smooth_stone_slab => BlockBehavior::default(), {
+        smooth_stone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        sandstone_slab => BlockBehavior::default(), {
+        sandstone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        cut_sandstone_slab => BlockBehavior::default(), {
+        cut_sandstone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        petrified_oak_slab => BlockBehavior::default(), {
+        petrified_oak_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        cobblestone_slab => BlockBehavior::default(), {
+        cobblestone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        brick_slab => BlockBehavior::default(), {
+        brick_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        stone_brick_slab => BlockBehavior::default(), {
+        stone_brick_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        mud_brick_slab => BlockBehavior::default(), {
+        mud_brick_slab => BlockBehavior::default().strength(1.5, 3.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        nether_brick_slab => BlockBehavior::default(), {
+        nether_brick_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        quartz_slab => BlockBehavior::default(), {
+        quartz_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        red_sandstone_slab => BlockBehavior::default(), {
+        red_sandstone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        cut_red_sandstone_slab => BlockBehavior::default(), {
+        cut_red_sandstone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        purpur_slab => BlockBehavior::default(), {
+        purpur_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        smooth_stone => BlockBehavior::default(), {},
-        smooth_sandstone => BlockBehavior::default(), {},
-        smooth_quartz => BlockBehavior::default(), {},
-        smooth_red_sandstone => BlockBehavior::default(), {},
-        spruce_fence_gate => BlockBehavior::default(), {
+        smooth_stone => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        smooth_sandstone => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        smooth_quartz => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        smooth_red_sandstone => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        spruce_fence_gate => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             in_wall: false,
             open: false,
             powered: false,
         },
-        birch_fence_gate => BlockBehavior::default(), {
+        birch_fence_gate => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             in_wall: false,
             open: false,
             powered: false,
         },
-        jungle_fence_gate => BlockBehavior::default(), {
+        jungle_fence_gate => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             in_wall: false,
             open: false,
             powered: false,
         },
-        acacia_fence_gate => BlockBehavior::default(), {
+        acacia_fence_gate => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             in_wall: false,
             open: false,
             powered: false,
         },
-        dark_oak_fence_gate => BlockBehavior::default(), {
+        dark_oak_fence_gate => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             in_wall: false,
             open: false,
             powered: false,
         },
-        mangrove_fence_gate => BlockBehavior::default(), {
+        mangrove_fence_gate => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             in_wall: false,
             open: false,
             powered: false,
         },
-        spruce_fence => BlockBehavior::default(), {
+        spruce_fence => BlockBehavior::default().strength(2., 3.), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        birch_fence => BlockBehavior::default(), {
+        birch_fence => BlockBehavior::default().strength(2., 3.), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        jungle_fence => BlockBehavior::default(), {
+        jungle_fence => BlockBehavior::default().strength(2., 3.), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        acacia_fence => BlockBehavior::default(), {
+        acacia_fence => BlockBehavior::default().strength(2., 3.), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        dark_oak_fence => BlockBehavior::default(), {
+        dark_oak_fence => BlockBehavior::default().strength(2., 3.), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        mangrove_fence => BlockBehavior::default(), {
+        mangrove_fence => BlockBehavior::default().strength(2., 3.), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        spruce_door => BlockBehavior::default(), {
+        spruce_door => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: Half::Lower,
             hinge: Hinge::Left,
             open: false,
             powered: false,
         },
-        birch_door => BlockBehavior::default(), {
+        birch_door => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: Half::Lower,
             hinge: Hinge::Left,
             open: false,
             powered: false,
         },
-        jungle_door => BlockBehavior::default(), {
+        jungle_door => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: Half::Lower,
             hinge: Hinge::Left,
             open: false,
             powered: false,
         },
-        acacia_door => BlockBehavior::default(), {
+        acacia_door => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: Half::Lower,
             hinge: Hinge::Left,
             open: false,
             powered: false,
         },
-        dark_oak_door => BlockBehavior::default(), {
+        dark_oak_door => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: Half::Lower,
             hinge: Hinge::Left,
             open: false,
             powered: false,
         },
-        mangrove_door => BlockBehavior::default(), {
+        mangrove_door => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: Half::Lower,
             hinge: Hinge::Left,
@@ -3192,7 +3192,7 @@ make_block_states! {
         end_rod => BlockBehavior::default(), {
             facing: FacingCubic::Up,
         },
-        chorus_plant => BlockBehavior::default(), {
+        chorus_plant => BlockBehavior::default().strength(0.4, 0.4), {
             down: false,
             east: false,
             north: false,
@@ -3200,197 +3200,197 @@ make_block_states! {
             up: false,
             west: false,
         },
-        chorus_flower => BlockBehavior::default(), {
+        chorus_flower => BlockBehavior::default().strength(0.4, 0.4), {
             age: ChorusFlowerAge::_0,
         },
-        purpur_block => BlockBehavior::default(), {},
-        purpur_pillar => BlockBehavior::default(), {
+        purpur_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        purpur_pillar => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             axis: Axis::Y,
         },
-        purpur_stairs => BlockBehavior::default(), {
+        purpur_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        end_stone_bricks => BlockBehavior::default(), {},
+        end_stone_bricks => BlockBehavior::default().strength(3., 9.).requires_correct_tool_for_drops(), {},
         beetroots => BlockBehavior::default(), {
             age: BeetrootsAge::_0,
         },
-        dirt_path => BlockBehavior::default(), {},
-        end_gateway => BlockBehavior::default(), {},
-        repeating_command_block => BlockBehavior::default(), {
+        dirt_path => BlockBehavior::default().strength(0.65, 0.65), {},
+        end_gateway => BlockBehavior::default().strength(-1., 3600000.), {},
+        repeating_command_block => BlockBehavior::default().strength(-1., 3600000.), {
             conditional: false,
             facing: FacingCubic::North,
         },
-        chain_command_block => BlockBehavior::default(), {
+        chain_command_block => BlockBehavior::default().strength(-1., 3600000.), {
             conditional: false,
             facing: FacingCubic::North,
         },
-        frosted_ice => BlockBehavior::default(), {
+        frosted_ice => BlockBehavior::default().strength(0.5, 0.5), {
             age: FrostedIceAge::_0,
         },
-        magma_block => BlockBehavior::default(), {},
-        nether_wart_block => BlockBehavior::default(), {},
-        red_nether_bricks => BlockBehavior::default(), {},
-        bone_block => BlockBehavior::default(), {
+        magma_block => BlockBehavior::default().strength(0.5, 0.5).requires_correct_tool_for_drops(), {},
+        nether_wart_block => BlockBehavior::default().strength(1., 1.), {},
+        red_nether_bricks => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        bone_block => BlockBehavior::default().strength(2., 2.).requires_correct_tool_for_drops(), {
             axis: Axis::Y,
         },
         structure_void => BlockBehavior::default(), {},
-        observer => BlockBehavior::default(), {
+        observer => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {
             facing: FacingCubic::South,
             powered: false,
         },
-        shulker_box => BlockBehavior::default(), {
+        shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        white_shulker_box => BlockBehavior::default(), {
+        white_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        orange_shulker_box => BlockBehavior::default(), {
+        orange_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        magenta_shulker_box => BlockBehavior::default(), {
+        magenta_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        light_blue_shulker_box => BlockBehavior::default(), {
+        light_blue_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        yellow_shulker_box => BlockBehavior::default(), {
+        yellow_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        lime_shulker_box => BlockBehavior::default(), {
+        lime_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        pink_shulker_box => BlockBehavior::default(), {
+        pink_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        gray_shulker_box => BlockBehavior::default(), {
+        gray_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        light_gray_shulker_box => BlockBehavior::default(), {
+        light_gray_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        cyan_shulker_box => BlockBehavior::default(), {
+        cyan_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        purple_shulker_box => BlockBehavior::default(), {
+        purple_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        blue_shulker_box => BlockBehavior::default(), {
+        blue_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        brown_shulker_box => BlockBehavior::default(), {
+        brown_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        green_shulker_box => BlockBehavior::default(), {
+        green_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        red_shulker_box => BlockBehavior::default(), {
+        red_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        black_shulker_box => BlockBehavior::default(), {
+        black_shulker_box => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCubic::Up,
         },
-        white_glazed_terracotta => BlockBehavior::default(), {
+        white_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        orange_glazed_terracotta => BlockBehavior::default(), {
+        orange_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        magenta_glazed_terracotta => BlockBehavior::default(), {
+        magenta_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        light_blue_glazed_terracotta => BlockBehavior::default(), {
+        light_blue_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        yellow_glazed_terracotta => BlockBehavior::default(), {
+        yellow_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        lime_glazed_terracotta => BlockBehavior::default(), {
+        lime_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        pink_glazed_terracotta => BlockBehavior::default(), {
+        pink_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        gray_glazed_terracotta => BlockBehavior::default(), {
+        gray_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        light_gray_glazed_terracotta => BlockBehavior::default(), {
+        light_gray_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        cyan_glazed_terracotta => BlockBehavior::default(), {
+        cyan_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        purple_glazed_terracotta => BlockBehavior::default(), {
+        purple_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        blue_glazed_terracotta => BlockBehavior::default(), {
+        blue_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        brown_glazed_terracotta => BlockBehavior::default(), {
+        brown_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        green_glazed_terracotta => BlockBehavior::default(), {
+        green_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        red_glazed_terracotta => BlockBehavior::default(), {
+        red_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        black_glazed_terracotta => BlockBehavior::default(), {
+        black_glazed_terracotta => BlockBehavior::default().strength(1.4, 1.4).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        white_concrete => BlockBehavior::default(), {},
-        orange_concrete => BlockBehavior::default(), {},
-        magenta_concrete => BlockBehavior::default(), {},
-        light_blue_concrete => BlockBehavior::default(), {},
-        yellow_concrete => BlockBehavior::default(), {},
-        lime_concrete => BlockBehavior::default(), {},
-        pink_concrete => BlockBehavior::default(), {},
-        gray_concrete => BlockBehavior::default(), {},
-        light_gray_concrete => BlockBehavior::default(), {},
-        cyan_concrete => BlockBehavior::default(), {},
-        purple_concrete => BlockBehavior::default(), {},
-        blue_concrete => BlockBehavior::default(), {},
-        brown_concrete => BlockBehavior::default(), {},
-        green_concrete => BlockBehavior::default(), {},
-        red_concrete => BlockBehavior::default(), {},
-        black_concrete => BlockBehavior::default(), {},
-        white_concrete_powder => BlockBehavior::default(), {},
-        orange_concrete_powder => BlockBehavior::default(), {},
-        magenta_concrete_powder => BlockBehavior::default(), {},
-        light_blue_concrete_powder => BlockBehavior::default(), {},
-        yellow_concrete_powder => BlockBehavior::default(), {},
-        lime_concrete_powder => BlockBehavior::default(), {},
-        pink_concrete_powder => BlockBehavior::default(), {},
-        gray_concrete_powder => BlockBehavior::default(), {},
-        light_gray_concrete_powder => BlockBehavior::default(), {},
-        cyan_concrete_powder => BlockBehavior::default(), {},
-        purple_concrete_powder => BlockBehavior::default(), {},
-        blue_concrete_powder => BlockBehavior::default(), {},
-        brown_concrete_powder => BlockBehavior::default(), {},
-        green_concrete_powder => BlockBehavior::default(), {},
-        red_concrete_powder => BlockBehavior::default(), {},
-        black_concrete_powder => BlockBehavior::default(), {},
+        white_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        orange_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        magenta_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        light_blue_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        yellow_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        lime_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        pink_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        gray_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        light_gray_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        cyan_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        purple_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        blue_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        brown_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        green_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        red_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        black_concrete => BlockBehavior::default().strength(1.8, 1.8).requires_correct_tool_for_drops(), {},
+        white_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        orange_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        magenta_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        light_blue_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        yellow_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        lime_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        pink_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        gray_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        light_gray_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        cyan_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        purple_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        blue_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        brown_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        green_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        red_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
+        black_concrete_powder => BlockBehavior::default().strength(0.5, 0.5), {},
         kelp => BlockBehavior::default(), {
             age: KelpAge::_0,
         },
         kelp_plant => BlockBehavior::default(), {},
-        dried_kelp_block => BlockBehavior::default(), {},
-        turtle_egg => BlockBehavior::default(), {
+        dried_kelp_block => BlockBehavior::default().strength(0.5, 2.5), {},
+        turtle_egg => BlockBehavior::default().strength(0.5, 0.5), {
             eggs: TurtleEggEggs::_1,
             hatch: TurtleEggHatch::_0,
         },
-        dead_tube_coral_block => BlockBehavior::default(), {},
-        dead_brain_coral_block => BlockBehavior::default(), {},
-        dead_bubble_coral_block => BlockBehavior::default(), {},
-        dead_fire_coral_block => BlockBehavior::default(), {},
-        dead_horn_coral_block => BlockBehavior::default(), {},
-        tube_coral_block => BlockBehavior::default(), {},
-        brain_coral_block => BlockBehavior::default(), {},
-        bubble_coral_block => BlockBehavior::default(), {},
-        fire_coral_block => BlockBehavior::default(), {},
-        horn_coral_block => BlockBehavior::default(), {},
+        dead_tube_coral_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        dead_brain_coral_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        dead_bubble_coral_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        dead_fire_coral_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        dead_horn_coral_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        tube_coral_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        brain_coral_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        bubble_coral_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        fire_coral_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        horn_coral_block => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
         dead_tube_coral => BlockBehavior::default(), {
             waterlogged: true,
         },
@@ -3495,12 +3495,12 @@ make_block_states! {
             pickles: SeaPicklePickles::_1,
             waterlogged: true,
         },
-        blue_ice => BlockBehavior::default(), {},
-        conduit => BlockBehavior::default(), {
+        blue_ice => BlockBehavior::default().strength(2.8, 2.8), {},
+        conduit => BlockBehavior::default().strength(3., 3.), {
             waterlogged: true,
         },
-        bamboo_sapling => BlockBehavior::default(), {},
-        bamboo => BlockBehavior::default(), {
+        bamboo_sapling => BlockBehavior::default().strength(1., 1.), {},
+        bamboo => BlockBehavior::default().strength(1., 1.), {
             age: BambooAge::_0,
             leaves: Leaves::None,
             stage: BambooStage::_0,
@@ -3511,143 +3511,143 @@ make_block_states! {
         bubble_column => BlockBehavior::default(), {
             drag: true,
         },
-        polished_granite_stairs => BlockBehavior::default(), {
+        polished_granite_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        smooth_red_sandstone_stairs => BlockBehavior::default(), {
+        smooth_red_sandstone_stairs => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        mossy_stone_brick_stairs => BlockBehavior::default(), {
+        mossy_stone_brick_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        polished_diorite_stairs => BlockBehavior::default(), {
+        polished_diorite_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        mossy_cobblestone_stairs => BlockBehavior::default(), {
+        mossy_cobblestone_stairs => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        end_stone_brick_stairs => BlockBehavior::default(), {
+        end_stone_brick_stairs => BlockBehavior::default().strength(3., 9.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        stone_stairs => BlockBehavior::default(), {
+        stone_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        smooth_sandstone_stairs => BlockBehavior::default(), {
+        smooth_sandstone_stairs => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        smooth_quartz_stairs => BlockBehavior::default(), {
+        smooth_quartz_stairs => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        granite_stairs => BlockBehavior::default(), {
+        granite_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        andesite_stairs => BlockBehavior::default(), {
+        andesite_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        red_nether_brick_stairs => BlockBehavior::default(), {
+        red_nether_brick_stairs => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        polished_andesite_stairs => BlockBehavior::default(), {
+        polished_andesite_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        diorite_stairs => BlockBehavior::default(), {
+        diorite_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        polished_granite_slab => BlockBehavior::default(), {
+        polished_granite_slab => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        smooth_red_sandstone_slab => BlockBehavior::default(), {
+        smooth_red_sandstone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        mossy_stone_brick_slab => BlockBehavior::default(), {
+        mossy_stone_brick_slab => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        polished_diorite_slab => BlockBehavior::default(), {
+        polished_diorite_slab => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        mossy_cobblestone_slab => BlockBehavior::default(), {
+        mossy_cobblestone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        end_stone_brick_slab => BlockBehavior::default(), {
+        end_stone_brick_slab => BlockBehavior::default().strength(3., 9.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        smooth_sandstone_slab => BlockBehavior::default(), {
+        smooth_sandstone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        smooth_quartz_slab => BlockBehavior::default(), {
+        smooth_quartz_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        granite_slab => BlockBehavior::default(), {
+        granite_slab => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        andesite_slab => BlockBehavior::default(), {
+        andesite_slab => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        red_nether_brick_slab => BlockBehavior::default(), {
+        red_nether_brick_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        polished_andesite_slab => BlockBehavior::default(), {
+        polished_andesite_slab => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        diorite_slab => BlockBehavior::default(), {
+        diorite_slab => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        brick_wall => BlockBehavior::default(), {
+        brick_wall => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3655,7 +3655,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        prismarine_wall => BlockBehavior::default(), {
+        prismarine_wall => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3663,7 +3663,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        red_sandstone_wall => BlockBehavior::default(), {
+        red_sandstone_wall => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3671,7 +3671,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        mossy_stone_brick_wall => BlockBehavior::default(), {
+        mossy_stone_brick_wall => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3679,7 +3679,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        granite_wall => BlockBehavior::default(), {
+        granite_wall => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3687,7 +3687,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        stone_brick_wall => BlockBehavior::default(), {
+        stone_brick_wall => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3695,7 +3695,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        mud_brick_wall => BlockBehavior::default(), {
+        mud_brick_wall => BlockBehavior::default().strength(1.5, 3.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3703,7 +3703,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        nether_brick_wall => BlockBehavior::default(), {
+        nether_brick_wall => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3711,7 +3711,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        andesite_wall => BlockBehavior::default(), {
+        andesite_wall => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3719,7 +3719,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        red_nether_brick_wall => BlockBehavior::default(), {
+        red_nether_brick_wall => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3727,7 +3727,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        sandstone_wall => BlockBehavior::default(), {
+        sandstone_wall => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3735,7 +3735,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        end_stone_brick_wall => BlockBehavior::default(), {
+        end_stone_brick_wall => BlockBehavior::default().strength(3., 9.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3743,7 +3743,7 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        diorite_wall => BlockBehavior::default(), {
+        diorite_wall => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -3756,56 +3756,56 @@ make_block_states! {
             distance: ScaffoldingDistance::_7,
             waterlogged: false,
         },
-        loom => BlockBehavior::default(), {
+        loom => BlockBehavior::default().strength(2.5, 2.5), {
             facing: FacingCardinal::North,
         },
-        barrel => BlockBehavior::default(), {
+        barrel => BlockBehavior::default().strength(2.5, 2.5), {
             facing: FacingCubic::North,
             open: false,
         },
-        smoker => BlockBehavior::default(), {
+        smoker => BlockBehavior::default().strength(3.5, 3.5).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             lit: false,
         },
-        blast_furnace => BlockBehavior::default(), {
+        blast_furnace => BlockBehavior::default().strength(3.5, 3.5).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             lit: false,
         },
-        cartography_table => BlockBehavior::default(), {},
-        fletching_table => BlockBehavior::default(), {},
-        grindstone => BlockBehavior::default(), {
+        cartography_table => BlockBehavior::default().strength(2.5, 2.5), {},
+        fletching_table => BlockBehavior::default().strength(2.5, 2.5), {},
+        grindstone => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             face: Face::Wall,
             facing: FacingCardinal::North,
         },
-        lectern => BlockBehavior::default(), {
+        lectern => BlockBehavior::default().strength(2.5, 2.5), {
             facing: FacingCardinal::North,
             has_book: false,
             powered: false,
         },
-        smithing_table => BlockBehavior::default(), {},
-        stonecutter => BlockBehavior::default(), {
+        smithing_table => BlockBehavior::default().strength(2.5, 2.5), {},
+        stonecutter => BlockBehavior::default().strength(3.5, 3.5).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
         },
-        bell => BlockBehavior::default(), {
+        bell => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {
             attachment: Attachment::Floor,
             facing: FacingCardinal::North,
             powered: false,
         },
-        lantern => BlockBehavior::default(), {
+        lantern => BlockBehavior::default().strength(3.5, 3.5).requires_correct_tool_for_drops(), {
             hanging: false,
             waterlogged: false,
         },
-        soul_lantern => BlockBehavior::default(), {
+        soul_lantern => BlockBehavior::default().strength(3.5, 3.5).requires_correct_tool_for_drops(), {
             hanging: false,
             waterlogged: false,
         },
-        campfire => BlockBehavior::default(), {
+        campfire => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCardinal::North,
             lit: true,
             signal_fire: false,
             waterlogged: false,
         },
-        soul_campfire => BlockBehavior::default(), {
+        soul_campfire => BlockBehavior::default().strength(2., 2.), {
             facing: FacingCardinal::North,
             lit: true,
             signal_fire: false,
@@ -3814,38 +3814,38 @@ make_block_states! {
         sweet_berry_bush => BlockBehavior::default(), {
             age: SweetBerryBushAge::_0,
         },
-        warped_stem => BlockBehavior::default(), {
+        warped_stem => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_warped_stem => BlockBehavior::default(), {
+        stripped_warped_stem => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        warped_hyphae => BlockBehavior::default(), {
+        warped_hyphae => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_warped_hyphae => BlockBehavior::default(), {
+        stripped_warped_hyphae => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        warped_nylium => BlockBehavior::default(), {},
+        warped_nylium => BlockBehavior::default().strength(0.4, 0.4).requires_correct_tool_for_drops(), {},
         warped_fungus => BlockBehavior::default(), {},
-        warped_wart_block => BlockBehavior::default(), {},
+        warped_wart_block => BlockBehavior::default().strength(1., 1.), {},
         warped_roots => BlockBehavior::default(), {},
         nether_sprouts => BlockBehavior::default(), {},
-        crimson_stem => BlockBehavior::default(), {
+        crimson_stem => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_crimson_stem => BlockBehavior::default(), {
+        stripped_crimson_stem => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        crimson_hyphae => BlockBehavior::default(), {
+        crimson_hyphae => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        stripped_crimson_hyphae => BlockBehavior::default(), {
+        stripped_crimson_hyphae => BlockBehavior::default().strength(2., 2.), {
             axis: Axis::Y,
         },
-        crimson_nylium => BlockBehavior::default(), {},
+        crimson_nylium => BlockBehavior::default().strength(0.4, 0.4).requires_correct_tool_for_drops(), {},
         crimson_fungus => BlockBehavior::default(), {},
-        shroomlight => BlockBehavior::default(), {},
+        shroomlight => BlockBehavior::default().strength(1., 1.), {},
         weeping_vines => BlockBehavior::default(), {
             age: WeepingVinesAge::_0,
         },
@@ -3855,155 +3855,155 @@ make_block_states! {
         },
         twisting_vines_plant => BlockBehavior::default(), {},
         crimson_roots => BlockBehavior::default(), {},
-        crimson_planks => BlockBehavior::default(), {},
-        warped_planks => BlockBehavior::default(), {},
-        crimson_slab => BlockBehavior::default(), {
+        crimson_planks => BlockBehavior::default().strength(2., 3.), {},
+        warped_planks => BlockBehavior::default().strength(2., 3.), {},
+        crimson_slab => BlockBehavior::default().strength(2., 3.), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        warped_slab => BlockBehavior::default(), {
+        warped_slab => BlockBehavior::default().strength(2., 3.), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        crimson_pressure_plate => BlockBehavior::default(), {
+        crimson_pressure_plate => BlockBehavior::default().strength(0.5, 0.5), {
             powered: false,
         },
-        warped_pressure_plate => BlockBehavior::default(), {
+        warped_pressure_plate => BlockBehavior::default().strength(0.5, 0.5), {
             powered: false,
         },
-        crimson_fence => BlockBehavior::default(), {
+        crimson_fence => BlockBehavior::default().strength(2., 3.), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        warped_fence => BlockBehavior::default(), {
+        warped_fence => BlockBehavior::default().strength(2., 3.), {
             east: false,
             north: false,
             south: false,
             waterlogged: false,
             west: false,
         },
-        crimson_trapdoor => BlockBehavior::default(), {
+        crimson_trapdoor => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             open: false,
             powered: false,
             waterlogged: false,
         },
-        warped_trapdoor => BlockBehavior::default(), {
+        warped_trapdoor => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             open: false,
             powered: false,
             waterlogged: false,
         },
-        crimson_fence_gate => BlockBehavior::default(), {
+        crimson_fence_gate => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             in_wall: false,
             open: false,
             powered: false,
         },
-        warped_fence_gate => BlockBehavior::default(), {
+        warped_fence_gate => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             in_wall: false,
             open: false,
             powered: false,
         },
-        crimson_stairs => BlockBehavior::default(), {
+        crimson_stairs => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        warped_stairs => BlockBehavior::default(), {
+        warped_stairs => BlockBehavior::default().strength(2., 3.), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        crimson_button => BlockBehavior::default(), {
+        crimson_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        warped_button => BlockBehavior::default(), {
+        warped_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        crimson_door => BlockBehavior::default(), {
+        crimson_door => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: Half::Lower,
             hinge: Hinge::Left,
             open: false,
             powered: false,
         },
-        warped_door => BlockBehavior::default(), {
+        warped_door => BlockBehavior::default().strength(3., 3.), {
             facing: FacingCardinal::North,
             half: Half::Lower,
             hinge: Hinge::Left,
             open: false,
             powered: false,
         },
-        crimson_sign => BlockBehavior::default(), {
+        crimson_sign => BlockBehavior::default().strength(1., 1.), {
             rotation: CrimsonSignRotation::_0,
             waterlogged: false,
         },
-        warped_sign => BlockBehavior::default(), {
+        warped_sign => BlockBehavior::default().strength(1., 1.), {
             rotation: WarpedSignRotation::_0,
             waterlogged: false,
         },
-        crimson_wall_sign => BlockBehavior::default(), {
+        crimson_wall_sign => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        warped_wall_sign => BlockBehavior::default(), {
+        warped_wall_sign => BlockBehavior::default().strength(1., 1.), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
-        structure_block => BlockBehavior::default(), {
+        structure_block => BlockBehavior::default().strength(-1., 3600000.), {
             mode: Mode::Load,
         },
-        jigsaw => BlockBehavior::default(), {
+        jigsaw => BlockBehavior::default().strength(-1., 3600000.), {
             orientation: Orientation::NorthUp,
         },
-        composter => BlockBehavior::default(), {
+        composter => BlockBehavior::default().strength(0.6, 0.6), {
             level: ComposterLevel::_0,
         },
-        target => BlockBehavior::default(), {
+        target => BlockBehavior::default().strength(0.5, 0.5), {
             power: TargetOutputPower::_0,
         },
-        bee_nest => BlockBehavior::default(), {
+        bee_nest => BlockBehavior::default().strength(0.3, 0.3), {
             facing: FacingCardinal::North,
             honey_level: BeeNestHoneyLevel::_0,
         },
-        beehive => BlockBehavior::default(), {
+        beehive => BlockBehavior::default().strength(0.6, 0.6), {
             facing: FacingCardinal::North,
             honey_level: BeehiveHoneyLevel::_0,
         },
-        honey_block => BlockBehavior::default(), {},
-        honeycomb_block => BlockBehavior::default(), {},
-        netherite_block => BlockBehavior::default(), {},
-        ancient_debris => BlockBehavior::default(), {},
-        crying_obsidian => BlockBehavior::default(), {},
-        respawn_anchor => BlockBehavior::default(), {
+        honey_block => BlockBehavior::default().speed_factor(0.4).jump_factor(0.5), {},
+        honeycomb_block => BlockBehavior::default().strength(0.6, 0.6), {},
+        netherite_block => BlockBehavior::default().strength(50., 1200.).requires_correct_tool_for_drops(), {},
+        ancient_debris => BlockBehavior::default().strength(30., 1200.).requires_correct_tool_for_drops(), {},
+        crying_obsidian => BlockBehavior::default().strength(50., 1200.).requires_correct_tool_for_drops(), {},
+        respawn_anchor => BlockBehavior::default().strength(50., 1200.).requires_correct_tool_for_drops(), {
             charges: RespawnAnchorCharge::_0,
         },
         potted_crimson_fungus => BlockBehavior::default(), {},
         potted_warped_fungus => BlockBehavior::default(), {},
         potted_crimson_roots => BlockBehavior::default(), {},
         potted_warped_roots => BlockBehavior::default(), {},
-        lodestone => BlockBehavior::default(), {},
-        blackstone => BlockBehavior::default(), {},
-        blackstone_stairs => BlockBehavior::default(), {
+        lodestone => BlockBehavior::default().strength(3.5, 3.5).requires_correct_tool_for_drops(), {},
+        blackstone => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        blackstone_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        blackstone_wall => BlockBehavior::default(), {
+        blackstone_wall => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -4011,25 +4011,25 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        blackstone_slab => BlockBehavior::default(), {
+        blackstone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        polished_blackstone => BlockBehavior::default(), {},
-        polished_blackstone_bricks => BlockBehavior::default(), {},
-        cracked_polished_blackstone_bricks => BlockBehavior::default(), {},
-        chiseled_polished_blackstone => BlockBehavior::default(), {},
-        polished_blackstone_brick_slab => BlockBehavior::default(), {
+        polished_blackstone => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        polished_blackstone_bricks => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        cracked_polished_blackstone_bricks => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        chiseled_polished_blackstone => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        polished_blackstone_brick_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        polished_blackstone_brick_stairs => BlockBehavior::default(), {
+        polished_blackstone_brick_stairs => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        polished_blackstone_brick_wall => BlockBehavior::default(), {
+        polished_blackstone_brick_wall => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -4037,26 +4037,26 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        gilded_blackstone => BlockBehavior::default(), {},
-        polished_blackstone_stairs => BlockBehavior::default(), {
+        gilded_blackstone => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        polished_blackstone_stairs => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        polished_blackstone_slab => BlockBehavior::default(), {
+        polished_blackstone_slab => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        polished_blackstone_pressure_plate => BlockBehavior::default(), {
+        polished_blackstone_pressure_plate => BlockBehavior::default().strength(0.5, 0.5).requires_correct_tool_for_drops(), {
             powered: false,
         },
-        polished_blackstone_button => BlockBehavior::default(), {
+        polished_blackstone_button => BlockBehavior::default().strength(0.5, 0.5), {
             face: Face::Wall,
             facing: FacingCardinal::North,
             powered: false,
         },
-        polished_blackstone_wall => BlockBehavior::default(), {
+        polished_blackstone_wall => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -4064,174 +4064,174 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        chiseled_nether_bricks => BlockBehavior::default(), {},
-        cracked_nether_bricks => BlockBehavior::default(), {},
-        quartz_bricks => BlockBehavior::default(), {},
-        candle => BlockBehavior::default(), {
+        chiseled_nether_bricks => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        cracked_nether_bricks => BlockBehavior::default().strength(2., 6.).requires_correct_tool_for_drops(), {},
+        quartz_bricks => BlockBehavior::default().strength(0.8, 0.8).requires_correct_tool_for_drops(), {},
+        candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: CandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        white_candle => BlockBehavior::default(), {
+        white_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: WhiteCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        orange_candle => BlockBehavior::default(), {
+        orange_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: OrangeCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        magenta_candle => BlockBehavior::default(), {
+        magenta_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: MagentaCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        light_blue_candle => BlockBehavior::default(), {
+        light_blue_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: LightBlueCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        yellow_candle => BlockBehavior::default(), {
+        yellow_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: YellowCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        lime_candle => BlockBehavior::default(), {
+        lime_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: LimeCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        pink_candle => BlockBehavior::default(), {
+        pink_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: PinkCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        gray_candle => BlockBehavior::default(), {
+        gray_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: GrayCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        light_gray_candle => BlockBehavior::default(), {
+        light_gray_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: LightGrayCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        cyan_candle => BlockBehavior::default(), {
+        cyan_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: CyanCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        purple_candle => BlockBehavior::default(), {
+        purple_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: PurpleCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        blue_candle => BlockBehavior::default(), {
+        blue_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: BlueCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        brown_candle => BlockBehavior::default(), {
+        brown_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: BrownCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        green_candle => BlockBehavior::default(), {
+        green_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: GreenCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        red_candle => BlockBehavior::default(), {
+        red_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: RedCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        black_candle => BlockBehavior::default(), {
+        black_candle => BlockBehavior::default().strength(0.1, 0.1), {
             candles: BlackCandleCandles::_1,
             lit: false,
             waterlogged: false,
         },
-        candle_cake => BlockBehavior::default(), {
+        candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        white_candle_cake => BlockBehavior::default(), {
+        white_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        orange_candle_cake => BlockBehavior::default(), {
+        orange_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        magenta_candle_cake => BlockBehavior::default(), {
+        magenta_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        light_blue_candle_cake => BlockBehavior::default(), {
+        light_blue_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        yellow_candle_cake => BlockBehavior::default(), {
+        yellow_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        lime_candle_cake => BlockBehavior::default(), {
+        lime_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        pink_candle_cake => BlockBehavior::default(), {
+        pink_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        gray_candle_cake => BlockBehavior::default(), {
+        gray_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        light_gray_candle_cake => BlockBehavior::default(), {
+        light_gray_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        cyan_candle_cake => BlockBehavior::default(), {
+        cyan_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        purple_candle_cake => BlockBehavior::default(), {
+        purple_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        blue_candle_cake => BlockBehavior::default(), {
+        blue_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        brown_candle_cake => BlockBehavior::default(), {
+        brown_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        green_candle_cake => BlockBehavior::default(), {
+        green_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        red_candle_cake => BlockBehavior::default(), {
+        red_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        black_candle_cake => BlockBehavior::default(), {
+        black_candle_cake => BlockBehavior::default().strength(0.5, 0.5), {
             lit: false,
         },
-        amethyst_block => BlockBehavior::default(), {},
-        budding_amethyst => BlockBehavior::default(), {},
-        amethyst_cluster => BlockBehavior::default(), {
+        amethyst_block => BlockBehavior::default().strength(1.5, 1.5).requires_correct_tool_for_drops(), {},
+        budding_amethyst => BlockBehavior::default().strength(1.5, 1.5).requires_correct_tool_for_drops(), {},
+        amethyst_cluster => BlockBehavior::default().strength(1.5, 1.5), {
             facing: FacingCubic::Up,
             waterlogged: false,
         },
-        large_amethyst_bud => BlockBehavior::default(), {
+        large_amethyst_bud => BlockBehavior::default().strength(1.5, 1.5), {
             facing: FacingCubic::Up,
             waterlogged: false,
         },
-        medium_amethyst_bud => BlockBehavior::default(), {
+        medium_amethyst_bud => BlockBehavior::default().strength(1.5, 1.5), {
             facing: FacingCubic::Up,
             waterlogged: false,
         },
-        small_amethyst_bud => BlockBehavior::default(), {
+        small_amethyst_bud => BlockBehavior::default().strength(1.5, 1.5), {
             facing: FacingCubic::Up,
             waterlogged: false,
         },
-        tuff => BlockBehavior::default(), {},
-        calcite => BlockBehavior::default(), {},
-        tinted_glass => BlockBehavior::default(), {},
-        powder_snow => BlockBehavior::default(), {},
-        sculk_sensor => BlockBehavior::default(), {
+        tuff => BlockBehavior::default().strength(1.5, 6.).requires_correct_tool_for_drops(), {},
+        calcite => BlockBehavior::default().strength(0.75, 0.75).requires_correct_tool_for_drops(), {},
+        tinted_glass => BlockBehavior::default().strength(0.3, 0.3), {},
+        powder_snow => BlockBehavior::default().strength(0.25, 0.25), {},
+        sculk_sensor => BlockBehavior::default().strength(1.5, 1.5), {
             power: SculkSensorPower::_0,
             sculk_sensor_phase: Phase::Inactive,
             waterlogged: false,
         },
-        sculk => BlockBehavior::default(), {},
-        sculk_vein => BlockBehavior::default(), {
+        sculk => BlockBehavior::default().strength(0.2, 0.2), {},
+        sculk_vein => BlockBehavior::default().strength(0.2, 0.2), {
             down: false,
             east: false,
             north: false,
@@ -4240,123 +4240,123 @@ make_block_states! {
             waterlogged: false,
             west: false,
         },
-        sculk_catalyst => BlockBehavior::default(), {
+        sculk_catalyst => BlockBehavior::default().strength(3., 3.), {
             bloom: false,
         },
-        sculk_shrieker => BlockBehavior::default(), {
+        sculk_shrieker => BlockBehavior::default().strength(3., 3.), {
             can_summon: false,
             shrieking: false,
             waterlogged: false,
         },
-        oxidized_copper => BlockBehavior::default(), {},
-        weathered_copper => BlockBehavior::default(), {},
-        exposed_copper => BlockBehavior::default(), {},
-        copper_block => BlockBehavior::default(), {},
-        copper_ore => BlockBehavior::default(), {},
-        deepslate_copper_ore => BlockBehavior::default(), {},
-        oxidized_cut_copper => BlockBehavior::default(), {},
-        weathered_cut_copper => BlockBehavior::default(), {},
-        exposed_cut_copper => BlockBehavior::default(), {},
-        cut_copper => BlockBehavior::default(), {},
-        oxidized_cut_copper_stairs => BlockBehavior::default(), {
+        oxidized_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        weathered_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        exposed_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        copper_block => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        copper_ore => BlockBehavior::default().strength(3., 3.).requires_correct_tool_for_drops(), {},
+        deepslate_copper_ore => BlockBehavior::default().strength(4.5, 3.).requires_correct_tool_for_drops(), {},
+        oxidized_cut_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        weathered_cut_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        exposed_cut_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        cut_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        oxidized_cut_copper_stairs => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        weathered_cut_copper_stairs => BlockBehavior::default(), {
+        weathered_cut_copper_stairs => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        exposed_cut_copper_stairs => BlockBehavior::default(), {
+        exposed_cut_copper_stairs => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        cut_copper_stairs => BlockBehavior::default(), {
+        cut_copper_stairs => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        oxidized_cut_copper_slab => BlockBehavior::default(), {
+        oxidized_cut_copper_slab => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        weathered_cut_copper_slab => BlockBehavior::default(), {
+        weathered_cut_copper_slab => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        exposed_cut_copper_slab => BlockBehavior::default(), {
+        exposed_cut_copper_slab => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        cut_copper_slab => BlockBehavior::default(), {
+        cut_copper_slab => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        waxed_copper_block => BlockBehavior::default(), {},
-        waxed_weathered_copper => BlockBehavior::default(), {},
-        waxed_exposed_copper => BlockBehavior::default(), {},
-        waxed_oxidized_copper => BlockBehavior::default(), {},
-        waxed_oxidized_cut_copper => BlockBehavior::default(), {},
-        waxed_weathered_cut_copper => BlockBehavior::default(), {},
-        waxed_exposed_cut_copper => BlockBehavior::default(), {},
-        waxed_cut_copper => BlockBehavior::default(), {},
-        waxed_oxidized_cut_copper_stairs => BlockBehavior::default(), {
+        waxed_copper_block => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        waxed_weathered_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        waxed_exposed_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        waxed_oxidized_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        waxed_oxidized_cut_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        waxed_weathered_cut_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        waxed_exposed_cut_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        waxed_cut_copper => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {},
+        waxed_oxidized_cut_copper_stairs => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        waxed_weathered_cut_copper_stairs => BlockBehavior::default(), {
+        waxed_weathered_cut_copper_stairs => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        waxed_exposed_cut_copper_stairs => BlockBehavior::default(), {
+        waxed_exposed_cut_copper_stairs => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        waxed_cut_copper_stairs => BlockBehavior::default(), {
+        waxed_cut_copper_stairs => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        waxed_oxidized_cut_copper_slab => BlockBehavior::default(), {
+        waxed_oxidized_cut_copper_slab => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        waxed_weathered_cut_copper_slab => BlockBehavior::default(), {
+        waxed_weathered_cut_copper_slab => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        waxed_exposed_cut_copper_slab => BlockBehavior::default(), {
+        waxed_exposed_cut_copper_slab => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        waxed_cut_copper_slab => BlockBehavior::default(), {
+        waxed_cut_copper_slab => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        lightning_rod => BlockBehavior::default(), {
+        lightning_rod => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             facing: FacingCubic::Up,
             powered: false,
             waterlogged: false,
         },
-        pointed_dripstone => BlockBehavior::default(), {
+        pointed_dripstone => BlockBehavior::default().strength(1.5, 3.), {
             thickness: Thickness::Tip,
             vertical_direction: TipDirection::Up,
             waterlogged: false,
         },
-        dripstone_block => BlockBehavior::default(), {},
+        dripstone_block => BlockBehavior::default().strength(1.5, 1.).requires_correct_tool_for_drops(), {},
         cave_vines => BlockBehavior::default(), {
             age: CaveVinesAge::_0,
             berries: false,
@@ -4367,14 +4367,14 @@ make_block_states! {
         spore_blossom => BlockBehavior::default(), {},
         azalea => BlockBehavior::default(), {},
         flowering_azalea => BlockBehavior::default(), {},
-        moss_carpet => BlockBehavior::default(), {},
-        moss_block => BlockBehavior::default(), {},
-        big_dripleaf => BlockBehavior::default(), {
+        moss_carpet => BlockBehavior::default().strength(0.1, 0.1), {},
+        moss_block => BlockBehavior::default().strength(0.1, 0.1), {},
+        big_dripleaf => BlockBehavior::default().strength(0.1, 0.1), {
             facing: FacingCardinal::North,
             tilt: Tilt::None,
             waterlogged: false,
         },
-        big_dripleaf_stem => BlockBehavior::default(), {
+        big_dripleaf_stem => BlockBehavior::default().strength(0.1, 0.1), {
             facing: FacingCardinal::North,
             waterlogged: false,
         },
@@ -4386,23 +4386,23 @@ make_block_states! {
         hanging_roots => BlockBehavior::default(), {
             waterlogged: false,
         },
-        rooted_dirt => BlockBehavior::default(), {},
-        mud => BlockBehavior::default(), {},
-        deepslate => BlockBehavior::default(), {
+        rooted_dirt => BlockBehavior::default().strength(0.5, 0.5), {},
+        mud => BlockBehavior::default().strength(0.5, 0.5), {},
+        deepslate => BlockBehavior::default().strength(3., 6.).requires_correct_tool_for_drops(), {
             axis: Axis::Y,
         },
-        cobbled_deepslate => BlockBehavior::default(), {},
-        cobbled_deepslate_stairs => BlockBehavior::default(), {
+        cobbled_deepslate => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {},
+        cobbled_deepslate_stairs => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        cobbled_deepslate_slab => BlockBehavior::default(), {
+        cobbled_deepslate_slab => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        cobbled_deepslate_wall => BlockBehavior::default(), {
+        cobbled_deepslate_wall => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -4410,18 +4410,18 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        polished_deepslate => BlockBehavior::default(), {},
-        polished_deepslate_stairs => BlockBehavior::default(), {
+        polished_deepslate => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {},
+        polished_deepslate_stairs => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        polished_deepslate_slab => BlockBehavior::default(), {
+        polished_deepslate_slab => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        polished_deepslate_wall => BlockBehavior::default(), {
+        polished_deepslate_wall => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -4429,18 +4429,18 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        deepslate_tiles => BlockBehavior::default(), {},
-        deepslate_tile_stairs => BlockBehavior::default(), {
+        deepslate_tiles => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {},
+        deepslate_tile_stairs => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        deepslate_tile_slab => BlockBehavior::default(), {
+        deepslate_tile_slab => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        deepslate_tile_wall => BlockBehavior::default(), {
+        deepslate_tile_wall => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -4448,18 +4448,18 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        deepslate_bricks => BlockBehavior::default(), {},
-        deepslate_brick_stairs => BlockBehavior::default(), {
+        deepslate_bricks => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {},
+        deepslate_brick_stairs => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             facing: FacingCardinal::North,
             half: TopBottom::Bottom,
             shape: StairShape::Straight,
             waterlogged: false,
         },
-        deepslate_brick_slab => BlockBehavior::default(), {
+        deepslate_brick_slab => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             kind: Type::Bottom,
             waterlogged: false,
         },
-        deepslate_brick_wall => BlockBehavior::default(), {
+        deepslate_brick_wall => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {
             east: EastWall::None,
             north: NorthWall::None,
             south: SouthWall::None,
@@ -4467,28 +4467,28 @@ make_block_states! {
             waterlogged: false,
             west: WestWall::None,
         },
-        chiseled_deepslate => BlockBehavior::default(), {},
-        cracked_deepslate_bricks => BlockBehavior::default(), {},
-        cracked_deepslate_tiles => BlockBehavior::default(), {},
-        infested_deepslate => BlockBehavior::default(), {
+        chiseled_deepslate => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {},
+        cracked_deepslate_bricks => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {},
+        cracked_deepslate_tiles => BlockBehavior::default().strength(3.5, 6.).requires_correct_tool_for_drops(), {},
+        infested_deepslate => BlockBehavior::default().strength(1.5, 0.75), {
             axis: CacheSize::Y,
         },
-        smooth_basalt => BlockBehavior::default(), {},
-        raw_iron_block => BlockBehavior::default(), {},
-        raw_copper_block => BlockBehavior::default(), {},
-        raw_gold_block => BlockBehavior::default(), {},
+        smooth_basalt => BlockBehavior::default().strength(1.25, 4.2).requires_correct_tool_for_drops(), {},
+        raw_iron_block => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {},
+        raw_copper_block => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {},
+        raw_gold_block => BlockBehavior::default().strength(5., 6.).requires_correct_tool_for_drops(), {},
         potted_azalea_bush => BlockBehavior::default(), {},
         potted_flowering_azalea_bush => BlockBehavior::default(), {},
-        ochre_froglight => BlockBehavior::default(), {
+        ochre_froglight => BlockBehavior::default().strength(0.3, 0.3), {
             axis: Axis::Y,
         },
-        verdant_froglight => BlockBehavior::default(), {
+        verdant_froglight => BlockBehavior::default().strength(0.3, 0.3), {
             axis: Axis::Y,
         },
-        pearlescent_froglight => BlockBehavior::default(), {
+        pearlescent_froglight => BlockBehavior::default().strength(0.3, 0.3), {
             axis: Axis::Y,
         },
         frogspawn => BlockBehavior::default(), {},
-        reinforced_deepslate => BlockBehavior::default(), {},
+        reinforced_deepslate => BlockBehavior::default().strength(55., 1200.), {},
     }
 }
@@ -2,6 +2,7 @@
 #![feature(trait_upcasting)]
 
 mod behavior;
+mod fluid_state;
 mod generated;
 mod range;
 
@@ -9,6 +10,7 @@ pub use generated::{blocks, properties};
 
 use azalea_buf::{BufReadError, McBufReadable, McBufVarReadable, McBufVarWritable, McBufWritable};
 pub use behavior::BlockBehavior;
+pub use fluid_state::{FluidKind, FluidState};
 use core::fmt::Debug;
 pub use range::BlockStates;
 use std::{
@@ -24,6 +26,9 @@ pub trait Block: Debug + Any {
     /// Convert the block to a block state. This is lossless, as the block
     /// contains all the state data.
     fn as_block_state(&self) -> BlockState;
+    /// Get the registry entry for this block, which doesn't contain any of
+    /// the state data.
+    fn as_registry_block(&self) -> azalea_registry::Block;
 }
 impl dyn Block {
     pub fn downcast_ref<T: Block>(&self) -> Option<&T> {
@@ -58,6 +63,13 @@ impl BlockState {
     pub fn is_valid_state(state_id: u32) -> bool {
         state_id <= Self::max_state()
     }
+
+    /// Whether the block is air, cave air or void air.
+    pub fn is_air(&self) -> bool {
+        self == &Self::AIR
+            || *self == BlockState::from(azalea_registry::Block::CaveAir)
+            || *self == BlockState::from(azalea_registry::Block::VoidAir)
+    }
 }
 
 impl TryFrom<u32> for BlockState {
@@ -73,6 +85,12 @@ impl TryFrom<u32> for BlockState {
     }
 }
 
+impl From<BlockState> for azalea_registry::Block {
+    fn from(block_state: BlockState) -> Self {
+        Box::<dyn Block>::from(block_state).as_registry_block()
+    }
+}
+
 impl McBufReadable for BlockState {
     fn read_from(buf: &mut Cursor<&[u8]>) -> Result<Self, BufReadError> {
         let state_id = u32::var_read_from(buf)?;
@@ -120,6 +138,15 @@ mod tests {
         assert_eq!(block.id(), "flowering_azalea");
     }
 
+    #[test]
+    fn test_from_blockstate_to_registry_block() {
+        let block_state = BlockState::from(azalea_registry::Block::FloweringAzalea);
+        assert_eq!(
+            azalea_registry::Block::from(block_state),
+            azalea_registry::Block::FloweringAzalea
+        );
+    }
+
     #[test]
     fn test_debug_blockstate() {
         let formatted = format!(
@@ -138,4 +165,45 @@ mod tests {
             formatted
         );
     }
+
+    #[test]
+    fn test_open() {
+        let closed = BlockState::from(azalea_registry::Block::OakDoor);
+        assert!(!closed.open());
+        let open = blocks::OakDoor {
+            open: true,
+            ..Default::default()
+        }
+        .as_block_state();
+        assert!(open.open());
+        assert!(blocks::SpruceFenceGate {
+            open: true,
+            ..Default::default()
+        }
+        .as_block_state()
+        .open());
+        assert!(!BlockState::from(azalea_registry::Block::Stone).open());
+    }
+
+    #[test]
+    fn test_horizontal_facing() {
+        let door = blocks::OakDoor {
+            facing: properties::FacingCardinal::East,
+            open: true,
+            ..Default::default()
+        }
+        .as_block_state();
+        assert!(matches!(
+            door.horizontal_facing(),
+            Some(properties::FacingCardinal::East)
+        ));
+        assert!(matches!(
+            BlockState::from(azalea_registry::Block::OakTrapdoor).horizontal_facing(),
+            Some(properties::FacingCardinal::North)
+        ));
+        // observers can face up and down too
+        assert!(BlockState::from(azalea_registry::Block::Observer)
+            .horizontal_facing()
+            .is_none());
+    }
 }
@@ -2,20 +2,25 @@ use crate::{
     chat::ChatPlugin,
     disconnect::{DisconnectEvent, DisconnectPlugin},
     events::{Event, EventPlugin, LocalPlayerEvents},
+    hud::{ActionBar, BossBars, HudPlugin, TabListHeaderFooter, Titles},
+    interact::{BlockStatePredictions, CurrentSequenceNumber, InteractPlugin},
+    inventory::{Inventory, InventoryPlugin},
     local_player::{
         death_event, handle_send_packet_event, update_in_loaded_chunk, GameProfileComponent,
         LocalPlayer, PhysicsState, SendPacketEvent,
     },
+    mining::{MineDelay, MinePlugin},
     movement::PlayerMovePlugin,
     packet_handling::{self, PacketHandlerPlugin, PacketReceiver},
     player::retroactively_add_game_profile_component,
+    scoreboard::Scoreboard,
     task_pool::TaskPoolPlugin,
     Account, PlayerInfo,
 };
 
 use azalea_auth::{game_profile::GameProfile, sessionserver::ClientSessionServerError};
 use azalea_chat::FormattedText;
-use azalea_physics::{PhysicsPlugin, PhysicsSet};
+use azalea_physics::{FireworkBoosts, PhysicsPlugin, PhysicsSet};
 use azalea_protocol::{
     connect::{Connection, ConnectionError},
     packets::{
@@ -41,13 +46,16 @@ use azalea_world::{
     entity::{EntityPlugin, EntityUpdateSet, Local, WorldName},
     Instance, InstanceContainer, PartialInstance,
 };
-use bevy_app::{App, CoreSchedule, Plugin, PluginGroup, PluginGroupBuilder};
+use bevy_app::{
+    App, CoreSchedule, IntoSystemAppConfig, Plugin, PluginGroup, PluginGroupBuilder,
+};
 use bevy_ecs::{
     bundle::Bundle,
     component::Component,
     entity::Entity,
     schedule::IntoSystemConfig,
     schedule::{LogLevel, ScheduleBuildSettings, ScheduleLabel},
+    system::{Res, Resource},
     world::World,
 };
 use bevy_log::LogPlugin;
@@ -57,7 +65,10 @@ use log::{debug, error};
 use parking_lot::{Mutex, RwLock};
 use std::{collections::HashMap, fmt::Debug, io, net::SocketAddr, sync::Arc, time::Duration};
 use thiserror::Error;
-use tokio::{sync::mpsc, time};
+use tokio::{
+    sync::{broadcast, mpsc},
+    time,
+};
 use uuid::Uuid;
 
 /// `Client` has the things that a user interacting with the library will want.
@@ -249,6 +260,16 @@ impl Client {
             local_player_events: LocalPlayerEvents(tx),
             client_information: ClientInformation::default(),
             tab_list: TabList::default(),
+            scoreboard: Scoreboard::default(),
+            boss_bars: BossBars::default(),
+            titles: Titles::default(),
+            action_bar: ActionBar::default(),
+            tab_list_header_footer: TabListHeaderFooter::default(),
+            inventory: Inventory::default(),
+            current_sequence_number: CurrentSequenceNumber::default(),
+            block_state_predictions: BlockStatePredictions::default(),
+            mine_delay: MineDelay::default(),
+            firework_boosts: FireworkBoosts::default(),
             _local: Local,
         });
 
@@ -443,6 +464,24 @@ impl Client {
             .is_some()
     }
 
+    /// Get a receiver that gets a message every game tick. This is useful for
+    /// waiting for something to happen in the world.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(bot: azalea_client::Client) {
+    /// let mut ticks = bot.get_tick_broadcaster();
+    /// // wait 20 ticks (one second)
+    /// for _ in 0..20 {
+    ///     let _ = ticks.recv().await;
+    /// }
+    /// # }
+    /// ```
+    pub fn get_tick_broadcaster(&self) -> broadcast::Receiver<()> {
+        let ecs = self.ecs.lock();
+        let tick_broadcast = ecs.resource::<TickBroadcast>();
+        tick_broadcast.subscribe()
+    }
+
     /// Tell the server we changed our game options (i.e. render distance, main
     /// hand). If this is not set before the login packet, the default will
     /// be sent.
@@ -491,6 +530,16 @@ pub struct JoinedClientBundle {
     pub local_player_events: LocalPlayerEvents,
     pub client_information: ClientInformation,
     pub tab_list: TabList,
+    pub scoreboard: Scoreboard,
+    pub boss_bars: BossBars,
+    pub titles: Titles,
+    pub action_bar: ActionBar,
+    pub tab_list_header_footer: TabListHeaderFooter,
+    pub inventory: Inventory,
+    pub current_sequence_number: CurrentSequenceNumber,
+    pub block_state_predictions: BlockStatePredictions,
+    pub mine_delay: MineDelay,
+    pub firework_boosts: FireworkBoosts,
     pub _local: Local,
 }
 
@@ -516,9 +565,22 @@ impl Plugin for AzaleaPlugin {
             .add_system(handle_send_packet_event);
 
         app.init_resource::<InstanceContainer>();
+
+        app.insert_resource(TickBroadcast(broadcast::channel(1).0))
+            .add_system(send_tick_broadcast.in_schedule(CoreSchedule::FixedUpdate));
     }
 }
 
+/// A resource that sends a message every game tick. Subscribe to it with
+/// [`Client::get_tick_broadcaster`].
+#[derive(Resource, Deref)]
+pub struct TickBroadcast(broadcast::Sender<()>);
+
+fn send_tick_broadcast(tick_broadcast: Res<TickBroadcast>) {
+    // this only errors if there's no receivers, which is fine
+    let _ = tick_broadcast.send(());
+}
+
 /// Create the [`App`]. This won't actually run anything yet.
 ///
 /// Note that you usually only need this if you're creating a client manually,
@@ -619,5 +681,9 @@ impl PluginGroup for DefaultPlugins {
             .add(ChatPlugin)
             .add(DisconnectPlugin)
             .add(PlayerMovePlugin)
+            .add(InteractPlugin)
+            .add(MinePlugin)
+            .add(InventoryPlugin)
+            .add(HudPlugin)
     }
 }
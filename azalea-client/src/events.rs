@@ -3,6 +3,8 @@
 
 use std::sync::Arc;
 
+use azalea_chat::FormattedText;
+use azalea_core::{ChunkPos, Vec3};
 use azalea_protocol::packets::game::{
     clientbound_player_combat_kill_packet::ClientboundPlayerCombatKillPacket, ClientboundGamePacket,
 };
@@ -14,10 +16,14 @@ use tokio::sync::mpsc;
 
 use crate::{
     chat::{ChatPacket, ChatReceivedEvent},
+    hud::{BossBar, TabListHeaderFooter},
     packet_handling::{
-        AddPlayerEvent, DeathEvent, KeepAliveEvent, PacketEvent, RemovePlayerEvent,
-        UpdatePlayerEvent,
+        AddBossBarEvent, AddPlayerEvent, ChunkUnloadedEvent, DeathEvent, KeepAliveEvent,
+        PacketEvent, RemoveBossBarEvent, RemovePlayerEvent, SetActionBarEvent, SetSubtitleEvent,
+        SetTitleEvent, TabListHeaderFooterEvent, TeleportedEvent, UpdateBossBarEvent,
+        UpdatePlayerEvent, UpdateScoreEvent,
     },
+    scoreboard::ScoreUpdate,
     PlayerInfo,
 };
 
@@ -87,6 +93,29 @@ pub enum Event {
     Death(Option<Arc<ClientboundPlayerCombatKillPacket>>),
     /// A `KeepAlive` packet was sent by the server.
     KeepAlive(u64),
+    /// The server teleported us, either with something like `/tp` or because
+    /// it didn't accept our movement. This contains our new position.
+    Teleported(Vec3),
+    /// A score in the scoreboard was changed or removed. You can get the
+    /// whole scoreboard with `Client::scoreboard`.
+    UpdateScore(ScoreUpdate),
+    /// A boss bar was added to the screen.
+    AddBossBar(BossBar),
+    /// A boss bar's progress, name, style or properties changed.
+    UpdateBossBar(BossBar),
+    /// A boss bar was removed from the screen.
+    RemoveBossBar(BossBar),
+    /// A title was shown in the middle of the screen.
+    Title(FormattedText),
+    /// The subtitle was set. It's only shown once there's also a title.
+    Subtitle(FormattedText),
+    /// Some text was shown above the hotbar.
+    ActionBar(FormattedText),
+    /// The text above and below the tab list was changed.
+    TabListHeaderFooter(TabListHeaderFooter),
+    /// The server told us to forget about the chunk at this position, usually
+    /// because we moved away from it.
+    ChunkUnloaded(ChunkPos),
 }
 
 /// A component that contains an event sender for events that are only
@@ -109,6 +138,16 @@ impl Plugin for EventPlugin {
             .add_system(remove_player_listener)
             .add_system(death_listener)
             .add_system(keepalive_listener)
+            .add_system(teleported_listener)
+            .add_system(update_score_listener)
+            .add_system(add_boss_bar_listener)
+            .add_system(update_boss_bar_listener)
+            .add_system(remove_boss_bar_listener)
+            .add_system(set_title_listener)
+            .add_system(set_subtitle_listener)
+            .add_system(set_action_bar_listener)
+            .add_system(tab_list_header_footer_listener)
+            .add_system(chunk_unloaded_listener)
             .add_system(tick_listener.in_schedule(CoreSchedule::FixedUpdate));
     }
 }
@@ -214,3 +253,137 @@ fn keepalive_listener(query: Query<&LocalPlayerEvents>, mut events: EventReader<
             .unwrap();
     }
 }
+
+fn teleported_listener(query: Query<&LocalPlayerEvents>, mut events: EventReader<TeleportedEvent>) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive teleported events");
+        local_player_events
+            .send(Event::Teleported(event.new_position))
+            .unwrap();
+    }
+}
+
+fn update_score_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<UpdateScoreEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive update score events");
+        local_player_events
+            .send(Event::UpdateScore(event.update.clone()))
+            .unwrap();
+    }
+}
+
+fn add_boss_bar_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<AddBossBarEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive add boss bar events");
+        local_player_events
+            .send(Event::AddBossBar(event.bar.clone()))
+            .unwrap();
+    }
+}
+
+fn update_boss_bar_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<UpdateBossBarEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive update boss bar events");
+        local_player_events
+            .send(Event::UpdateBossBar(event.bar.clone()))
+            .unwrap();
+    }
+}
+
+fn remove_boss_bar_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<RemoveBossBarEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive remove boss bar events");
+        local_player_events
+            .send(Event::RemoveBossBar(event.bar.clone()))
+            .unwrap();
+    }
+}
+
+fn set_title_listener(query: Query<&LocalPlayerEvents>, mut events: EventReader<SetTitleEvent>) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive set title events");
+        local_player_events
+            .send(Event::Title(event.text.clone()))
+            .unwrap();
+    }
+}
+
+fn set_subtitle_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<SetSubtitleEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive set subtitle events");
+        local_player_events
+            .send(Event::Subtitle(event.text.clone()))
+            .unwrap();
+    }
+}
+
+fn set_action_bar_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<SetActionBarEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive set action bar events");
+        local_player_events
+            .send(Event::ActionBar(event.text.clone()))
+            .unwrap();
+    }
+}
+
+fn tab_list_header_footer_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<TabListHeaderFooterEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query.get(event.entity).expect(
+            "Non-localplayer entities shouldn't be able to receive tab list header footer events",
+        );
+        local_player_events
+            .send(Event::TabListHeaderFooter(event.header_footer.clone()))
+            .unwrap();
+    }
+}
+
+fn chunk_unloaded_listener(
+    query: Query<&LocalPlayerEvents>,
+    mut events: EventReader<ChunkUnloadedEvent>,
+) {
+    for event in events.iter() {
+        let local_player_events = query
+            .get(event.entity)
+            .expect("Non-localplayer entities shouldn't be able to receive chunk unloaded events");
+        local_player_events
+            .send(Event::ChunkUnloaded(event.pos))
+            .unwrap();
+    }
+}
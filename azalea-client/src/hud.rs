@@ -0,0 +1,151 @@
+//! Keep track of the things that the server shows on the player's screen, like
+//! boss bars, titles, the action bar and the tab list header and footer.
+
+use std::collections::HashMap;
+
+use azalea_chat::FormattedText;
+use azalea_protocol::packets::game::clientbound_boss_event_packet::{
+    AddOperation, BossBarColor, BossBarOverlay, Properties,
+};
+use bevy_app::{App, CoreSchedule, IntoSystemAppConfig, Plugin};
+use bevy_ecs::{component::Component, system::Query};
+use derive_more::{Deref, DerefMut};
+use uuid::Uuid;
+
+pub struct HudPlugin;
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(tick_titles.in_schedule(CoreSchedule::FixedUpdate));
+    }
+}
+
+/// A component that contains the boss bars that are currently being shown to
+/// a local player, indexed by their id.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut)]
+pub struct BossBars(pub HashMap<Uuid, BossBar>);
+
+#[derive(Clone, Debug)]
+pub struct BossBar {
+    pub id: Uuid,
+    pub name: FormattedText,
+    /// How full the bar is, from 0 to 1.
+    pub progress: f32,
+    pub color: BossBarColor,
+    pub overlay: BossBarOverlay,
+    pub properties: Properties,
+}
+
+impl BossBar {
+    pub fn new(id: Uuid, add: AddOperation) -> Self {
+        Self {
+            id,
+            name: add.name,
+            progress: add.progress,
+            color: add.style.color,
+            overlay: add.style.overlay,
+            properties: add.properties,
+        }
+    }
+}
+
+/// A component that contains the title and subtitle that are currently being
+/// shown to a local player.
+#[derive(Component, Clone, Debug)]
+pub struct Titles {
+    /// The big text in the middle of the screen. This is `None` if there's no
+    /// title being shown.
+    pub title: Option<FormattedText>,
+    /// The smaller text under the title. This is only shown while there's also
+    /// a title.
+    pub subtitle: Option<FormattedText>,
+    /// The number of ticks the title takes to fade in.
+    pub fade_in: u32,
+    /// The number of ticks the title stays on the screen.
+    pub stay: u32,
+    /// The number of ticks the title takes to fade out.
+    pub fade_out: u32,
+    /// The number of ticks until the title disappears.
+    pub ticks_left: u32,
+}
+
+impl Default for Titles {
+    fn default() -> Self {
+        Self {
+            title: None,
+            subtitle: None,
+            fade_in: Self::DEFAULT_FADE_IN,
+            stay: Self::DEFAULT_STAY,
+            fade_out: Self::DEFAULT_FADE_OUT,
+            ticks_left: 0,
+        }
+    }
+}
+
+impl Titles {
+    const DEFAULT_FADE_IN: u32 = 10;
+    const DEFAULT_STAY: u32 = 70;
+    const DEFAULT_FADE_OUT: u32 = 20;
+
+    /// Show a new title, resetting the timer.
+    pub fn set_title(&mut self, title: FormattedText) {
+        self.title = Some(title);
+        self.ticks_left = self.total_ticks();
+    }
+
+    /// Change how long titles are shown for. If a title is currently being
+    /// shown, its timer is restarted.
+    pub fn set_times(&mut self, fade_in: u32, stay: u32, fade_out: u32) {
+        self.fade_in = fade_in;
+        self.stay = stay;
+        self.fade_out = fade_out;
+        if self.title.is_some() && self.ticks_left > 0 {
+            self.ticks_left = self.total_ticks();
+        }
+    }
+
+    /// Hide the current title and subtitle, and optionally reset the timings
+    /// to the defaults.
+    pub fn clear(&mut self, reset_times: bool) {
+        self.title = None;
+        self.subtitle = None;
+        self.ticks_left = 0;
+        if reset_times {
+            self.set_times(
+                Self::DEFAULT_FADE_IN,
+                Self::DEFAULT_STAY,
+                Self::DEFAULT_FADE_OUT,
+            );
+        }
+    }
+
+    fn total_ticks(&self) -> u32 {
+        self.fade_in + self.stay + self.fade_out
+    }
+}
+
+/// A component that contains the last text the server sent to a local player's
+/// action bar (the text above the hotbar).
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut)]
+pub struct ActionBar(pub Option<FormattedText>);
+
+/// A component that contains the text shown above and below the tab list of a
+/// local player.
+#[derive(Component, Clone, Debug, Default)]
+pub struct TabListHeaderFooter {
+    pub header: FormattedText,
+    pub footer: FormattedText,
+}
+
+/// Count down the time left for titles and remove them when they run out.
+fn tick_titles(mut query: Query<&mut Titles>) {
+    for mut titles in &mut query {
+        if titles.ticks_left == 0 {
+            continue;
+        }
+        titles.ticks_left -= 1;
+        if titles.ticks_left == 0 {
+            titles.title = None;
+            titles.subtitle = None;
+        }
+    }
+}
@@ -0,0 +1,478 @@
+//! Interacting with blocks in the world, i.e. right clicking them.
+
+use std::{collections::HashMap, str::FromStr};
+
+use azalea_block::{Block, BlockState};
+use azalea_core::{BlockHitResult, BlockPos, Direction, Slot, Vec3};
+use azalea_physics::FireworkBoosts;
+use azalea_protocol::packets::game::{
+    serverbound_interact_packet::InteractionHand,
+    serverbound_swing_packet::ServerboundSwingPacket,
+    serverbound_use_item_on_packet::ServerboundUseItemOnPacket,
+    serverbound_use_item_packet::ServerboundUseItemPacket,
+};
+use azalea_world::entity::{
+    direction_looking_at, metadata::FallFlying, set_rotation, EyeHeight, Physics, Position,
+};
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EventReader,
+    schedule::IntoSystemConfigs,
+    system::Query,
+};
+use derive_more::{Deref, DerefMut};
+
+use crate::{
+    inventory::{handle_set_selected_hotbar_slot_event, Inventory},
+    local_player::{handle_send_packet_event, LocalPlayer},
+    Client,
+};
+
+pub struct InteractPlugin;
+impl Plugin for InteractPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BlockInteractEvent>()
+            .add_event::<PlaceBlockEvent>()
+            .add_event::<UseItemEvent>()
+            .add_systems(
+                (
+                    handle_block_interact_event,
+                    handle_place_block_event,
+                    handle_use_item_event,
+                )
+                    .chain()
+                    .after(handle_set_selected_hotbar_slot_event)
+                    .before(handle_send_packet_event),
+            );
+    }
+}
+
+/// The server ignores players trying to break or use blocks that are further
+/// than this from their eyes.
+pub const MAX_INTERACTION_DISTANCE: f64 = 6.;
+
+impl Client {
+    /// Right click a block. The behavior of this depends on the target block,
+    /// and it'll either place the block you're holding in your hand or use the
+    /// block you clicked (like toggling a lever).
+    ///
+    /// Note that this may trigger anticheats as it doesn't take into account
+    /// whether you're actually looking at the block.
+    pub fn block_interact(&mut self, position: BlockPos) {
+        self.ecs.lock().send_event(BlockInteractEvent {
+            entity: self.entity,
+            position,
+        });
+    }
+
+    /// Place the block we're holding against the given face of a block, like
+    /// right clicking it.
+    ///
+    /// The block is predicted to be placed right away, and if the server
+    /// doesn't place it (for example because the clicked block is a chest that
+    /// opened instead) the prediction is undone once the server acknowledges
+    /// our click. This returns false without doing anything if the block is
+    /// too far away to reach.
+    ///
+    /// ```rust,no_run
+    /// # use azalea_client::Client;
+    /// # use azalea_core::{BlockPos, Direction};
+    /// # fn example(mut bot: Client) {
+    /// // place a block on top of the block at 0 63 0
+    /// bot.place_block(BlockPos::new(0, 63, 0), Direction::Up);
+    /// # }
+    /// ```
+    pub fn place_block(&mut self, against: BlockPos, face: Direction) -> bool {
+        let mut ecs = self.ecs.lock();
+        let eye_position = {
+            let (position, eye_height) = self.query::<(&Position, &EyeHeight)>(&mut ecs);
+            position.up(**eye_height as f64)
+        };
+        if !can_reach_block(&eye_position, &against) {
+            return false;
+        }
+        ecs.send_event(PlaceBlockEvent {
+            entity: self.entity,
+            against,
+            face,
+        });
+        true
+    }
+
+    /// Use the item in our hand without targeting a block, like right clicking
+    /// the air. This is how you eat food, throw projectiles or start drawing a
+    /// bow.
+    pub fn use_item(&mut self) {
+        self.ecs.lock().send_event(UseItemEvent {
+            entity: self.entity,
+        });
+    }
+}
+
+/// Right click a block. The behavior of this depends on the target block,
+/// and it'll either place the block you're holding in your hand or use the
+/// block you clicked (like toggling a lever).
+pub struct BlockInteractEvent {
+    /// The local player entity that's clicking the block.
+    pub entity: Entity,
+    /// The coordinates of the block.
+    pub position: BlockPos,
+}
+
+/// Place the block we're holding against a face of another block. See
+/// [`Client::place_block`].
+pub struct PlaceBlockEvent {
+    /// The local player entity that's placing the block.
+    pub entity: Entity,
+    /// The block we're clicking.
+    pub against: BlockPos,
+    /// The face of the block we're clicking. The new block is placed next to
+    /// this face.
+    pub face: Direction,
+}
+
+/// Use the item we're holding without targeting a block. See
+/// [`Client::use_item`].
+pub struct UseItemEvent {
+    /// The local player entity that's using the item.
+    pub entity: Entity,
+}
+
+/// A component that contains the number of changes this client has made to
+/// blocks. The server sends this number back in a `BlockChangedAck` packet so
+/// we know which of our predictions it accepted.
+#[derive(Component, Copy, Clone, Debug, Default, Deref, DerefMut)]
+pub struct CurrentSequenceNumber(u32);
+
+/// The block changes that we've made locally but that the server hasn't
+/// acknowledged yet.
+///
+/// While a block is being predicted, updates the server sends for it are
+/// remembered here instead of being applied. Once the server acknowledges the
+/// sequence number of the prediction, the block is set to whatever the server
+/// last said it was, which undoes the prediction if the server rejected it.
+#[derive(Component, Clone, Debug, Default)]
+pub struct BlockStatePredictions {
+    server_states: HashMap<BlockPos, ServerVerifiedState>,
+    last_acked_sequence: u32,
+}
+
+#[derive(Clone, Debug)]
+struct ServerVerifiedState {
+    sequence: u32,
+    state: BlockState,
+}
+
+impl BlockStatePredictions {
+    /// Start predicting the block at `pos`. `state` should be the state the
+    /// block had before we changed it locally.
+    pub fn retain_known_server_state(&mut self, pos: BlockPos, state: BlockState, sequence: u32) {
+        self.server_states
+            .entry(pos)
+            .and_modify(|s| s.sequence = sequence)
+            .or_insert(ServerVerifiedState { sequence, state });
+    }
+
+    /// Remember a block update from the server. Returns true if the block is
+    /// being predicted, in which case the update shouldn't be applied to the
+    /// world yet.
+    pub fn update_known_server_state(&mut self, pos: &BlockPos, state: BlockState) -> bool {
+        if let Some(server_state) = self.server_states.get_mut(pos) {
+            server_state.state = state;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Stop predicting every block with a sequence number up to and including
+    /// `sequence`, and return the states they should be set to.
+    pub fn end_predictions_up_to(&mut self, sequence: u32) -> Vec<(BlockPos, BlockState)> {
+        self.last_acked_sequence = self.last_acked_sequence.max(sequence);
+
+        let mut ended = Vec::new();
+        self.server_states.retain(|pos, server_state| {
+            if server_state.sequence <= sequence {
+                ended.push((*pos, server_state.state));
+                false
+            } else {
+                true
+            }
+        });
+        ended
+    }
+
+    /// Whether we're waiting for the server to acknowledge a change to the
+    /// block at `pos`.
+    pub fn is_predicting(&self, pos: &BlockPos) -> bool {
+        self.server_states.contains_key(pos)
+    }
+
+    /// The highest sequence number the server has acknowledged.
+    pub fn last_acked_sequence(&self) -> u32 {
+        self.last_acked_sequence
+    }
+}
+
+fn handle_block_interact_event(
+    mut events: EventReader<BlockInteractEvent>,
+    mut query: Query<(
+        &mut LocalPlayer,
+        &mut CurrentSequenceNumber,
+        &Position,
+        &EyeHeight,
+    )>,
+) {
+    for event in events.iter() {
+        let Ok((mut local_player, mut sequence_number, position, eye_height)) =
+            query.get_mut(event.entity) else {
+                continue;
+            };
+
+        if !local_player
+            .world
+            .read()
+            .world_border
+            .is_within_border(&event.position)
+        {
+            continue;
+        }
+
+        **sequence_number += 1;
+
+        let eye_position = position.up(**eye_height as f64);
+        let direction = closest_face(&eye_position, &event.position);
+        let block_hit = BlockHitResult {
+            location: face_center(&event.position, direction),
+            direction,
+            block_pos: event.position,
+            miss: false,
+            inside: false,
+        };
+
+        local_player.write_packet(
+            ServerboundUseItemOnPacket {
+                hand: InteractionHand::MainHand,
+                block_hit: block_hit.into(),
+                sequence: **sequence_number,
+            }
+            .get(),
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_place_block_event(
+    mut events: EventReader<PlaceBlockEvent>,
+    mut query: Query<(
+        &mut LocalPlayer,
+        &mut CurrentSequenceNumber,
+        &mut BlockStatePredictions,
+        &Inventory,
+        &Position,
+        &EyeHeight,
+        &mut Physics,
+    )>,
+) {
+    for event in events.iter() {
+        let Ok((
+            mut local_player,
+            mut sequence_number,
+            mut predictions,
+            inventory,
+            position,
+            eye_height,
+            mut physics,
+        )) = query.get_mut(event.entity) else {
+            continue;
+        };
+
+        if !local_player
+            .world
+            .read()
+            .world_border
+            .is_within_border(&event.against)
+        {
+            continue;
+        }
+
+        let eye_position = position.up(**eye_height as f64);
+        let location = face_center(&event.against, event.face);
+        let (y_rot, x_rot) = direction_looking_at(&eye_position, &location);
+        set_rotation(&mut physics, y_rot, x_rot);
+
+        **sequence_number += 1;
+
+        let target = event.against + event.face.normal();
+        let mut predicted = false;
+        if let Some(placed_state) = placed_block_state(inventory.held_item()) {
+            let world_lock = local_player.world.clone();
+            let world = world_lock.read();
+            let target_is_air = world
+                .chunks
+                .get_block_state(&target)
+                .is_some_and(|state| state.is_air());
+            // blocks can't be placed where they'd collide with us
+            let collides_with_us = Box::<dyn Block>::from(placed_state)
+                .behavior()
+                .has_collision
+                && physics.bounding_box.intersects_vec3(
+                    &Vec3::new(target.x as f64, target.y as f64, target.z as f64),
+                    &Vec3::new(
+                        target.x as f64 + 1.,
+                        target.y as f64 + 1.,
+                        target.z as f64 + 1.,
+                    ),
+                );
+            if target_is_air && !collides_with_us {
+                predictions.retain_known_server_state(target, BlockState::AIR, **sequence_number);
+                world.chunks.set_block_state(&target, placed_state);
+                predicted = true;
+            }
+        }
+
+        let block_hit = BlockHitResult {
+            location,
+            direction: event.face,
+            block_pos: event.against,
+            miss: false,
+            inside: false,
+        };
+        local_player.write_packet(
+            ServerboundUseItemOnPacket {
+                hand: InteractionHand::MainHand,
+                block_hit: block_hit.into(),
+                sequence: **sequence_number,
+            }
+            .get(),
+        );
+        if predicted {
+            local_player.write_packet(
+                ServerboundSwingPacket {
+                    hand: InteractionHand::MainHand,
+                }
+                .get(),
+            );
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_use_item_event(
+    mut events: EventReader<UseItemEvent>,
+    mut query: Query<(
+        &mut LocalPlayer,
+        &mut CurrentSequenceNumber,
+        &Inventory,
+        &FallFlying,
+        &mut FireworkBoosts,
+    )>,
+) {
+    for event in events.iter() {
+        let Ok((
+            mut local_player,
+            mut sequence_number,
+            inventory,
+            fall_flying,
+            mut firework_boosts,
+        )) = query.get_mut(event.entity) else {
+            continue;
+        };
+
+        // the server attaches the rocket to us, but we have to do the pushing
+        if **fall_flying {
+            if let Some(flight_duration) = firework_flight_duration(inventory.held_item()) {
+                firework_boosts.add_rocket(flight_duration);
+            }
+        }
+
+        **sequence_number += 1;
+        local_player.write_packet(
+            ServerboundUseItemPacket {
+                hand: InteractionHand::MainHand,
+                sequence: **sequence_number,
+            }
+            .get(),
+        );
+    }
+}
+
+/// The `Flight` value of a firework rocket item, or `None` if the item isn't a
+/// firework rocket.
+fn firework_flight_duration(item: &Slot) -> Option<u8> {
+    let Slot::Present(item) = item else {
+        return None;
+    };
+    if azalea_registry::Item::try_from(item.id) != Ok(azalea_registry::Item::FireworkRocket) {
+        return None;
+    }
+    let flight_duration = item
+        .tag()
+        .and_then(|tag| tag.as_compound())
+        .and_then(|compound| compound.get("Fireworks"))
+        .and_then(|fireworks| fireworks.as_compound())
+        .and_then(|fireworks| fireworks.get("Flight"))
+        .and_then(|flight| flight.as_byte())
+        .copied()
+        .unwrap_or(0);
+    Some(flight_duration.max(0) as u8)
+}
+
+/// The state we expect a block to be in right after placing the item, or
+/// `None` if the item doesn't place a block we know about.
+///
+/// This is only the default state of the block, so it may be wrong for blocks
+/// that depend on how they were placed (like stairs), but the server will
+/// correct it.
+fn placed_block_state(item: &Slot) -> Option<BlockState> {
+    let Slot::Present(item) = item else {
+        return None;
+    };
+    let item = azalea_registry::Item::try_from(item.id).ok()?;
+    // most block items have the same id as their block
+    let block = azalea_registry::Block::from_str(&item.to_string()).ok()?;
+    Some(BlockState::from(block))
+}
+
+/// Whether the server will let a player with their eyes at `eye_position`
+/// interact with the block at `block_pos`.
+pub fn can_reach_block(eye_position: &Vec3, block_pos: &BlockPos) -> bool {
+    eye_position.distance_to_sqr(&block_pos.center())
+        <= MAX_INTERACTION_DISTANCE * MAX_INTERACTION_DISTANCE
+}
+
+/// Get the face of the block at `block_pos` that's the most directly facing
+/// `from`.
+pub fn closest_face(from: &Vec3, block_pos: &BlockPos) -> Direction {
+    let delta = from - &block_pos.center();
+    if delta.x.abs() > delta.y.abs() && delta.x.abs() > delta.z.abs() {
+        if delta.x > 0. {
+            Direction::East
+        } else {
+            Direction::West
+        }
+    } else if delta.z.abs() > delta.y.abs() {
+        if delta.z > 0. {
+            Direction::South
+        } else {
+            Direction::North
+        }
+    } else if delta.y > 0. {
+        Direction::Up
+    } else {
+        Direction::Down
+    }
+}
+
+/// Get the point in the middle of the given face of a block.
+pub fn face_center(block_pos: &BlockPos, direction: Direction) -> Vec3 {
+    let normal = direction.normal();
+    let center = block_pos.center();
+    Vec3 {
+        x: center.x + normal.x as f64 * 0.5,
+        y: center.y + normal.y as f64 * 0.5,
+        z: center.z + normal.z as f64 * 0.5,
+    }
+}
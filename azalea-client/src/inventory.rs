@@ -0,0 +1,1317 @@
+//! Keep track of the items in the player's inventory and in whatever container
+//! they have open, and click around in them.
+
+use std::{cmp, collections::HashMap, ops::Range};
+
+use azalea_chat::FormattedText;
+use azalea_core::{BlockPos, Slot, SlotData};
+use azalea_protocol::packets::game::{
+    serverbound_container_click_packet::{ClickType, ServerboundContainerClickPacket},
+    serverbound_container_close_packet::ServerboundContainerClosePacket,
+    serverbound_set_carried_item_packet::ServerboundSetCarriedItemPacket,
+};
+use azalea_registry::Item;
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EventReader,
+    schedule::IntoSystemConfigs,
+    system::Query,
+};
+use log::warn;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    local_player::{handle_send_packet_event, LocalPlayer},
+    Client,
+};
+
+pub struct InventoryPlugin;
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ContainerClickEvent>()
+            .add_event::<CloseContainerEvent>()
+            .add_event::<SetSelectedHotbarSlotEvent>()
+            .add_systems(
+                (
+                    handle_container_click_event,
+                    handle_close_container_event,
+                    handle_set_selected_hotbar_slot_event,
+                )
+                    .chain()
+                    .before(handle_send_packet_event),
+            );
+    }
+}
+
+/// The number of ticks [`Client::open_container`] will wait for the server to
+/// open the container before giving up.
+const OPEN_CONTAINER_TIMEOUT_TICKS: usize = 40;
+
+impl Client {
+    /// Right click a block and wait for the server to open a container for
+    /// it, like a chest or furnace.
+    ///
+    /// This returns the id of the container that was opened, or `None` if the
+    /// server didn't open anything within a couple seconds (for example if the
+    /// block isn't a container or it's too far away). You can look at the
+    /// contents with [`Client::menu`], click it with [`Client::click`], and
+    /// you should close it with [`Client::close_container`] when you're done.
+    ///
+    /// ```rust,no_run
+    /// # use azalea_client::Client;
+    /// # use azalea_core::BlockPos;
+    /// # async fn example(mut bot: Client) {
+    /// if bot.open_container(BlockPos::new(0, 64, 0)).await.is_some() {
+    ///     let menu = bot.menu();
+    ///     for (i, slot) in menu.slots[menu.container_slots()].iter().enumerate() {
+    ///         println!("{i}: {slot:?}");
+    ///     }
+    ///     bot.close_container();
+    /// }
+    /// # }
+    /// ```
+    pub async fn open_container(&mut self, pos: BlockPos) -> Option<u8> {
+        self.ecs
+            .lock()
+            .entity_mut(self.entity)
+            .insert(WaitingForContainerOpen);
+        self.block_interact(pos);
+
+        let mut ticks = self.get_tick_broadcaster();
+        for _ in 0..OPEN_CONTAINER_TIMEOUT_TICKS {
+            if let Err(RecvError::Closed) = ticks.recv().await {
+                break;
+            }
+            let ecs = self.ecs.lock();
+            if ecs.get::<WaitingForContainerOpen>(self.entity).is_none() {
+                return ecs.get::<Inventory>(self.entity).map(|inventory| inventory.id);
+            }
+        }
+
+        self.ecs
+            .lock()
+            .entity_mut(self.entity)
+            .remove::<WaitingForContainerOpen>();
+        None
+    }
+
+    /// Get the menu that's currently open. If no container is open, this will
+    /// be the player's inventory.
+    pub fn menu(&self) -> Menu {
+        let mut ecs = self.ecs.lock();
+        let inventory = self.query::<&Inventory>(&mut ecs);
+        inventory.menu().clone()
+    }
+
+    /// Click a slot in the menu that's currently open. The result of the
+    /// click is predicted locally, and the server will correct us if we got it
+    /// wrong.
+    ///
+    /// ```rust,no_run
+    /// # use azalea_client::{Client, inventory::{ClickOperation, QuickMoveClick}};
+    /// # fn example(mut bot: Client) {
+    /// // shift click the first slot of the open container
+    /// bot.click(QuickMoveClick::Left { slot: 0 });
+    /// # }
+    /// ```
+    pub fn click(&mut self, operation: impl Into<ClickOperation>) {
+        let mut ecs = self.ecs.lock();
+        let window_id = self.query::<&Inventory>(&mut ecs).id;
+        ecs.send_event(ContainerClickEvent {
+            entity: self.entity,
+            window_id,
+            operation: operation.into(),
+        });
+    }
+
+    /// Close the container that's currently open, or the player's inventory if
+    /// there's no container open.
+    pub fn close_container(&mut self) {
+        let mut ecs = self.ecs.lock();
+        let id = self.query::<&Inventory>(&mut ecs).id;
+        ecs.send_event(CloseContainerEvent {
+            entity: self.entity,
+            id,
+        });
+    }
+
+    /// Hold the item in the given hotbar slot, from 0 to 8, like scrolling or
+    /// pressing a number key.
+    pub fn set_selected_hotbar_slot(&mut self, slot: u8) {
+        self.ecs.lock().send_event(SetSelectedHotbarSlotEvent {
+            entity: self.entity,
+            slot,
+        });
+    }
+}
+
+/// A component present on local players that keeps track of the items in their
+/// inventory and in the container they have open.
+#[derive(Component, Debug, Clone)]
+pub struct Inventory {
+    /// The player's own inventory. This is always open in the background, even
+    /// when another container is open.
+    ///
+    /// Slot 0 is the crafting result, 1-4 are the crafting grid, 5-8 are the
+    /// armor slots, 9-35 are the main inventory, 36-44 are the hotbar and 45
+    /// is the offhand.
+    pub inventory_menu: Menu,
+    /// The id of the container that's currently open, or 0 if only the
+    /// player's inventory is open.
+    pub id: u8,
+    /// The container that's currently open, if any.
+    pub container_menu: Option<Menu>,
+    /// The item that's being held by the cursor in the open menu.
+    pub carried: Slot,
+    /// A number that the server increments every time it changes the open
+    /// menu. We send it back whenever we click so the server can tell whether
+    /// we're out of sync.
+    pub state_id: u32,
+    /// The hotbar slot that the player is holding, from 0 to 8.
+    pub selected_hotbar_slot: u8,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self {
+            inventory_menu: Menu::player(),
+            id: 0,
+            container_menu: None,
+            carried: Slot::Empty,
+            state_id: 0,
+            selected_hotbar_slot: 0,
+        }
+    }
+}
+
+/// A marker component that's present while we're waiting for the server to
+/// open a container.
+#[derive(Component)]
+pub struct WaitingForContainerOpen;
+
+impl Inventory {
+    /// The menu that's currently open. If no container is open, this is the
+    /// player's inventory.
+    pub fn menu(&self) -> &Menu {
+        self.container_menu.as_ref().unwrap_or(&self.inventory_menu)
+    }
+
+    /// A mutable reference to the menu that's currently open.
+    pub fn menu_mut(&mut self) -> &mut Menu {
+        self.container_menu
+            .as_mut()
+            .unwrap_or(&mut self.inventory_menu)
+    }
+
+    /// The item in the player's main hand.
+    pub fn held_item(&self) -> &Slot {
+        let hotbar = self.inventory_menu.hotbar_slots();
+        &self.inventory_menu.slots[hotbar.start + self.selected_hotbar_slot as usize]
+    }
+
+    /// Set up the state for a container the server just opened.
+    pub fn open_container(&mut self, id: u8, menu: Menu) {
+        self.id = id;
+        self.container_menu = Some(menu);
+        self.copy_inventory_to_container();
+    }
+
+    /// Forget about the open container (if any) without telling the server.
+    pub fn close_container(&mut self) {
+        self.id = 0;
+        self.container_menu = None;
+        self.carried = Slot::Empty;
+    }
+
+    /// Set a slot from an index into the player's inventory (as opposed to an
+    /// index into a menu). 0-8 are the hotbar, 9-35 are the main inventory,
+    /// 36-39 are the armor slots from the feet up, and 40 is the offhand.
+    pub fn set_inventory_item(&mut self, index: usize, item: Slot) {
+        let menu_index = match index {
+            0..=8 => 36 + index,
+            9..=35 => index,
+            36..=39 => 8 - (index - 36),
+            40 => 45,
+            _ => {
+                warn!("Tried to set item at invalid inventory index {index}");
+                return;
+            }
+        };
+        self.inventory_menu.slots[menu_index] = item;
+        self.copy_inventory_to_container();
+    }
+
+    /// Copy the slots for the player's inventory from the open container to the
+    /// inventory menu, since they're the same items.
+    pub fn copy_container_to_inventory(&mut self) {
+        if let Some(container_menu) = &self.container_menu {
+            let container_player_slots = container_menu.player_slots();
+            if container_player_slots.is_empty() {
+                return;
+            }
+            let player_slots = self.inventory_menu.player_slots();
+            self.inventory_menu.slots[player_slots]
+                .clone_from_slice(&container_menu.slots[container_player_slots]);
+        }
+    }
+
+    /// Copy the slots for the player's inventory from the inventory menu to the
+    /// open container.
+    pub fn copy_inventory_to_container(&mut self) {
+        if let Some(container_menu) = &mut self.container_menu {
+            let player_slots = container_menu.player_slots();
+            if player_slots.is_empty() {
+                return;
+            }
+            container_menu.slots[player_slots]
+                .clone_from_slice(&self.inventory_menu.slots[self.inventory_menu.player_slots()]);
+        }
+    }
+
+    /// Predict what clicking in the open menu will do, and return the slots
+    /// that changed. This is the same as what the vanilla client does, except
+    /// the rules about what items can go in which slots are simplified.
+    pub fn simulate_click(&mut self, operation: &ClickOperation) -> HashMap<u16, Slot> {
+        let old_slots = self.menu().slots.clone();
+
+        match operation {
+            ClickOperation::Pickup(PickupClick::Left { slot: None }) => {
+                // drop everything we're holding
+                self.carried = Slot::Empty;
+            }
+            ClickOperation::Pickup(PickupClick::Right { slot: None }) => {
+                // drop one of the items we're holding
+                self.carried.split(1);
+            }
+            ClickOperation::Pickup(PickupClick::Left { slot: Some(slot) }) => {
+                self.simulate_pickup(*slot as usize, false);
+            }
+            ClickOperation::Pickup(PickupClick::Right { slot: Some(slot) }) => {
+                self.simulate_pickup(*slot as usize, true);
+            }
+            ClickOperation::QuickMove(
+                QuickMoveClick::Left { slot } | QuickMoveClick::Right { slot },
+            ) => {
+                self.simulate_quick_move(*slot as usize);
+            }
+            ClickOperation::Swap(SwapClick {
+                source_slot,
+                target_slot,
+            }) => {
+                self.simulate_swap(*source_slot as usize, *target_slot);
+            }
+            ClickOperation::Throw(ThrowClick::Single { slot }) => {
+                if self.carried.is_empty() {
+                    if let Some(item) = self.menu_mut().slots.get_mut(*slot as usize) {
+                        item.split(1);
+                    }
+                }
+            }
+            ClickOperation::Throw(ThrowClick::All { slot }) => {
+                if self.carried.is_empty() {
+                    if let Some(item) = self.menu_mut().slots.get_mut(*slot as usize) {
+                        *item = Slot::Empty;
+                    }
+                }
+            }
+        }
+
+        self.copy_container_to_inventory();
+
+        let mut changed_slots = HashMap::new();
+        for (i, (old, new)) in old_slots.iter().zip(&self.menu().slots).enumerate() {
+            if old != new {
+                changed_slots.insert(i as u16, new.clone());
+            }
+        }
+        changed_slots
+    }
+
+    fn simulate_pickup(&mut self, index: usize, right: bool) {
+        let mut carried = std::mem::take(&mut self.carried);
+        let menu = self.menu_mut();
+        if index >= menu.slots.len() {
+            self.carried = carried;
+            return;
+        }
+
+        match (menu.slots[index].clone(), &carried) {
+            (Slot::Empty, Slot::Empty) => {}
+            (Slot::Empty, Slot::Present(carried_item)) => {
+                if menu.may_place(index, carried_item) {
+                    let count = if right { 1 } else { carried_item.count };
+                    let count = cmp::min(count, menu.max_stack_size(index, carried_item));
+                    menu.slots[index] = carried.split(count);
+                }
+            }
+            (Slot::Present(slot_item), Slot::Empty) => {
+                let count = if right {
+                    // half, rounded up
+                    slot_item.count - slot_item.count / 2
+                } else {
+                    slot_item.count
+                };
+                carried = menu.slots[index].split(count);
+            }
+            (Slot::Present(slot_item), Slot::Present(carried_item)) => {
+                if menu.may_place(index, carried_item) {
+                    let max = menu.max_stack_size(index, carried_item);
+                    if slot_item.is_same_item_and_nbt(carried_item) {
+                        let count = if right { 1 } else { carried_item.count };
+                        let count = cmp::min(count, max.saturating_sub(slot_item.count));
+                        carried.split(count);
+                        grow(&mut menu.slots[index], count);
+                    } else if carried_item.count <= max {
+                        std::mem::swap(&mut menu.slots[index], &mut carried);
+                    }
+                } else if slot_item.is_same_item_and_nbt(carried_item) {
+                    // we can't put items here (like in a crafting result slot),
+                    // but we can add the items in it to the stack we're holding
+                    if carried_item.count + slot_item.count <= max_stack_size(carried_item) {
+                        grow(&mut carried, slot_item.count);
+                        menu.slots[index] = Slot::Empty;
+                    }
+                }
+            }
+        }
+
+        self.carried = carried;
+    }
+
+    fn simulate_quick_move(&mut self, index: usize) {
+        let menu = self.menu_mut();
+        let Some(Slot::Present(item)) = menu.slots.get(index) else {
+            return;
+        };
+
+        let player_slots = menu.player_slots();
+        let hotbar_slots = menu.hotbar_slots();
+        let main_inventory_slots = player_slots.start..hotbar_slots.start;
+
+        // the slots we try to move the item to, and whether we should go through
+        // them backwards
+        let targets = match menu.kind {
+            None => {
+                let empty_equipment_slot =
+                    equipment_slot(item).filter(|&i| menu.slots[i].is_empty());
+                if index < 9 {
+                    vec![(player_slots, index == 0)]
+                } else if let Some(i) = empty_equipment_slot {
+                    vec![(i..i + 1, false)]
+                } else if main_inventory_slots.contains(&index) {
+                    vec![(hotbar_slots, false)]
+                } else if hotbar_slots.contains(&index) {
+                    vec![(main_inventory_slots, false)]
+                } else {
+                    vec![(player_slots, false)]
+                }
+            }
+            Some(kind) => {
+                if !player_slots.contains(&index) {
+                    vec![(player_slots, true)]
+                } else if matches!(
+                    kind,
+                    azalea_registry::Menu::Generic9x1
+                        | azalea_registry::Menu::Generic9x2
+                        | azalea_registry::Menu::Generic9x3
+                        | azalea_registry::Menu::Generic9x4
+                        | azalea_registry::Menu::Generic9x5
+                        | azalea_registry::Menu::Generic9x6
+                        | azalea_registry::Menu::Generic3x3
+                        | azalea_registry::Menu::ShulkerBox
+                        | azalea_registry::Menu::Hopper
+                ) {
+                    vec![(menu.container_slots(), false)]
+                } else if main_inventory_slots.contains(&index) {
+                    // other menus have special rules about what goes where, so
+                    // we only predict the fallback of moving between the main
+                    // inventory and hotbar
+                    vec![(hotbar_slots, false)]
+                } else {
+                    vec![(main_inventory_slots, false)]
+                }
+            }
+        };
+
+        let mut item = std::mem::take(&mut menu.slots[index]);
+        for (range, reverse) in targets {
+            menu.move_item_to(&mut item, range, reverse);
+            if item.is_empty() {
+                break;
+            }
+        }
+        menu.slots[index] = item;
+    }
+
+    fn simulate_swap(&mut self, index: usize, hotbar_index: u8) {
+        // menus without the player's inventory (like lecterns) have nothing to
+        // swap with
+        if index >= self.menu().slots.len() || self.menu().hotbar_slots().is_empty() {
+            return;
+        }
+        // the index of the inventory slot we're swapping with in the open menu,
+        // or None if it's the offhand and a container is open
+        let target_index = match hotbar_index {
+            0..=8 => Some(self.menu().hotbar_slots().start + hotbar_index as usize),
+            40 if self.container_menu.is_none() => Some(45),
+            40 => None,
+            _ => return,
+        };
+        let target_item = match target_index {
+            Some(i) => self.menu().slots[i].clone(),
+            None => self.inventory_menu.slots[45].clone(),
+        };
+
+        let menu = self.menu_mut();
+        let slot_item = menu.slots[index].clone();
+        let (new_slot_item, new_target_item) = match (&slot_item, &target_item) {
+            (Slot::Empty, Slot::Empty) => return,
+            (Slot::Present(_), Slot::Empty) => (Slot::Empty, slot_item),
+            (_, Slot::Present(target)) => {
+                if !menu.may_place(index, target) {
+                    return;
+                }
+                let max = menu.max_stack_size(index, target);
+                if target.count <= max {
+                    (target_item, slot_item)
+                } else if slot_item.is_empty() {
+                    let mut rest = target_item.clone();
+                    (rest.split(max), rest)
+                } else {
+                    // vanilla tries to put the item somewhere else in the
+                    // inventory here, we don't bother predicting that
+                    return;
+                }
+            }
+        };
+
+        menu.slots[index] = new_slot_item;
+        match target_index {
+            Some(i) => menu.slots[i] = new_target_item,
+            None => self.inventory_menu.slots[45] = new_target_item,
+        }
+    }
+}
+
+/// A menu (also called a container or window) that's open for the player,
+/// like their inventory or a chest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Menu {
+    /// The kind of menu this is, or `None` if it's the player's inventory.
+    pub kind: Option<azalea_registry::Menu>,
+    /// The title of the menu that's shown at the top of the screen.
+    pub title: FormattedText,
+    /// All the slots in the menu, including the ones for the player's
+    /// inventory at the end.
+    pub slots: Vec<Slot>,
+    /// Extra numbers the server sent us about the menu, like the progress of
+    /// the arrow in a furnace.
+    pub data: HashMap<u16, u16>,
+}
+
+impl Menu {
+    /// Create an empty menu of the given kind.
+    pub fn new(kind: azalea_registry::Menu, title: FormattedText) -> Self {
+        let container_slot_count = container_slot_count(kind);
+        // lecterns are the only menu that don't show the player's inventory
+        let player_slot_count = if kind == azalea_registry::Menu::Lectern {
+            0
+        } else {
+            36
+        };
+        Self {
+            kind: Some(kind),
+            title,
+            slots: vec![Slot::Empty; container_slot_count + player_slot_count],
+            data: HashMap::new(),
+        }
+    }
+
+    /// Create an empty player inventory menu.
+    pub fn player() -> Self {
+        Self {
+            kind: None,
+            title: FormattedText::default(),
+            slots: vec![Slot::Empty; 46],
+            data: HashMap::new(),
+        }
+    }
+
+    /// The slots in this menu that aren't part of the player's inventory. For
+    /// the player's inventory menu, this is the crafting and armor slots.
+    pub fn container_slots(&self) -> Range<usize> {
+        match self.kind {
+            Some(kind) => 0..container_slot_count(kind),
+            None => 0..9,
+        }
+    }
+
+    /// The slots in this menu for the player's main inventory and hotbar.
+    pub fn player_slots(&self) -> Range<usize> {
+        match self.kind {
+            Some(azalea_registry::Menu::Lectern) => 1..1,
+            Some(kind) => {
+                let start = container_slot_count(kind);
+                start..start + 36
+            }
+            None => 9..45,
+        }
+    }
+
+    /// The slots in this menu for the player's hotbar.
+    pub fn hotbar_slots(&self) -> Range<usize> {
+        let player_slots = self.player_slots();
+        if player_slots.is_empty() {
+            return player_slots;
+        }
+        player_slots.end - 9..player_slots.end
+    }
+
+    /// Whether the slot at the given index is where the output of the menu
+    /// goes, meaning items can be taken out of it but not put in.
+    pub fn is_result_slot(&self, index: usize) -> bool {
+        use azalea_registry::Menu::*;
+        match self.kind {
+            None | Some(Crafting) => index == 0,
+            Some(Stonecutter) => index == 1,
+            Some(
+                Anvil | BlastFurnace | Furnace | Grindstone | Merchant | Smithing | Smoker
+                | CartographyTable,
+            ) => index == 2,
+            Some(Loom) => index == 3,
+            _ => false,
+        }
+    }
+
+    /// Whether the given item can be put in the slot at the index. This only
+    /// checks result slots and armor slots.
+    pub fn may_place(&self, index: usize, item: &SlotData) -> bool {
+        if self.is_result_slot(index) {
+            return false;
+        }
+        match self.kind {
+            None if (5..9).contains(&index) => equipment_slot(item) == Some(index),
+            Some(azalea_registry::Menu::Lectern) => false,
+            _ => true,
+        }
+    }
+
+    /// The maximum number of the given item that can go in the slot at the
+    /// index.
+    pub fn max_stack_size(&self, index: usize, item: &SlotData) -> u8 {
+        let slot_max = match self.kind {
+            None if (5..9).contains(&index) => 1,
+            Some(azalea_registry::Menu::Beacon) if index == 0 => 1,
+            Some(azalea_registry::Menu::BrewingStand) if index < 3 => 1,
+            _ => 64,
+        };
+        cmp::min(slot_max, max_stack_size(item))
+    }
+
+    /// Try to move the item into the slots in the range, first by adding to
+    /// existing stacks and then by putting it in an empty slot. The item is
+    /// left with whatever couldn't be moved.
+    fn move_item_to(&mut self, item: &mut Slot, range: Range<usize>, reverse: bool) {
+        let indexes: Vec<usize> = if reverse {
+            range.rev().collect()
+        } else {
+            range.collect()
+        };
+
+        if let Slot::Present(item_data) = item.clone() {
+            if max_stack_size(&item_data) > 1 {
+                for &i in &indexes {
+                    let Slot::Present(existing) = &self.slots[i] else {
+                        continue;
+                    };
+                    if !existing.is_same_item_and_nbt(&item_data) {
+                        continue;
+                    }
+                    let max = self.max_stack_size(i, &item_data);
+                    let count = cmp::min(item.count(), max.saturating_sub(existing.count));
+                    grow(&mut self.slots[i], count);
+                    item.split(count);
+                    if item.is_empty() {
+                        return;
+                    }
+                }
+            }
+        }
+
+        let Slot::Present(item_data) = item.clone() else {
+            return;
+        };
+        for &i in &indexes {
+            if self.slots[i].is_empty() && self.may_place(i, &item_data) {
+                let max = self.max_stack_size(i, &item_data);
+                self.slots[i] = item.split(max);
+                return;
+            }
+        }
+    }
+}
+
+/// Add `count` items to a slot that already has an item in it.
+fn grow(slot: &mut Slot, count: u8) {
+    if let Slot::Present(item) = slot {
+        item.count += count;
+    }
+}
+
+/// The number of slots a menu has, not counting the ones for the player's
+/// inventory.
+fn container_slot_count(kind: azalea_registry::Menu) -> usize {
+    use azalea_registry::Menu::*;
+    match kind {
+        Generic9x1 | Generic3x3 => 9,
+        Generic9x2 => 18,
+        Generic9x3 | ShulkerBox => 27,
+        Generic9x4 => 36,
+        Generic9x5 => 45,
+        Generic9x6 => 54,
+        Beacon | Lectern => 1,
+        Enchantment | Stonecutter => 2,
+        Anvil | BlastFurnace | Furnace | Grindstone | Merchant | Smithing | Smoker
+        | CartographyTable => 3,
+        Loom => 4,
+        BrewingStand | Hopper => 5,
+        Crafting => 10,
+    }
+}
+
+/// The slot in the player's inventory menu that this item is worn in, if it's
+/// armor or something else that can go in an equipment slot.
+fn equipment_slot(item: &SlotData) -> Option<usize> {
+    match Item::try_from(item.id).ok()? {
+        Item::LeatherHelmet
+        | Item::ChainmailHelmet
+        | Item::IronHelmet
+        | Item::DiamondHelmet
+        | Item::GoldenHelmet
+        | Item::NetheriteHelmet
+        | Item::TurtleHelmet
+        | Item::CarvedPumpkin
+        | Item::SkeletonSkull
+        | Item::WitherSkeletonSkull
+        | Item::PlayerHead
+        | Item::ZombieHead
+        | Item::CreeperHead
+        | Item::DragonHead => Some(5),
+        Item::LeatherChestplate
+        | Item::ChainmailChestplate
+        | Item::IronChestplate
+        | Item::DiamondChestplate
+        | Item::GoldenChestplate
+        | Item::NetheriteChestplate
+        | Item::Elytra => Some(6),
+        Item::LeatherLeggings
+        | Item::ChainmailLeggings
+        | Item::IronLeggings
+        | Item::DiamondLeggings
+        | Item::GoldenLeggings
+        | Item::NetheriteLeggings => Some(7),
+        Item::LeatherBoots
+        | Item::ChainmailBoots
+        | Item::IronBoots
+        | Item::DiamondBoots
+        | Item::GoldenBoots
+        | Item::NetheriteBoots => Some(8),
+        Item::Shield => Some(45),
+        _ => None,
+    }
+}
+
+/// The maximum number of this item that can be in one stack.
+pub fn max_stack_size(item: &SlotData) -> u8 {
+    Item::try_from(item.id).map_or(64, item_max_stack_size)
+}
+
+/// The maximum number of an item that can be in one stack.
+pub fn item_max_stack_size(item: Item) -> u8 {
+    match item {
+        Item::ShulkerBox
+        | Item::WhiteShulkerBox
+        | Item::OrangeShulkerBox
+        | Item::MagentaShulkerBox
+        | Item::LightBlueShulkerBox
+        | Item::YellowShulkerBox
+        | Item::LimeShulkerBox
+        | Item::PinkShulkerBox
+        | Item::GrayShulkerBox
+        | Item::LightGrayShulkerBox
+        | Item::CyanShulkerBox
+        | Item::PurpleShulkerBox
+        | Item::BlueShulkerBox
+        | Item::BrownShulkerBox
+        | Item::GreenShulkerBox
+        | Item::RedShulkerBox
+        | Item::BlackShulkerBox
+        | Item::Saddle
+        | Item::Minecart
+        | Item::ChestMinecart
+        | Item::FurnaceMinecart
+        | Item::TntMinecart
+        | Item::HopperMinecart
+        | Item::CarrotOnAStick
+        | Item::WarpedFungusOnAStick
+        | Item::Elytra
+        | Item::OakBoat
+        | Item::OakChestBoat
+        | Item::SpruceBoat
+        | Item::SpruceChestBoat
+        | Item::BirchBoat
+        | Item::BirchChestBoat
+        | Item::JungleBoat
+        | Item::JungleChestBoat
+        | Item::AcaciaBoat
+        | Item::AcaciaChestBoat
+        | Item::DarkOakBoat
+        | Item::DarkOakChestBoat
+        | Item::MangroveBoat
+        | Item::MangroveChestBoat
+        | Item::TurtleHelmet
+        | Item::FlintAndSteel
+        | Item::Bow
+        | Item::WoodenSword
+        | Item::WoodenShovel
+        | Item::WoodenPickaxe
+        | Item::WoodenAxe
+        | Item::WoodenHoe
+        | Item::StoneSword
+        | Item::StoneShovel
+        | Item::StonePickaxe
+        | Item::StoneAxe
+        | Item::StoneHoe
+        | Item::GoldenSword
+        | Item::GoldenShovel
+        | Item::GoldenPickaxe
+        | Item::GoldenAxe
+        | Item::GoldenHoe
+        | Item::IronSword
+        | Item::IronShovel
+        | Item::IronPickaxe
+        | Item::IronAxe
+        | Item::IronHoe
+        | Item::DiamondSword
+        | Item::DiamondShovel
+        | Item::DiamondPickaxe
+        | Item::DiamondAxe
+        | Item::DiamondHoe
+        | Item::NetheriteSword
+        | Item::NetheriteShovel
+        | Item::NetheritePickaxe
+        | Item::NetheriteAxe
+        | Item::NetheriteHoe
+        | Item::MushroomStew
+        | Item::LeatherHelmet
+        | Item::LeatherChestplate
+        | Item::LeatherLeggings
+        | Item::LeatherBoots
+        | Item::ChainmailHelmet
+        | Item::ChainmailChestplate
+        | Item::ChainmailLeggings
+        | Item::ChainmailBoots
+        | Item::IronHelmet
+        | Item::IronChestplate
+        | Item::IronLeggings
+        | Item::IronBoots
+        | Item::DiamondHelmet
+        | Item::DiamondChestplate
+        | Item::DiamondLeggings
+        | Item::DiamondBoots
+        | Item::GoldenHelmet
+        | Item::GoldenChestplate
+        | Item::GoldenLeggings
+        | Item::GoldenBoots
+        | Item::NetheriteHelmet
+        | Item::NetheriteChestplate
+        | Item::NetheriteLeggings
+        | Item::NetheriteBoots
+        | Item::WaterBucket
+        | Item::LavaBucket
+        | Item::PowderSnowBucket
+        | Item::MilkBucket
+        | Item::PufferfishBucket
+        | Item::SalmonBucket
+        | Item::CodBucket
+        | Item::TropicalFishBucket
+        | Item::AxolotlBucket
+        | Item::TadpoleBucket
+        | Item::Bundle
+        | Item::FishingRod
+        | Item::Spyglass
+        | Item::Cake
+        | Item::WhiteBed
+        | Item::OrangeBed
+        | Item::MagentaBed
+        | Item::LightBlueBed
+        | Item::YellowBed
+        | Item::LimeBed
+        | Item::PinkBed
+        | Item::GrayBed
+        | Item::LightGrayBed
+        | Item::CyanBed
+        | Item::PurpleBed
+        | Item::BlueBed
+        | Item::BrownBed
+        | Item::GreenBed
+        | Item::RedBed
+        | Item::BlackBed
+        | Item::Shears
+        | Item::Potion
+        | Item::WritableBook
+        | Item::EnchantedBook
+        | Item::RabbitStew
+        | Item::IronHorseArmor
+        | Item::GoldenHorseArmor
+        | Item::DiamondHorseArmor
+        | Item::LeatherHorseArmor
+        | Item::CommandBlockMinecart
+        | Item::BeetrootSoup
+        | Item::SplashPotion
+        | Item::LingeringPotion
+        | Item::Shield
+        | Item::TotemOfUndying
+        | Item::KnowledgeBook
+        | Item::DebugStick
+        | Item::MusicDisc13
+        | Item::MusicDiscCat
+        | Item::MusicDiscBlocks
+        | Item::MusicDiscChirp
+        | Item::MusicDiscFar
+        | Item::MusicDiscMall
+        | Item::MusicDiscMellohi
+        | Item::MusicDiscStal
+        | Item::MusicDiscStrad
+        | Item::MusicDiscWard
+        | Item::MusicDisc11
+        | Item::MusicDiscWait
+        | Item::MusicDiscOtherside
+        | Item::MusicDisc5
+        | Item::MusicDiscPigstep
+        | Item::Trident
+        | Item::Crossbow
+        | Item::SuspiciousStew
+        | Item::FlowerBannerPattern
+        | Item::CreeperBannerPattern
+        | Item::SkullBannerPattern
+        | Item::MojangBannerPattern
+        | Item::GlobeBannerPattern
+        | Item::PiglinBannerPattern
+        | Item::GoatHorn => 1,
+        Item::OakSign
+        | Item::SpruceSign
+        | Item::BirchSign
+        | Item::JungleSign
+        | Item::AcaciaSign
+        | Item::DarkOakSign
+        | Item::MangroveSign
+        | Item::CrimsonSign
+        | Item::WarpedSign
+        | Item::Bucket
+        | Item::Snowball
+        | Item::Egg
+        | Item::EnderPearl
+        | Item::WrittenBook
+        | Item::ArmorStand
+        | Item::WhiteBanner
+        | Item::OrangeBanner
+        | Item::MagentaBanner
+        | Item::LightBlueBanner
+        | Item::YellowBanner
+        | Item::LimeBanner
+        | Item::PinkBanner
+        | Item::GrayBanner
+        | Item::LightGrayBanner
+        | Item::CyanBanner
+        | Item::PurpleBanner
+        | Item::BlueBanner
+        | Item::BrownBanner
+        | Item::GreenBanner
+        | Item::RedBanner
+        | Item::BlackBanner
+        | Item::HoneyBottle => 16,
+        _ => 64,
+    }
+}
+
+/// Something we can do in a menu with the mouse or keyboard.
+#[derive(Debug, Clone)]
+pub enum ClickOperation {
+    Pickup(PickupClick),
+    QuickMove(QuickMoveClick),
+    Swap(SwapClick),
+    Throw(ThrowClick),
+}
+
+impl ClickOperation {
+    /// The slot number that's sent to the server for this click. Clicking
+    /// outside of the menu is -999.
+    pub fn slot_num(&self) -> u16 {
+        match self {
+            ClickOperation::Pickup(
+                PickupClick::Left { slot } | PickupClick::Right { slot },
+            ) => slot.unwrap_or(-999i16 as u16),
+            ClickOperation::QuickMove(
+                QuickMoveClick::Left { slot } | QuickMoveClick::Right { slot },
+            ) => *slot,
+            ClickOperation::Swap(SwapClick { source_slot, .. }) => *source_slot,
+            ClickOperation::Throw(ThrowClick::Single { slot } | ThrowClick::All { slot }) => *slot,
+        }
+    }
+
+    /// The button number that's sent to the server for this click.
+    pub fn button_num(&self) -> u8 {
+        match self {
+            ClickOperation::Pickup(PickupClick::Left { .. })
+            | ClickOperation::QuickMove(QuickMoveClick::Left { .. })
+            | ClickOperation::Throw(ThrowClick::Single { .. }) => 0,
+            ClickOperation::Pickup(PickupClick::Right { .. })
+            | ClickOperation::QuickMove(QuickMoveClick::Right { .. })
+            | ClickOperation::Throw(ThrowClick::All { .. }) => 1,
+            ClickOperation::Swap(SwapClick { target_slot, .. }) => *target_slot,
+        }
+    }
+
+    pub fn click_type(&self) -> ClickType {
+        match self {
+            ClickOperation::Pickup(_) => ClickType::Pickup,
+            ClickOperation::QuickMove(_) => ClickType::QuickMove,
+            ClickOperation::Swap(_) => ClickType::Swap,
+            ClickOperation::Throw(_) => ClickType::Throw,
+        }
+    }
+}
+
+/// A normal click in a menu. Left clicking picks up or puts down the whole
+/// stack, and right clicking picks up half of a stack or puts down one item.
+///
+/// If the slot is `None`, it's a click outside of the menu, which drops the
+/// carried item.
+#[derive(Debug, Clone)]
+pub enum PickupClick {
+    Left { slot: Option<u16> },
+    Right { slot: Option<u16> },
+}
+impl From<PickupClick> for ClickOperation {
+    fn from(click: PickupClick) -> Self {
+        ClickOperation::Pickup(click)
+    }
+}
+
+/// Shift click a slot to move it to the other part of the menu. The left and
+/// right buttons do the same thing.
+#[derive(Debug, Clone)]
+pub enum QuickMoveClick {
+    Left { slot: u16 },
+    Right { slot: u16 },
+}
+impl From<QuickMoveClick> for ClickOperation {
+    fn from(click: QuickMoveClick) -> Self {
+        ClickOperation::QuickMove(click)
+    }
+}
+
+/// Swap a slot with a hotbar slot, like pressing a number key while hovering
+/// over it. `target_slot` is 0-8 for the hotbar, or 40 for the offhand.
+#[derive(Debug, Clone)]
+pub struct SwapClick {
+    pub source_slot: u16,
+    pub target_slot: u8,
+}
+impl From<SwapClick> for ClickOperation {
+    fn from(click: SwapClick) -> Self {
+        ClickOperation::Swap(click)
+    }
+}
+
+/// Drop one item from a slot, or the whole stack, like pressing Q while
+/// hovering over it.
+#[derive(Debug, Clone)]
+pub enum ThrowClick {
+    Single { slot: u16 },
+    All { slot: u16 },
+}
+impl From<ThrowClick> for ClickOperation {
+    fn from(click: ThrowClick) -> Self {
+        ClickOperation::Throw(click)
+    }
+}
+
+/// Click a slot in a menu. You usually want to use [`Client::click`] instead.
+pub struct ContainerClickEvent {
+    pub entity: Entity,
+    /// The id of the menu we're clicking in. If this isn't the menu that's
+    /// open, the click is ignored.
+    pub window_id: u8,
+    pub operation: ClickOperation,
+}
+
+fn handle_container_click_event(
+    mut events: EventReader<ContainerClickEvent>,
+    mut query: Query<(&mut LocalPlayer, &mut Inventory)>,
+) {
+    for event in events.iter() {
+        let Ok((mut local_player, mut inventory)) = query.get_mut(event.entity) else {
+            continue;
+        };
+        if inventory.id != event.window_id {
+            warn!(
+                "Tried to click in container {} but the open container is {}",
+                event.window_id, inventory.id
+            );
+            continue;
+        }
+
+        let changed_slots = inventory.simulate_click(&event.operation);
+
+        local_player.write_packet(
+            ServerboundContainerClickPacket {
+                container_id: inventory.id,
+                state_id: inventory.state_id,
+                slot_num: event.operation.slot_num(),
+                button_num: event.operation.button_num(),
+                click_type: event.operation.click_type(),
+                changed_slots,
+                carried_item: inventory.carried.clone(),
+            }
+            .get(),
+        );
+    }
+}
+
+/// Close a container, or the player's inventory if `id` is 0. You usually want
+/// to use [`Client::close_container`] instead.
+pub struct CloseContainerEvent {
+    pub entity: Entity,
+    pub id: u8,
+}
+
+fn handle_close_container_event(
+    mut events: EventReader<CloseContainerEvent>,
+    mut query: Query<(&mut LocalPlayer, &mut Inventory)>,
+) {
+    for event in events.iter() {
+        let Ok((mut local_player, mut inventory)) = query.get_mut(event.entity) else {
+            continue;
+        };
+        if inventory.id != event.id {
+            // it was already closed
+            continue;
+        }
+
+        local_player.write_packet(
+            ServerboundContainerClosePacket {
+                container_id: event.id,
+            }
+            .get(),
+        );
+        inventory.close_container();
+    }
+}
+
+/// Hold the item in a different hotbar slot. You usually want to use
+/// [`Client::set_selected_hotbar_slot`] instead.
+pub struct SetSelectedHotbarSlotEvent {
+    pub entity: Entity,
+    /// The hotbar slot to hold, from 0 to 8.
+    pub slot: u8,
+}
+
+pub(crate) fn handle_set_selected_hotbar_slot_event(
+    mut events: EventReader<SetSelectedHotbarSlotEvent>,
+    mut query: Query<(&mut LocalPlayer, &mut Inventory)>,
+) {
+    for event in events.iter() {
+        let Ok((mut local_player, mut inventory)) = query.get_mut(event.entity) else {
+            continue;
+        };
+        if event.slot > 8 {
+            warn!("Tried to select hotbar slot {}", event.slot);
+            continue;
+        }
+        if inventory.selected_hotbar_slot == event.slot {
+            continue;
+        }
+
+        inventory.selected_hotbar_slot = event.slot;
+        local_player.write_packet(
+            ServerboundSetCarriedItemPacket {
+                slot: event.slot as u16,
+            }
+            .get(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(item: Item, count: u8) -> Slot {
+        Slot::Present(SlotData {
+            id: item as u32,
+            count,
+            nbt: Default::default(),
+        })
+    }
+
+    fn inventory_with_chest() -> Inventory {
+        let mut inventory = Inventory::default();
+        inventory.open_container(
+            1,
+            Menu::new(azalea_registry::Menu::Generic9x3, FormattedText::default()),
+        );
+        inventory
+    }
+
+    #[test]
+    fn test_pickup_and_place() {
+        let mut inventory = Inventory::default();
+        inventory.inventory_menu.slots[9] = item(Item::Stone, 10);
+
+        let changed = inventory.simulate_click(&PickupClick::Left { slot: Some(9) }.into());
+        assert_eq!(inventory.carried, item(Item::Stone, 10));
+        assert_eq!(changed.get(&9), Some(&Slot::Empty));
+
+        inventory.simulate_click(&PickupClick::Right { slot: Some(10) }.into());
+        assert_eq!(inventory.inventory_menu.slots[10], item(Item::Stone, 1));
+        assert_eq!(inventory.carried, item(Item::Stone, 9));
+
+        inventory.simulate_click(&PickupClick::Left { slot: Some(10) }.into());
+        assert_eq!(inventory.inventory_menu.slots[10], item(Item::Stone, 10));
+        assert!(inventory.carried.is_empty());
+    }
+
+    #[test]
+    fn test_pickup_half() {
+        let mut inventory = Inventory::default();
+        inventory.inventory_menu.slots[9] = item(Item::Stone, 5);
+
+        inventory.simulate_click(&PickupClick::Right { slot: Some(9) }.into());
+        assert_eq!(inventory.carried, item(Item::Stone, 3));
+        assert_eq!(inventory.inventory_menu.slots[9], item(Item::Stone, 2));
+    }
+
+    #[test]
+    fn test_pickup_swaps_different_items() {
+        let mut inventory = Inventory::default();
+        inventory.inventory_menu.slots[9] = item(Item::Stone, 5);
+        inventory.carried = item(Item::Dirt, 3);
+
+        inventory.simulate_click(&PickupClick::Left { slot: Some(9) }.into());
+        assert_eq!(inventory.inventory_menu.slots[9], item(Item::Dirt, 3));
+        assert_eq!(inventory.carried, item(Item::Stone, 5));
+    }
+
+    #[test]
+    fn test_pickup_armor_slot() {
+        let mut inventory = Inventory {
+            carried: item(Item::Stone, 1),
+            ..Default::default()
+        };
+        inventory.simulate_click(&PickupClick::Left { slot: Some(5) }.into());
+        assert!(inventory.inventory_menu.slots[5].is_empty());
+
+        inventory.carried = item(Item::IronHelmet, 1);
+        inventory.simulate_click(&PickupClick::Left { slot: Some(5) }.into());
+        assert_eq!(inventory.inventory_menu.slots[5], item(Item::IronHelmet, 1));
+    }
+
+    #[test]
+    fn test_quick_move_inventory_to_hotbar() {
+        let mut inventory = Inventory::default();
+        inventory.inventory_menu.slots[9] = item(Item::Stone, 10);
+        inventory.inventory_menu.slots[37] = item(Item::Stone, 60);
+
+        let changed = inventory.simulate_click(&QuickMoveClick::Left { slot: 9 }.into());
+        // fills the existing stack first, then goes in the first empty slot
+        assert_eq!(inventory.inventory_menu.slots[37], item(Item::Stone, 64));
+        assert_eq!(inventory.inventory_menu.slots[36], item(Item::Stone, 6));
+        assert!(inventory.inventory_menu.slots[9].is_empty());
+        assert_eq!(changed.len(), 3);
+    }
+
+    #[test]
+    fn test_quick_move_armor() {
+        let mut inventory = Inventory::default();
+        inventory.inventory_menu.slots[20] = item(Item::DiamondBoots, 1);
+
+        inventory.simulate_click(&QuickMoveClick::Left { slot: 20 }.into());
+        assert_eq!(
+            inventory.inventory_menu.slots[8],
+            item(Item::DiamondBoots, 1)
+        );
+    }
+
+    #[test]
+    fn test_quick_move_chest() {
+        let mut inventory = inventory_with_chest();
+        let menu = inventory.menu_mut();
+        menu.slots[0] = item(Item::Stone, 10);
+
+        inventory.simulate_click(&QuickMoveClick::Left { slot: 0 }.into());
+        // goes in the player's inventory starting from the end of the hotbar
+        assert_eq!(inventory.menu().slots[62], item(Item::Stone, 10));
+        assert_eq!(inventory.inventory_menu.slots[44], item(Item::Stone, 10));
+
+        inventory.simulate_click(&QuickMoveClick::Left { slot: 62 }.into());
+        assert_eq!(inventory.menu().slots[0], item(Item::Stone, 10));
+        assert!(inventory.inventory_menu.slots[44].is_empty());
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut inventory = Inventory::default();
+        inventory.inventory_menu.slots[9] = item(Item::Stone, 10);
+        inventory.inventory_menu.slots[38] = item(Item::Dirt, 5);
+
+        inventory.simulate_click(
+            &SwapClick {
+                source_slot: 9,
+                target_slot: 2,
+            }
+            .into(),
+        );
+        assert_eq!(inventory.inventory_menu.slots[9], item(Item::Dirt, 5));
+        assert_eq!(inventory.inventory_menu.slots[38], item(Item::Stone, 10));
+    }
+
+    #[test]
+    fn test_swap_offhand() {
+        let mut inventory = Inventory::default();
+        inventory.inventory_menu.slots[9] = item(Item::Stone, 10);
+
+        inventory.simulate_click(
+            &SwapClick {
+                source_slot: 9,
+                target_slot: 40,
+            }
+            .into(),
+        );
+        assert!(inventory.inventory_menu.slots[9].is_empty());
+        assert_eq!(inventory.inventory_menu.slots[45], item(Item::Stone, 10));
+    }
+
+    #[test]
+    fn test_swap_in_lectern() {
+        let mut inventory = Inventory::default();
+        inventory.open_container(
+            1,
+            Menu::new(azalea_registry::Menu::Lectern, FormattedText::default()),
+        );
+        inventory.menu_mut().slots[0] = item(Item::WrittenBook, 1);
+
+        let changed = inventory.simulate_click(
+            &SwapClick {
+                source_slot: 0,
+                target_slot: 0,
+            }
+            .into(),
+        );
+        assert!(changed.is_empty());
+        assert_eq!(inventory.menu().slots[0], item(Item::WrittenBook, 1));
+    }
+}
@@ -18,11 +18,16 @@ pub mod disconnect;
 mod entity_query;
 mod events;
 mod get_mc_dir;
+pub mod hud;
+pub mod interact;
+pub mod inventory;
 mod local_player;
+pub mod mining;
 mod movement;
 pub mod packet_handling;
 pub mod ping;
 mod player;
+pub mod scoreboard;
 pub mod task_pool;
 
 pub use account::Account;
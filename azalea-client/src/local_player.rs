@@ -56,6 +56,7 @@ pub struct PhysicsState {
     /// player moved enough. This is that tick counter.
     pub position_remainder: u32,
     pub was_sprinting: bool,
+    pub was_sneaking: bool,
     // Whether we're going to try to start sprinting this tick. Equivalent to
     // holding down ctrl for a tick.
     pub trying_to_sprint: bool,
@@ -0,0 +1,606 @@
+//! Breaking blocks, i.e. left clicking them until they break.
+
+use azalea_block::{Block, BlockState, FluidKind};
+use azalea_core::{BlockPos, Direction, Slot};
+use azalea_physics::fluids::is_eye_in_fluid;
+use azalea_protocol::packets::game::{
+    serverbound_interact_packet::InteractionHand,
+    serverbound_player_action_packet::{self, ServerboundPlayerActionPacket},
+    serverbound_swing_packet::ServerboundSwingPacket,
+};
+use azalea_registry::{tags, Enchantment, Item, MobEffect};
+use azalea_world::entity::{
+    direction_looking_at, set_rotation, ActiveEffects, EyeHeight, Physics, Position,
+};
+use bevy_app::{App, CoreSchedule, IntoSystemAppConfig, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    system::{Commands, Query},
+};
+use derive_more::{Deref, DerefMut};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    interact::{can_reach_block, closest_face, BlockStatePredictions, CurrentSequenceNumber},
+    inventory::Inventory,
+    Client, LocalPlayer,
+};
+
+pub struct MinePlugin;
+impl Plugin for MinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(continue_mining_block.in_schedule(CoreSchedule::FixedUpdate));
+    }
+}
+
+/// The number of ticks a player has to wait after breaking a block before it
+/// can start mining the next one.
+const DESTROY_DELAY_TICKS: u32 = 5;
+
+impl Client {
+    /// Look at a block and mine it until it breaks, like holding left click on
+    /// it.
+    ///
+    /// The time this takes is the same as in vanilla, so it depends on the
+    /// block, the tool you're holding and its enchantments, your status
+    /// effects, and whether you're on the ground or underwater. This returns
+    /// whether the block was broken once the server confirmed it, so it'll
+    /// return false if the block is too far away, the server didn't let us
+    /// break it, or [`Client::stop_mining`] was called.
+    ///
+    /// ```rust,no_run
+    /// # use azalea_client::Client;
+    /// # use azalea_core::BlockPos;
+    /// # async fn example(mut bot: Client) {
+    /// if !bot.mine(BlockPos::new(0, 64, 0)).await {
+    ///     println!("couldn't break the block");
+    /// }
+    /// # }
+    /// ```
+    pub async fn mine(&mut self, position: BlockPos) -> bool {
+        let original_state = {
+            let world = self.world();
+            let world = world.read();
+            // vanilla doesn't let you mine blocks outside of the world border
+            if !world.world_border.is_within_border(&position) {
+                return false;
+            }
+            world.chunks.get_block_state(&position)
+        };
+        let Some(original_state) = original_state else {
+            return false;
+        };
+        if original_state.is_air() {
+            return false;
+        }
+
+        {
+            let mut ecs = self.ecs.lock();
+            let (position_component, eye_height) = self.query::<(&Position, &EyeHeight)>(&mut ecs);
+            let eye_position = position_component.up(**eye_height as f64);
+            if !can_reach_block(&eye_position, &position) {
+                return false;
+            }
+            ecs.entity_mut(self.entity).insert(MineTarget(position));
+        }
+
+        let mut ticks = self.get_tick_broadcaster();
+        loop {
+            if let Err(RecvError::Closed) = ticks.recv().await {
+                return false;
+            }
+            let ecs = self.ecs.lock();
+            match ecs.get::<MineTarget>(self.entity) {
+                Some(target) if **target == position => continue,
+                // we started mining a different block
+                Some(_) => return false,
+                None => {}
+            }
+            // wait for the server to tell us whether it accepted our prediction
+            let Some(predictions) = ecs.get::<BlockStatePredictions>(self.entity) else {
+                return false;
+            };
+            if !predictions.is_predicting(&position) {
+                break;
+            }
+        }
+
+        let world = self.world();
+        let world = world.read();
+        world.chunks.get_block_state(&position) != Some(original_state)
+    }
+
+    /// Stop mining the block we're currently mining, if any.
+    pub fn stop_mining(&mut self) {
+        self.ecs
+            .lock()
+            .entity_mut(self.entity)
+            .remove::<MineTarget>();
+    }
+}
+
+/// A component present on local players that are trying to mine a block. It's
+/// removed when the block breaks.
+#[derive(Component, Copy, Clone, Debug, Deref, DerefMut)]
+pub struct MineTarget(pub BlockPos);
+
+/// A component present on local players that have started mining a block,
+/// with how far along they are.
+#[derive(Component, Clone, Debug)]
+pub struct Mining {
+    pub pos: BlockPos,
+    pub direction: Direction,
+    /// How much of the block has been broken, from 0 to 1.
+    pub progress: f32,
+    /// The number of ticks we've been mining the block for.
+    pub ticks: u32,
+    /// The item we started mining with. If we switch to a different item we
+    /// have to start over.
+    pub item: Slot,
+}
+
+/// The number of ticks until a local player can start mining another block.
+#[derive(Component, Copy, Clone, Debug, Default, Deref, DerefMut)]
+pub struct MineDelay(pub u32);
+
+#[allow(clippy::type_complexity)]
+fn continue_mining_block(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut LocalPlayer,
+        Option<&MineTarget>,
+        Option<&mut Mining>,
+        &mut MineDelay,
+        &mut CurrentSequenceNumber,
+        &mut BlockStatePredictions,
+        &Inventory,
+        &Position,
+        &EyeHeight,
+        &mut Physics,
+        &ActiveEffects,
+    )>,
+) {
+    for (
+        entity,
+        mut local_player,
+        target,
+        mining,
+        mut delay,
+        mut sequence_number,
+        mut predictions,
+        inventory,
+        position,
+        eye_height,
+        mut physics,
+        active_effects,
+    ) in &mut query
+    {
+        let Some(&MineTarget(target)) = target else {
+            // we stopped mining before the block broke
+            if let Some(mining) = mining {
+                local_player.write_packet(
+                    ServerboundPlayerActionPacket {
+                        action: serverbound_player_action_packet::Action::AbortDestroyBlock,
+                        pos: mining.pos,
+                        direction: mining.direction,
+                        sequence: 0,
+                    }
+                    .get(),
+                );
+                commands.entity(entity).remove::<Mining>();
+            }
+            continue;
+        };
+
+        let eye_position = position.up(**eye_height as f64);
+        let (y_rot, x_rot) = direction_looking_at(&eye_position, &target.center());
+        set_rotation(&mut physics, y_rot, x_rot);
+
+        if **delay > 0 {
+            **delay -= 1;
+            swing(&mut local_player);
+            continue;
+        }
+
+        let world_lock = local_player.world.clone();
+        let world = world_lock.read();
+        let Some(block_state) = world.chunks.get_block_state(&target) else {
+            commands.entity(entity).remove::<(MineTarget, Mining)>();
+            continue;
+        };
+        let eyes_in_water = is_eye_in_fluid(&world, eye_position, FluidKind::Water);
+        let block = Box::<dyn Block>::from(block_state);
+
+        let held_item = inventory.held_item();
+        let is_same_target = match &mining {
+            Some(mining) => mining.pos == target && mining.item == *held_item,
+            None => false,
+        };
+
+        if is_same_target {
+            let mut mining = mining.unwrap();
+            if block_state.is_air() {
+                // someone else broke it
+                commands.entity(entity).remove::<(MineTarget, Mining)>();
+                continue;
+            }
+
+            mining.progress += get_mine_progress(
+                block.as_ref(),
+                inventory,
+                eyes_in_water,
+                physics.on_ground,
+                active_effects,
+            );
+            mining.ticks += 1;
+
+            if mining.progress >= 1. {
+                **sequence_number += 1;
+                predictions.retain_known_server_state(target, block_state, **sequence_number);
+                world.chunks.set_block_state(&target, BlockState::AIR);
+                local_player.write_packet(
+                    ServerboundPlayerActionPacket {
+                        action: serverbound_player_action_packet::Action::StopDestroyBlock,
+                        pos: mining.pos,
+                        direction: mining.direction,
+                        sequence: **sequence_number,
+                    }
+                    .get(),
+                );
+                **delay = DESTROY_DELAY_TICKS;
+                commands.entity(entity).remove::<(MineTarget, Mining)>();
+            }
+            swing(&mut local_player);
+            continue;
+        }
+
+        // we're starting to mine a new block
+        if let Some(mining) = mining {
+            local_player.write_packet(
+                ServerboundPlayerActionPacket {
+                    action: serverbound_player_action_packet::Action::AbortDestroyBlock,
+                    pos: mining.pos,
+                    direction: mining.direction,
+                    sequence: 0,
+                }
+                .get(),
+            );
+        }
+        if block_state.is_air() {
+            commands.entity(entity).remove::<(MineTarget, Mining)>();
+            continue;
+        }
+
+        let direction = closest_face(&eye_position, &target);
+        let progress = get_mine_progress(
+            block.as_ref(),
+            inventory,
+            eyes_in_water,
+            physics.on_ground,
+            active_effects,
+        );
+
+        **sequence_number += 1;
+        if progress >= 1. {
+            // instamine
+            predictions.retain_known_server_state(target, block_state, **sequence_number);
+            world.chunks.set_block_state(&target, BlockState::AIR);
+            commands.entity(entity).remove::<(MineTarget, Mining)>();
+        } else {
+            commands.entity(entity).insert(Mining {
+                pos: target,
+                direction,
+                progress: 0.,
+                ticks: 0,
+                item: held_item.clone(),
+            });
+        }
+        local_player.write_packet(
+            ServerboundPlayerActionPacket {
+                action: serverbound_player_action_packet::Action::StartDestroyBlock,
+                pos: target,
+                direction,
+                sequence: **sequence_number,
+            }
+            .get(),
+        );
+        swing(&mut local_player);
+    }
+}
+
+fn swing(local_player: &mut LocalPlayer) {
+    local_player.write_packet(
+        ServerboundSwingPacket {
+            hand: InteractionHand::MainHand,
+        }
+        .get(),
+    );
+}
+
+/// How much of a block a player breaks every tick while mining it, where 1 is
+/// the whole block.
+///
+/// This is the same calculation vanilla does, so it depends on the held item,
+/// its Efficiency level, Haste and Mining Fatigue, whether the player is on
+/// the ground, and whether their eyes are underwater (unless their helmet has
+/// Aqua Affinity).
+pub fn get_mine_progress(
+    block: &dyn Block,
+    inventory: &Inventory,
+    eyes_in_water: bool,
+    on_ground: bool,
+    active_effects: &ActiveEffects,
+) -> f32 {
+    let destroy_time = block.behavior().destroy_time;
+    if destroy_time == -1. {
+        return 0.;
+    }
+
+    let registry_block = block.as_registry_block();
+    let held_item = inventory.held_item();
+    let held_item_kind = match held_item {
+        Slot::Present(item) => Item::try_from(item.id).ok(),
+        Slot::Empty => None,
+    };
+
+    // bamboo is instantly broken by swords
+    if matches!(
+        registry_block,
+        azalea_registry::Block::Bamboo | azalea_registry::Block::BambooSapling
+    ) && held_item_kind.is_some_and(|item| tool_kind(item) == Some(ToolKind::Sword))
+    {
+        return 1.;
+    }
+
+    let has_correct_tool = !block.behavior().requires_correct_tool_for_drops
+        || held_item_kind.is_some_and(|item| is_correct_tool_for_drops(item, registry_block));
+    let divider = if has_correct_tool { 30. } else { 100. };
+
+    destroy_speed(
+        registry_block,
+        held_item,
+        held_item_kind,
+        inventory,
+        eyes_in_water,
+        on_ground,
+        active_effects,
+    ) / destroy_time
+        / divider
+}
+
+fn destroy_speed(
+    block: azalea_registry::Block,
+    held_item: &Slot,
+    held_item_kind: Option<Item>,
+    inventory: &Inventory,
+    eyes_in_water: bool,
+    on_ground: bool,
+    active_effects: &ActiveEffects,
+) -> f32 {
+    let mut speed = held_item_kind.map_or(1., |item| item_destroy_speed(item, block));
+
+    if speed > 1. {
+        if let Slot::Present(item) = held_item {
+            let efficiency = item.enchantment_level(&Enchantment::Efficiency.to_string());
+            if efficiency > 0 {
+                speed += (efficiency * efficiency + 1) as f32;
+            }
+        }
+    }
+
+    let dig_speed_amplifier = match (
+        active_effects.amplifier(MobEffect::Haste),
+        active_effects.amplifier(MobEffect::ConduitPower),
+    ) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    };
+    if let Some(amplifier) = dig_speed_amplifier {
+        speed *= 1. + (amplifier as f32 + 1.) * 0.2;
+    }
+
+    if let Some(amplifier) = active_effects.amplifier(MobEffect::MiningFatigue) {
+        speed *= match amplifier {
+            0 => 0.3,
+            1 => 0.09,
+            2 => 0.0027,
+            _ => 8.1e-4,
+        };
+    }
+
+    if eyes_in_water {
+        let helmet = &inventory.inventory_menu.slots[5];
+        let has_aqua_affinity = match helmet {
+            Slot::Present(item) => {
+                item.enchantment_level(&Enchantment::AquaAffinity.to_string()) > 0
+            }
+            Slot::Empty => false,
+        };
+        if !has_aqua_affinity {
+            speed /= 5.;
+        }
+    }
+
+    if !on_ground {
+        speed /= 5.;
+    }
+
+    speed
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ToolKind {
+    Pickaxe,
+    Axe,
+    Shovel,
+    Hoe,
+    Sword,
+    Shears,
+}
+
+/// The material a tool is made out of.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Tier {
+    Wood,
+    Stone,
+    Iron,
+    Diamond,
+    Gold,
+    Netherite,
+}
+
+impl Tier {
+    fn speed(self) -> f32 {
+        match self {
+            Tier::Wood => 2.,
+            Tier::Stone => 4.,
+            Tier::Iron => 6.,
+            Tier::Diamond => 8.,
+            Tier::Gold => 12.,
+            Tier::Netherite => 9.,
+        }
+    }
+
+    fn level(self) -> u8 {
+        match self {
+            Tier::Wood | Tier::Gold => 0,
+            Tier::Stone => 1,
+            Tier::Iron => 2,
+            Tier::Diamond => 3,
+            Tier::Netherite => 4,
+        }
+    }
+}
+
+fn tool_kind(item: Item) -> Option<ToolKind> {
+    tool(item).map(|(kind, _)| kind)
+}
+
+fn tool(item: Item) -> Option<(ToolKind, Option<Tier>)> {
+    Some(match item {
+        Item::WoodenPickaxe => (ToolKind::Pickaxe, Some(Tier::Wood)),
+        Item::StonePickaxe => (ToolKind::Pickaxe, Some(Tier::Stone)),
+        Item::IronPickaxe => (ToolKind::Pickaxe, Some(Tier::Iron)),
+        Item::DiamondPickaxe => (ToolKind::Pickaxe, Some(Tier::Diamond)),
+        Item::GoldenPickaxe => (ToolKind::Pickaxe, Some(Tier::Gold)),
+        Item::NetheritePickaxe => (ToolKind::Pickaxe, Some(Tier::Netherite)),
+
+        Item::WoodenAxe => (ToolKind::Axe, Some(Tier::Wood)),
+        Item::StoneAxe => (ToolKind::Axe, Some(Tier::Stone)),
+        Item::IronAxe => (ToolKind::Axe, Some(Tier::Iron)),
+        Item::DiamondAxe => (ToolKind::Axe, Some(Tier::Diamond)),
+        Item::GoldenAxe => (ToolKind::Axe, Some(Tier::Gold)),
+        Item::NetheriteAxe => (ToolKind::Axe, Some(Tier::Netherite)),
+
+        Item::WoodenShovel => (ToolKind::Shovel, Some(Tier::Wood)),
+        Item::StoneShovel => (ToolKind::Shovel, Some(Tier::Stone)),
+        Item::IronShovel => (ToolKind::Shovel, Some(Tier::Iron)),
+        Item::DiamondShovel => (ToolKind::Shovel, Some(Tier::Diamond)),
+        Item::GoldenShovel => (ToolKind::Shovel, Some(Tier::Gold)),
+        Item::NetheriteShovel => (ToolKind::Shovel, Some(Tier::Netherite)),
+
+        Item::WoodenHoe => (ToolKind::Hoe, Some(Tier::Wood)),
+        Item::StoneHoe => (ToolKind::Hoe, Some(Tier::Stone)),
+        Item::IronHoe => (ToolKind::Hoe, Some(Tier::Iron)),
+        Item::DiamondHoe => (ToolKind::Hoe, Some(Tier::Diamond)),
+        Item::GoldenHoe => (ToolKind::Hoe, Some(Tier::Gold)),
+        Item::NetheriteHoe => (ToolKind::Hoe, Some(Tier::Netherite)),
+
+        Item::WoodenSword => (ToolKind::Sword, Some(Tier::Wood)),
+        Item::StoneSword => (ToolKind::Sword, Some(Tier::Stone)),
+        Item::IronSword => (ToolKind::Sword, Some(Tier::Iron)),
+        Item::DiamondSword => (ToolKind::Sword, Some(Tier::Diamond)),
+        Item::GoldenSword => (ToolKind::Sword, Some(Tier::Gold)),
+        Item::NetheriteSword => (ToolKind::Sword, Some(Tier::Netherite)),
+
+        Item::Shears => (ToolKind::Shears, None),
+
+        _ => return None,
+    })
+}
+
+fn is_mineable_with(kind: ToolKind, block: azalea_registry::Block) -> bool {
+    match kind {
+        ToolKind::Pickaxe => tags::blocks::MINEABLE_PICKAXE.contains(&block),
+        ToolKind::Axe => tags::blocks::MINEABLE_AXE.contains(&block),
+        ToolKind::Shovel => tags::blocks::MINEABLE_SHOVEL.contains(&block),
+        ToolKind::Hoe => tags::blocks::MINEABLE_HOE.contains(&block),
+        ToolKind::Sword | ToolKind::Shears => false,
+    }
+}
+
+/// How fast the item breaks the block, not counting enchantments or effects.
+fn item_destroy_speed(item: Item, block: azalea_registry::Block) -> f32 {
+    use azalea_registry::Block as B;
+
+    let Some((kind, tier)) = tool(item) else {
+        return 1.;
+    };
+    match kind {
+        ToolKind::Sword => {
+            if block == B::Cobweb {
+                15.
+            } else if tags::blocks::LEAVES.contains(&block)
+                || matches!(
+                    block,
+                    B::Pumpkin
+                        | B::CarvedPumpkin
+                        | B::JackOLantern
+                        | B::Melon
+                        | B::Cocoa
+                        | B::ChorusPlant
+                        | B::ChorusFlower
+                        | B::BigDripleaf
+                        | B::BigDripleafStem
+                        | B::Vine
+                        | B::GlowLichen
+                        | B::MossCarpet
+                )
+            {
+                1.5
+            } else {
+                1.
+            }
+        }
+        ToolKind::Shears => {
+            if block == B::Cobweb || tags::blocks::LEAVES.contains(&block) {
+                15.
+            } else if tags::blocks::WOOL.contains(&block) {
+                5.
+            } else if matches!(block, B::Vine | B::GlowLichen) {
+                2.
+            } else {
+                1.
+            }
+        }
+        _ => {
+            if is_mineable_with(kind, block) {
+                tier.map_or(1., Tier::speed)
+            } else {
+                1.
+            }
+        }
+    }
+}
+
+/// Whether the item can harvest blocks that require the correct tool.
+fn is_correct_tool_for_drops(item: Item, block: azalea_registry::Block) -> bool {
+    use azalea_registry::Block as B;
+
+    let Some((kind, tier)) = tool(item) else {
+        return false;
+    };
+    match kind {
+        ToolKind::Sword => block == B::Cobweb,
+        ToolKind::Shears => matches!(block, B::Cobweb | B::RedstoneWire | B::Tripwire),
+        _ => {
+            let level = tier.map_or(0, Tier::level);
+            let tier_too_low = (level < 3 && tags::blocks::NEEDS_DIAMOND_TOOL.contains(&block))
+                || (level < 2 && tags::blocks::NEEDS_IRON_TOOL.contains(&block))
+                || (level < 1 && tags::blocks::NEEDS_STONE_TOOL.contains(&block));
+            !tier_too_low && is_mineable_with(kind, block)
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::client::Client;
+use crate::inventory::Inventory;
 use crate::local_player::{
     update_in_loaded_chunk, LocalPlayer, LocalPlayerInLoadedChunk, PhysicsState,
 };
+use azalea_core::Slot;
 use azalea_physics::{force_jump_listener, PhysicsSet};
 use azalea_protocol::packets::game::serverbound_player_command_packet::ServerboundPlayerCommandPacket;
 use azalea_protocol::packets::game::{
@@ -10,8 +12,13 @@ use azalea_protocol::packets::game::{
     serverbound_move_player_rot_packet::ServerboundMovePlayerRotPacket,
     serverbound_move_player_status_only_packet::ServerboundMovePlayerStatusOnlyPacket,
 };
+use azalea_registry::{Item, MobEffect};
 use azalea_world::{
-    entity::{self, metadata::Sprinting, Attributes, Jumping, MinecraftEntityId},
+    entity::{
+        self,
+        metadata::{FallFlying, Sprinting},
+        ActiveEffects, Attributes, Jumping, MinecraftEntityId, Sneaking,
+    },
     MoveEntityError,
 };
 use bevy_app::{App, CoreSchedule, IntoSystemAppConfigs, Plugin};
@@ -84,6 +91,21 @@ impl Client {
         **jumping_ref
     }
 
+    /// Set whether we're sneaking. This acts as if you held shift in vanilla,
+    /// so you'll move slower and won't fall off the edges of blocks.
+    pub fn set_sneaking(&mut self, sneaking: bool) {
+        let mut ecs = self.ecs.lock();
+        let mut sneaking_mut = self.query::<&mut Sneaking>(&mut ecs);
+        **sneaking_mut = sneaking;
+    }
+
+    /// Returns whether the player is currently sneaking.
+    pub fn sneaking(&self) -> bool {
+        let mut ecs = self.ecs.lock();
+        let sneaking_ref = self.query::<&Sneaking>(&mut ecs);
+        **sneaking_ref
+    }
+
     /// Sets your rotation. `y_rot` is yaw (looking to the side), `x_rot` is
     /// pitch (looking up and down). You can get these numbers from the vanilla
     /// f3 screen.
@@ -94,8 +116,59 @@ impl Client {
 
         entity::set_rotation(&mut physics, y_rot, x_rot);
     }
+
+    /// Start gliding with the elytra we're wearing. This only works if we're in
+    /// the air and have a working elytra in our chestplate slot, and returns
+    /// whether we started fall flying.
+    ///
+    /// We'll stop fall flying automatically when we land. To go faster, use a
+    /// firework rocket while flying.
+    pub fn start_fall_flying(&mut self) -> bool {
+        let mut ecs = self.ecs.lock();
+        let (mut local_player, id, physics, inventory, active_effects, mut fall_flying) = self
+            .query::<(
+                &mut LocalPlayer,
+                &MinecraftEntityId,
+                &entity::Physics,
+                &Inventory,
+                &ActiveEffects,
+                &mut FallFlying,
+            )>(&mut ecs);
+
+        if physics.on_ground
+            || **fall_flying
+            || physics.is_in_water
+            || active_effects.has(MobEffect::Levitation)
+        {
+            return false;
+        }
+
+        // slot 6 of the inventory menu is the chestplate slot
+        let Slot::Present(chest_item) = &inventory.inventory_menu.slots[6] else {
+            return false;
+        };
+        // elytras stop working when they only have 1 durability left
+        if Item::try_from(chest_item.id) != Ok(Item::Elytra)
+            || chest_item.damage() >= ELYTRA_DURABILITY - 1
+        {
+            return false;
+        }
+
+        **fall_flying = true;
+        local_player.write_packet(
+            ServerboundPlayerCommandPacket {
+                id: **id,
+                action: azalea_protocol::packets::game::serverbound_player_command_packet::Action::StartFallFlying,
+                data: 0,
+            }
+            .get(),
+        );
+        true
+    }
 }
 
+const ELYTRA_DURABILITY: u32 = 432;
+
 #[allow(clippy::type_complexity)]
 pub(crate) fn send_position(
     mut query: Query<
@@ -107,6 +180,7 @@ pub(crate) fn send_position(
             &mut entity::LastSentPosition,
             &mut entity::Physics,
             &entity::metadata::Sprinting,
+            &Sneaking,
         ),
         &LocalPlayerInLoadedChunk,
     >,
@@ -119,9 +193,11 @@ pub(crate) fn send_position(
         mut last_sent_position,
         mut physics,
         sprinting,
+        sneaking,
     ) in query.iter_mut()
     {
         local_player.send_sprinting_if_needed(id, sprinting, &mut physics_state);
+        local_player.send_sneaking_if_needed(id, sneaking, &mut physics_state);
 
         let packet = {
             // TODO: the camera being able to be controlled by other entities isn't
@@ -234,6 +310,30 @@ impl LocalPlayer {
         }
     }
 
+    fn send_sneaking_if_needed(
+        &mut self,
+        id: &MinecraftEntityId,
+        sneaking: &Sneaking,
+        physics_state: &mut PhysicsState,
+    ) {
+        if **sneaking != physics_state.was_sneaking {
+            let sneaking_action = if **sneaking {
+                azalea_protocol::packets::game::serverbound_player_command_packet::Action::PressShiftKey
+            } else {
+                azalea_protocol::packets::game::serverbound_player_command_packet::Action::ReleaseShiftKey
+            };
+            self.write_packet(
+                ServerboundPlayerCommandPacket {
+                    id: **id,
+                    action: sneaking_action,
+                    data: 0,
+                }
+                .get(),
+            );
+            physics_state.was_sneaking = **sneaking;
+        }
+    }
+
     /// Update the impulse from self.move_direction. The multipler is used for
     /// sneaking.
     pub(crate) fn tick_controls(multiplier: Option<f32>, physics_state: &mut PhysicsState) {
@@ -279,12 +379,17 @@ pub fn local_player_ai_step(
             &mut entity::Physics,
             &mut entity::metadata::Sprinting,
             &mut entity::Attributes,
+            &Sneaking,
         ),
         With<LocalPlayerInLoadedChunk>,
     >,
 ) {
-    for (mut physics_state, mut physics, mut sprinting, mut attributes) in query.iter_mut() {
-        LocalPlayer::tick_controls(None, &mut physics_state);
+    for (mut physics_state, mut physics, mut sprinting, mut attributes, sneaking) in
+        query.iter_mut()
+    {
+        // sneaking makes you move at 30% of the normal speed
+        let multiplier = if **sneaking { Some(0.3) } else { None };
+        LocalPlayer::tick_controls(multiplier, &mut physics_state);
 
         // server ai step
         physics.xxa = physics_state.left_impulse;
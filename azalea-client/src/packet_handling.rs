@@ -1,11 +1,18 @@
-use std::{collections::HashSet, io::Cursor, sync::Arc};
+use std::{collections::HashSet, io::Cursor, str::FromStr, sync::Arc, time::Duration};
 
 use azalea_auth::game_profile::GameProfile;
-use azalea_core::{ChunkPos, ResourceLocation, Vec3};
+use azalea_chat::FormattedText;
+use azalea_core::{rotation_from_byte, ChunkBlockPos, ChunkPos, ResourceLocation, Vec3};
 use azalea_protocol::{
     connect::{ReadConnection, WriteConnection},
     packets::game::{
+        clientbound_boss_event_packet::Operation as BossBarOperation,
+        clientbound_level_chunk_with_light_packet::BlockEntity as PacketBlockEntity,
+        clientbound_light_update_packet::ClientboundLightUpdatePacketData,
         clientbound_player_combat_kill_packet::ClientboundPlayerCombatKillPacket,
+        clientbound_set_objective_packet::Method as ObjectiveMethod,
+        clientbound_set_player_team_packet::Method as TeamMethod,
+        clientbound_set_score_packet::Method as ScoreMethod,
         serverbound_accept_teleportation_packet::ServerboundAcceptTeleportationPacket,
         serverbound_custom_payload_packet::ServerboundCustomPayloadPacket,
         serverbound_keep_alive_packet::ServerboundKeepAlivePacket,
@@ -16,18 +23,22 @@ use azalea_protocol::{
 };
 use azalea_world::{
     entity::{
+        effects::update_effect_attribute_modifiers,
         metadata::{apply_metadata, Health, PlayerMetadataBundle},
-        set_rotation, Dead, EntityBundle, EntityKind, EntityUpdateSet, LastSentPosition,
-        MinecraftEntityId, Physics, PlayerBundle, Position, WorldName,
+        set_rotation, ActiveEffects, Attributes, Dead, EntityBundle, EntityKind, EntityUpdateSet,
+        HeadRotation, LastSentPosition, MinecraftEntityId, MobEffectInstance, Physics,
+        PlayerBundle, Position, WorldName,
     },
-    entity::{LoadedBy, RelativeEntityUpdate},
-    InstanceContainer, PartialInstance,
+    entity::{LoadedBy, Local, RelativeEntityUpdate},
+    light::LightLayer,
+    BlockEntity, Chunk, InstanceContainer, PartialInstance,
 };
 use bevy_app::{App, CoreSet, Plugin};
 use bevy_ecs::{
     component::Component,
     entity::Entity,
     event::{EventReader, EventWriter, Events},
+    query::Without,
     schedule::IntoSystemConfig,
     system::{Commands, Query, ResMut, SystemState},
     world::World,
@@ -35,12 +46,17 @@ use bevy_ecs::{
 use log::{debug, error, trace, warn};
 use parking_lot::Mutex;
 use tokio::sync::mpsc;
+use uuid::Uuid;
 
 use crate::{
     chat::{ChatPacket, ChatReceivedEvent},
     client::TabList,
     disconnect::DisconnectEvent,
+    hud::{ActionBar, BossBar, BossBars, TabListHeaderFooter, Titles},
+    interact::BlockStatePredictions,
+    inventory::{Inventory, Menu, WaitingForContainerOpen},
     local_player::{GameProfileComponent, LocalPlayer},
+    scoreboard::{DisplaySlot, Objective, ScoreUpdate, Scoreboard, Team},
     ClientInformation, PlayerInfo,
 };
 
@@ -89,7 +105,17 @@ impl Plugin for PacketHandlerPlugin {
             .add_event::<UpdatePlayerEvent>()
             .add_event::<ChatReceivedEvent>()
             .add_event::<DeathEvent>()
-            .add_event::<KeepAliveEvent>();
+            .add_event::<KeepAliveEvent>()
+            .add_event::<TeleportedEvent>()
+            .add_event::<UpdateScoreEvent>()
+            .add_event::<AddBossBarEvent>()
+            .add_event::<UpdateBossBarEvent>()
+            .add_event::<RemoveBossBarEvent>()
+            .add_event::<SetTitleEvent>()
+            .add_event::<SetSubtitleEvent>()
+            .add_event::<SetActionBarEvent>()
+            .add_event::<TabListHeaderFooterEvent>()
+            .add_event::<ChunkUnloadedEvent>();
     }
 }
 
@@ -137,6 +163,78 @@ pub struct KeepAliveEvent {
     pub id: u64,
 }
 
+/// The server teleported a local player, either because of something like a
+/// `/tp` command or because it didn't accept a movement (rubber-banding).
+#[derive(Debug, Clone)]
+pub struct TeleportedEvent {
+    pub entity: Entity,
+    /// Where the player was before the teleport.
+    pub old_position: Vec3,
+    /// Where the player is now.
+    pub new_position: Vec3,
+}
+
+/// A score in the scoreboard of a local player was changed or removed.
+#[derive(Debug, Clone)]
+pub struct UpdateScoreEvent {
+    pub entity: Entity,
+    pub update: ScoreUpdate,
+}
+
+/// A boss bar was added to the screen of a local player.
+#[derive(Debug, Clone)]
+pub struct AddBossBarEvent {
+    pub entity: Entity,
+    pub bar: BossBar,
+}
+/// The progress, name, style or properties of a boss bar of a local player was
+/// changed.
+#[derive(Debug, Clone)]
+pub struct UpdateBossBarEvent {
+    pub entity: Entity,
+    pub bar: BossBar,
+}
+/// A boss bar was removed from the screen of a local player.
+#[derive(Debug, Clone)]
+pub struct RemoveBossBarEvent {
+    pub entity: Entity,
+    pub bar: BossBar,
+}
+
+/// A title was shown to a local player.
+#[derive(Debug, Clone)]
+pub struct SetTitleEvent {
+    pub entity: Entity,
+    pub text: FormattedText,
+}
+/// The subtitle of a local player was set. Note that it's only shown once
+/// there's also a title.
+#[derive(Debug, Clone)]
+pub struct SetSubtitleEvent {
+    pub entity: Entity,
+    pub text: FormattedText,
+}
+/// Some text was shown in the action bar of a local player.
+#[derive(Debug, Clone)]
+pub struct SetActionBarEvent {
+    pub entity: Entity,
+    pub text: FormattedText,
+}
+/// The header and footer of the tab list of a local player were changed.
+#[derive(Debug, Clone)]
+pub struct TabListHeaderFooterEvent {
+    pub entity: Entity,
+    pub header_footer: TabListHeaderFooter,
+}
+
+/// The server told a local player to forget about a chunk. The chunk is only
+/// removed from the shared world if no other local player is using it.
+#[derive(Debug, Clone)]
+pub struct ChunkUnloadedEvent {
+    pub entity: Entity,
+    pub pos: ChunkPos,
+}
+
 /// Something that receives packets from the server.
 #[derive(Component, Clone)]
 pub struct PacketReceiver {
@@ -228,6 +326,12 @@ fn process_packet_events(ecs: &mut World) {
                         dimension.height,
                         dimension.min_y,
                     );
+                    weak_world.write().biome_registry = p
+                        .registry_holder
+                        .root
+                        .biome_names()
+                        .into_iter()
+                        .collect();
                     // set the partial_world to an empty world
                     // (when we add chunks or entities those will be in the
                     // world_container)
@@ -277,6 +381,20 @@ fn process_packet_events(ecs: &mut World) {
             }
             ClientboundGamePacket::SetChunkCacheRadius(p) => {
                 debug!("Got set chunk cache radius packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let mut world = local_player.world.write();
+                let mut partial_world = local_player.partial_instance.write();
+
+                // vanilla keeps a few extra chunks around in case the server
+                // is slow to tell us about the view center moving
+                let chunk_radius = u32::max(p.radius, 2) + 3;
+                partial_world
+                    .chunks
+                    .set_chunk_radius(chunk_radius, &mut world.chunks);
             }
             ClientboundGamePacket::CustomPayload(p) => {
                 debug!("Got custom payload packet {:?}", p);
@@ -292,6 +410,12 @@ fn process_packet_events(ecs: &mut World) {
             }
             ClientboundGamePacket::SetCarriedItem(p) => {
                 debug!("Got set carried item packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut Inventory>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut inventory = query.get_mut(player_entity).unwrap();
+
+                inventory.selected_hotbar_slot = p.slot;
             }
             ClientboundGamePacket::UpdateTags(_p) => {
                 debug!("Got update tags packet");
@@ -317,48 +441,44 @@ fn process_packet_events(ecs: &mut World) {
                 debug!("Got recipe packet");
             }
             ClientboundGamePacket::PlayerPosition(p) => {
-                // TODO: reply with teleport confirm
                 debug!("Got player position packet {:?}", p);
 
-                let mut system_state: SystemState<
+                #[allow(clippy::type_complexity)]
+                let mut system_state: SystemState<(
                     Query<(
                         &mut LocalPlayer,
                         &mut Physics,
                         &mut Position,
                         &mut LastSentPosition,
                     )>,
-                > = SystemState::new(ecs);
-                let mut query = system_state.get_mut(ecs);
+                    EventWriter<TeleportedEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut teleported_events) = system_state.get_mut(ecs);
                 let Ok((mut local_player, mut physics, mut position, mut last_sent_position)) =
                         query.get_mut(player_entity) else {
                             continue;
                         };
 
-                let delta_movement = physics.delta;
+                // TODO: dismount the vehicle if p.dismount_vehicle is true, once we have
+                // vehicles
 
-                let is_x_relative = p.relative_arguments.x;
-                let is_y_relative = p.relative_arguments.y;
-                let is_z_relative = p.relative_arguments.z;
+                let delta_movement = physics.delta;
 
-                let (delta_x, new_pos_x) = if is_x_relative {
-                    last_sent_position.x += p.x;
+                // for relative coordinates, our velocity on that axis is kept, but for
+                // absolute ones it's reset
+                let (delta_x, new_pos_x) = if p.relative_arguments.x {
                     (delta_movement.x, position.x + p.x)
                 } else {
-                    last_sent_position.x = p.x;
                     (0.0, p.x)
                 };
-                let (delta_y, new_pos_y) = if is_y_relative {
-                    last_sent_position.y += p.y;
+                let (delta_y, new_pos_y) = if p.relative_arguments.y {
                     (delta_movement.y, position.y + p.y)
                 } else {
-                    last_sent_position.y = p.y;
                     (0.0, p.y)
                 };
-                let (delta_z, new_pos_z) = if is_z_relative {
-                    last_sent_position.z += p.z;
+                let (delta_z, new_pos_z) = if p.relative_arguments.z {
                     (delta_movement.z, position.z + p.z)
                 } else {
-                    last_sent_position.z = p.z;
                     (0.0, p.z)
                 };
 
@@ -380,14 +500,13 @@ fn process_packet_events(ecs: &mut World) {
                 // function makes sure the rotations stay in their
                 // ranges
                 set_rotation(&mut physics, y_rot, x_rot);
-                // TODO: minecraft sets "xo", "yo", and "zo" here but idk what that means
-                // so investigate that ig
                 let new_pos = Vec3 {
                     x: new_pos_x,
                     y: new_pos_y,
                     z: new_pos_z,
                 };
 
+                let old_pos = **position;
                 **position = new_pos;
 
                 local_player.write_packet(ServerboundAcceptTeleportationPacket { id: p.id }.get());
@@ -396,13 +515,26 @@ fn process_packet_events(ecs: &mut World) {
                         x: new_pos.x,
                         y: new_pos.y,
                         z: new_pos.z,
-                        y_rot,
-                        x_rot,
+                        y_rot: physics.y_rot,
+                        x_rot: physics.x_rot,
                         // this is always false
                         on_ground: false,
                     }
                     .get(),
                 );
+
+                // we just sent our position and rotation, so the movement system doesn't
+                // have to send them again
+                **last_sent_position = new_pos;
+                physics.y_rot_last = physics.y_rot;
+                physics.x_rot_last = physics.x_rot;
+
+                teleported_events.send(TeleportedEvent {
+                    entity: player_entity,
+                    old_position: old_pos,
+                    new_position: new_pos,
+                });
+                system_state.apply(ecs);
             }
             ClientboundGamePacket::SetChunkCacheCenter(p) => {
                 debug!("Got chunk cache center packet {:?}", p);
@@ -459,10 +591,29 @@ fn process_packet_events(ecs: &mut World) {
                     &mut world.chunks,
                 ) {
                     error!("Couldn't set chunk data: {}", e);
+                    continue;
+                }
+
+                if let Some(chunk) = world.chunks.get(&pos) {
+                    let mut chunk = chunk.write();
+                    update_chunk_light(&mut chunk, &p.light_data);
+                    set_chunk_block_entities(&mut chunk, &p.chunk_data.block_entities);
                 }
             }
-            ClientboundGamePacket::LightUpdate(_p) => {
-                // debug!("Got light update packet {:?}", p);
+            ClientboundGamePacket::LightUpdate(p) => {
+                trace!("Got light update packet for {} {}", p.x, p.z);
+                let pos = ChunkPos::new(p.x, p.z);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                // light updates can be sent before the chunk, but then the
+                // chunk packet will include the light anyways
+                if let Some(chunk) = world.chunks.get(&pos) {
+                    update_chunk_light(&mut chunk.write(), &p.light_data);
+                }
             }
             ClientboundGamePacket::AddEntity(p) => {
                 debug!("Got add entity packet {:?}", p);
@@ -519,11 +670,84 @@ fn process_packet_events(ecs: &mut World) {
 
                 system_state.apply(ecs);
             }
-            ClientboundGamePacket::UpdateAttributes(_p) => {
+            ClientboundGamePacket::UpdateAttributes(p) => {
                 // debug!("Got update attributes packet {:?}", p);
+
+                let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
+                    SystemState::new(ecs);
+                let (mut commands, query) = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                let entity = world.entity_by_id(&MinecraftEntityId(p.entity_id));
+                drop(world);
+
+                if let Some(entity) = entity {
+                    let snapshots = p.attributes.clone();
+                    commands.entity(entity).add(RelativeEntityUpdate {
+                        partial_world: local_player.partial_instance.clone(),
+                        update: Box::new(move |entity_mut| {
+                            let mut attributes = entity_mut.get_mut::<Attributes>().unwrap();
+                            for snapshot in snapshots {
+                                let Ok(attribute) = azalea_registry::Attribute::from_str(
+                                    &snapshot.attribute.to_string(),
+                                ) else {
+                                    warn!("Unknown attribute {}", snapshot.attribute);
+                                    continue;
+                                };
+                                let instance = attributes.get_mut(attribute);
+                                instance.base = snapshot.base;
+                                instance.clear_modifiers();
+                                for modifier in snapshot.modifiers {
+                                    // we just cleared the modifiers so this can only fail if the
+                                    // server sent the same modifier twice
+                                    let _ = instance.insert(modifier);
+                                }
+                            }
+                        }),
+                    });
+                } else {
+                    warn!(
+                        "Got update attributes packet for unknown entity id {}",
+                        p.entity_id
+                    );
+                }
+
+                system_state.apply(ecs);
             }
-            ClientboundGamePacket::SetEntityMotion(_p) => {
+            ClientboundGamePacket::SetEntityMotion(p) => {
                 // debug!("Got entity velocity packet {:?}", p);
+
+                let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
+                    SystemState::new(ecs);
+                let (mut commands, query) = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                let entity = world.entity_by_id(&MinecraftEntityId(p.id));
+                drop(world);
+
+                if let Some(entity) = entity {
+                    let delta = Vec3 {
+                        x: p.xa as f64 / 8000.,
+                        y: p.ya as f64 / 8000.,
+                        z: p.za as f64 / 8000.,
+                    };
+                    commands.entity(entity).add(RelativeEntityUpdate {
+                        partial_world: local_player.partial_instance.clone(),
+                        update: Box::new(move |entity_mut| {
+                            let mut physics = entity_mut.get_mut::<Physics>().unwrap();
+                            physics.delta = delta;
+                        }),
+                    });
+                } else {
+                    warn!(
+                        "Got set entity motion packet for unknown entity id {}",
+                        p.id
+                    );
+                }
+
+                system_state.apply(ecs);
             }
             ClientboundGamePacket::SetEntityLink(p) => {
                 debug!("Got set entity link packet {:?}", p);
@@ -558,6 +782,27 @@ fn process_packet_events(ecs: &mut World) {
             }
             ClientboundGamePacket::InitializeBorder(p) => {
                 debug!("Got initialize border packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get(ecs);
+                let local_player = query.get(player_entity).unwrap();
+                let mut world = local_player.world.write();
+
+                let world_border = &mut world.world_border;
+                world_border.center_x = p.new_center_x;
+                world_border.center_z = p.new_center_z;
+                if p.lerp_time > 0 {
+                    world_border.lerp_size_between(
+                        p.old_size,
+                        p.new_size,
+                        Duration::from_millis(p.lerp_time),
+                    );
+                } else {
+                    world_border.set_size(p.new_size);
+                }
+                world_border.absolute_max_size = p.new_absolute_max_size;
+                world_border.warning_blocks = p.warning_blocks;
+                world_border.warning_time = p.warning_time;
             }
             ClientboundGamePacket::SetTime(_p) => {
                 // debug!("Got set time packet {:?}", p);
@@ -567,6 +812,29 @@ fn process_packet_events(ecs: &mut World) {
             }
             ClientboundGamePacket::ContainerSetContent(p) => {
                 debug!("Got container set content packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut Inventory>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut inventory = query.get_mut(player_entity).unwrap();
+
+                // container id 0 is always the player's inventory
+                if p.container_id == 0 {
+                    inventory.inventory_menu.slots = p.items.clone();
+                    inventory.copy_inventory_to_container();
+                } else if p.container_id == inventory.id {
+                    if let Some(container_menu) = &mut inventory.container_menu {
+                        container_menu.slots = p.items.clone();
+                    }
+                    inventory.copy_container_to_inventory();
+                } else {
+                    warn!(
+                        "Got container set content packet for container {} but the open container is {}",
+                        p.container_id, inventory.id
+                    );
+                    continue;
+                }
+                inventory.state_id = p.state_id;
+                inventory.carried = p.carried_item.clone();
             }
             ClientboundGamePacket::SetHealth(p) => {
                 debug!("Got set health packet {:?}", p);
@@ -604,11 +872,17 @@ fn process_packet_events(ecs: &mut World) {
 
                 if let Some(entity) = entity {
                     let new_position = p.position;
+                    let y_rot = rotation_from_byte(p.y_rot);
+                    let x_rot = rotation_from_byte(p.x_rot);
+                    let on_ground = p.on_ground;
                     commands.entity(entity).add(RelativeEntityUpdate {
                         partial_world: local_player.partial_instance.clone(),
                         update: Box::new(move |entity| {
                             let mut position = entity.get_mut::<Position>().unwrap();
                             **position = new_position;
+                            let mut physics = entity.get_mut::<Physics>().unwrap();
+                            set_rotation(&mut physics, y_rot, x_rot);
+                            physics.on_ground = on_ground;
                         }),
                     });
                 } else {
@@ -620,8 +894,35 @@ fn process_packet_events(ecs: &mut World) {
             ClientboundGamePacket::UpdateAdvancements(p) => {
                 debug!("Got update advancements packet {:?}", p);
             }
-            ClientboundGamePacket::RotateHead(_p) => {
+            ClientboundGamePacket::RotateHead(p) => {
                 // debug!("Got rotate head packet {:?}", p);
+
+                let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
+                    SystemState::new(ecs);
+                let (mut commands, query) = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                let entity = world.entity_by_id(&MinecraftEntityId(p.entity_id));
+                drop(world);
+
+                if let Some(entity) = entity {
+                    let y_head_rot = rotation_from_byte(p.y_head_rot);
+                    commands.entity(entity).add(RelativeEntityUpdate {
+                        partial_world: local_player.partial_instance.clone(),
+                        update: Box::new(move |entity_mut| {
+                            let mut head_rotation = entity_mut.get_mut::<HeadRotation>().unwrap();
+                            **head_rotation = y_head_rot;
+                        }),
+                    });
+                } else {
+                    warn!(
+                        "Got rotate head packet for unknown entity id {}",
+                        p.entity_id
+                    );
+                }
+
+                system_state.apply(ecs);
             }
             ClientboundGamePacket::MoveEntityPos(p) => {
                 let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
@@ -635,11 +936,13 @@ fn process_packet_events(ecs: &mut World) {
 
                 if let Some(entity) = entity {
                     let delta = p.delta.clone();
+                    let on_ground = p.on_ground;
                     commands.entity(entity).add(RelativeEntityUpdate {
                         partial_world: local_player.partial_instance.clone(),
                         update: Box::new(move |entity_mut| {
                             let mut position = entity_mut.get_mut::<Position>().unwrap();
                             **position = position.with_delta(&delta);
+                            entity_mut.get_mut::<Physics>().unwrap().on_ground = on_ground;
                         }),
                     });
                 } else {
@@ -648,6 +951,8 @@ fn process_packet_events(ecs: &mut World) {
                         p.entity_id
                     );
                 }
+
+                system_state.apply(ecs);
             }
             ClientboundGamePacket::PlayerInfo(p) => {
                 debug!("Got player info packet {p:?}");
@@ -764,11 +1069,17 @@ fn process_packet_events(ecs: &mut World) {
 
                 if let Some(entity) = entity {
                     let delta = p.delta.clone();
+                    let y_rot = rotation_from_byte(p.y_rot);
+                    let x_rot = rotation_from_byte(p.x_rot);
+                    let on_ground = p.on_ground;
                     commands.entity(entity).add(RelativeEntityUpdate {
                         partial_world: local_player.partial_instance.clone(),
                         update: Box::new(move |entity_mut| {
                             let mut position = entity_mut.get_mut::<Position>().unwrap();
                             **position = position.with_delta(&delta);
+                            let mut physics = entity_mut.get_mut::<Physics>().unwrap();
+                            set_rotation(&mut physics, y_rot, x_rot);
+                            physics.on_ground = on_ground;
                         }),
                     });
                 } else {
@@ -781,8 +1092,38 @@ fn process_packet_events(ecs: &mut World) {
                 system_state.apply(ecs);
             }
 
-            ClientboundGamePacket::MoveEntityRot(_p) => {
+            ClientboundGamePacket::MoveEntityRot(p) => {
                 // debug!("Got move entity rot packet {:?}", p);
+
+                let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
+                    SystemState::new(ecs);
+                let (mut commands, query) = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                let entity = world.entity_by_id(&MinecraftEntityId(p.entity_id));
+                drop(world);
+
+                if let Some(entity) = entity {
+                    let y_rot = rotation_from_byte(p.y_rot);
+                    let x_rot = rotation_from_byte(p.x_rot);
+                    let on_ground = p.on_ground;
+                    commands.entity(entity).add(RelativeEntityUpdate {
+                        partial_world: local_player.partial_instance.clone(),
+                        update: Box::new(move |entity_mut| {
+                            let mut physics = entity_mut.get_mut::<Physics>().unwrap();
+                            set_rotation(&mut physics, y_rot, x_rot);
+                            physics.on_ground = on_ground;
+                        }),
+                    });
+                } else {
+                    warn!(
+                        "Got move entity rot packet for unknown entity id {}",
+                        p.entity_id
+                    );
+                }
+
+                system_state.apply(ecs);
             }
             ClientboundGamePacket::KeepAlive(p) => {
                 debug!("Got keep alive packet {p:?} for {player_entity:?}");
@@ -838,9 +1179,17 @@ fn process_packet_events(ecs: &mut World) {
             ClientboundGamePacket::BlockUpdate(p) => {
                 debug!("Got block update packet {:?}", p);
 
-                let mut system_state: SystemState<Query<&mut LocalPlayer>> = SystemState::new(ecs);
+                let mut system_state: SystemState<
+                    Query<(&mut LocalPlayer, &mut BlockStatePredictions)>,
+                > = SystemState::new(ecs);
                 let mut query = system_state.get_mut(ecs);
-                let local_player = query.get_mut(player_entity).unwrap();
+                let (local_player, mut predictions) = query.get_mut(player_entity).unwrap();
+
+                // if we're predicting this block, wait until the server acknowledges our
+                // change before applying it
+                if predictions.update_known_server_state(&p.pos, p.block_state) {
+                    continue;
+                }
 
                 let world = local_player.world.write();
 
@@ -851,16 +1200,20 @@ fn process_packet_events(ecs: &mut World) {
             }
             ClientboundGamePacket::SectionBlocksUpdate(p) => {
                 debug!("Got section blocks update packet {:?}", p);
-                let mut system_state: SystemState<Query<&mut LocalPlayer>> = SystemState::new(ecs);
+                let mut system_state: SystemState<
+                    Query<(&mut LocalPlayer, &mut BlockStatePredictions)>,
+                > = SystemState::new(ecs);
                 let mut query = system_state.get_mut(ecs);
-                let local_player = query.get_mut(player_entity).unwrap();
+                let (local_player, mut predictions) = query.get_mut(player_entity).unwrap();
 
                 let world = local_player.world.write();
 
                 for state in &p.states {
-                    world
-                        .chunks
-                        .set_block_state(&(p.section_pos + state.pos.clone()), state.state);
+                    let pos = p.section_pos + state.pos.clone();
+                    if predictions.update_known_server_state(&pos, state.state) {
+                        continue;
+                    }
+                    world.chunks.set_block_state(&pos, state.state);
                 }
             }
             ClientboundGamePacket::GameEvent(p) => {
@@ -877,30 +1230,264 @@ fn process_packet_events(ecs: &mut World) {
             }
             ClientboundGamePacket::UpdateMobEffect(p) => {
                 debug!("Got update mob effect packet {:?}", p);
+
+                let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
+                    SystemState::new(ecs);
+                let (mut commands, query) = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                let entity = world.entity_by_id(&MinecraftEntityId(p.entity_id));
+                drop(world);
+
+                if let Some(entity) = entity {
+                    let effect = p.effect;
+                    let instance = MobEffectInstance::from_flags(
+                        p.effect_amplifier,
+                        p.effect_duration_ticks,
+                        p.flags,
+                    );
+                    commands.entity(entity).add(RelativeEntityUpdate {
+                        partial_world: local_player.partial_instance.clone(),
+                        update: Box::new(move |entity_mut| {
+                            let mut active_effects = entity_mut.get_mut::<ActiveEffects>().unwrap();
+                            active_effects.insert(effect, instance);
+                            let mut attributes = entity_mut.get_mut::<Attributes>().unwrap();
+                            update_effect_attribute_modifiers(
+                                &mut attributes,
+                                effect,
+                                Some(instance.amplifier),
+                            );
+                        }),
+                    });
+                } else {
+                    warn!(
+                        "Got update mob effect packet for unknown entity id {}",
+                        p.entity_id
+                    );
+                }
+
+                system_state.apply(ecs);
             }
             ClientboundGamePacket::AddExperienceOrb(_) => {}
             ClientboundGamePacket::AwardStats(_) => {}
-            ClientboundGamePacket::BlockChangedAck(_) => {}
+            ClientboundGamePacket::BlockChangedAck(p) => {
+                debug!("Got block changed ack packet {:?}", p);
+
+                let mut system_state: SystemState<
+                    Query<(&LocalPlayer, &mut BlockStatePredictions)>,
+                > = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let (local_player, mut predictions) = query.get_mut(player_entity).unwrap();
+
+                let world = local_player.world.write();
+                for (pos, state) in predictions.end_predictions_up_to(p.sequence as u32) {
+                    world.chunks.set_block_state(&pos, state);
+                }
+            }
             ClientboundGamePacket::BlockDestruction(_) => {}
-            ClientboundGamePacket::BlockEntityData(_) => {}
+            ClientboundGamePacket::BlockEntityData(p) => {
+                debug!("Got block entity data packet {:?}", p);
+
+                // an empty tag means there's no data to update
+                if p.tag == azalea_nbt::Tag::End {
+                    continue;
+                }
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                world.chunks.set_block_entity(
+                    &p.pos,
+                    BlockEntity {
+                        kind: p.block_entity_type,
+                        data: p.tag.clone(),
+                    },
+                );
+            }
             ClientboundGamePacket::BlockEvent(p) => {
                 debug!("Got block event packet {:?}", p);
             }
-            ClientboundGamePacket::BossEvent(_) => {}
+            ClientboundGamePacket::BossEvent(p) => {
+                debug!("Got boss event packet {:?}", p);
+
+                #[allow(clippy::type_complexity)]
+                let mut system_state: SystemState<(
+                    Query<&mut BossBars>,
+                    EventWriter<AddBossBarEvent>,
+                    EventWriter<UpdateBossBarEvent>,
+                    EventWriter<RemoveBossBarEvent>,
+                )> = SystemState::new(ecs);
+                let (
+                    mut query,
+                    mut add_boss_bar_events,
+                    mut update_boss_bar_events,
+                    mut remove_boss_bar_events,
+                ) = system_state.get_mut(ecs);
+                let mut boss_bars = query.get_mut(player_entity).unwrap();
+
+                let updated_bar = match &p.operation {
+                    BossBarOperation::Add(add) => {
+                        let bar = BossBar::new(p.id, add.clone());
+                        boss_bars.insert(p.id, bar.clone());
+                        add_boss_bar_events.send(AddBossBarEvent {
+                            entity: player_entity,
+                            bar,
+                        });
+                        None
+                    }
+                    BossBarOperation::Remove => {
+                        if let Some(bar) = boss_bars.remove(&p.id) {
+                            remove_boss_bar_events.send(RemoveBossBarEvent {
+                                entity: player_entity,
+                                bar,
+                            });
+                        }
+                        None
+                    }
+                    BossBarOperation::UpdateProgress(progress) => {
+                        update_boss_bar(&mut boss_bars, p.id, |bar| bar.progress = *progress)
+                    }
+                    BossBarOperation::UpdateName(name) => {
+                        update_boss_bar(&mut boss_bars, p.id, |bar| bar.name = name.clone())
+                    }
+                    BossBarOperation::UpdateStyle(style) => {
+                        update_boss_bar(&mut boss_bars, p.id, |bar| {
+                            bar.color = style.color;
+                            bar.overlay = style.overlay;
+                        })
+                    }
+                    BossBarOperation::UpdateProperties(properties) => {
+                        update_boss_bar(&mut boss_bars, p.id, |bar| {
+                            bar.properties = properties.clone();
+                        })
+                    }
+                };
+                if let Some(bar) = updated_bar {
+                    update_boss_bar_events.send(UpdateBossBarEvent {
+                        entity: player_entity,
+                        bar,
+                    });
+                }
+            }
+            ClientboundGamePacket::ClearTitles(p) => {
+                debug!("Got clear titles packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut Titles>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut titles = query.get_mut(player_entity).unwrap();
+
+                titles.clear(p.reset_times);
+            }
             ClientboundGamePacket::CommandSuggestions(_) => {}
-            ClientboundGamePacket::ContainerSetData(_) => {}
-            ClientboundGamePacket::ContainerSetSlot(_) => {}
+            ClientboundGamePacket::ContainerSetData(p) => {
+                debug!("Got container set data packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut Inventory>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut inventory = query.get_mut(player_entity).unwrap();
+
+                if p.container_id == inventory.id {
+                    inventory.menu_mut().data.insert(p.id, p.value);
+                }
+            }
+            ClientboundGamePacket::ContainerSetSlot(p) => {
+                debug!("Got container set slot packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut Inventory>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut inventory = query.get_mut(player_entity).unwrap();
+
+                // -1 and -2 are sent as unsigned bytes
+                match p.container_id {
+                    // the item on the cursor
+                    255 => inventory.carried = p.item_stack.clone(),
+                    // an index into the player's inventory rather than a menu
+                    254 => inventory.set_inventory_item(p.slot as usize, p.item_stack.clone()),
+                    container_id => {
+                        let slot = p.slot as usize;
+                        if container_id == 0 {
+                            if let Some(item) = inventory.inventory_menu.slots.get_mut(slot) {
+                                *item = p.item_stack.clone();
+                            }
+                            inventory.copy_inventory_to_container();
+                        } else if container_id == inventory.id {
+                            if let Some(item) = inventory.menu_mut().slots.get_mut(slot) {
+                                *item = p.item_stack.clone();
+                            }
+                            inventory.copy_container_to_inventory();
+                        } else {
+                            continue;
+                        }
+                        inventory.state_id = p.state_id;
+                    }
+                }
+            }
             ClientboundGamePacket::Cooldown(_) => {}
             ClientboundGamePacket::CustomChatCompletions(_) => {}
             ClientboundGamePacket::DeleteChat(_) => {}
             ClientboundGamePacket::Explode(_) => {}
-            ClientboundGamePacket::ForgetLevelChunk(_) => {}
+            ClientboundGamePacket::ForgetLevelChunk(p) => {
+                debug!("Got forget level chunk packet {:?}", p);
+                let pos = ChunkPos::new(p.x, p.z);
+
+                #[allow(clippy::type_complexity)]
+                let mut system_state: SystemState<(
+                    Query<&LocalPlayer>,
+                    Query<(&MinecraftEntityId, &mut LoadedBy), Without<Local>>,
+                    EventWriter<ChunkUnloadedEvent>,
+                )> = SystemState::new(ecs);
+                let (query, mut entity_query, mut chunk_unloaded_events) =
+                    system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let mut world = local_player.world.write();
+                let mut partial_world = local_player.partial_instance.write();
+
+                partial_world.chunks.remove(&pos, &mut world.chunks);
+
+                // we don't have the entities in the chunk loaded anymore, so
+                // they'll get despawned if no other local player has them
+                if let Some(entities) = world.entities_by_chunk.get(&pos) {
+                    for &entity in entities {
+                        let Ok((id, mut loaded_by)) = entity_query.get_mut(entity) else {
+                            continue;
+                        };
+                        loaded_by.remove(&player_entity);
+                        partial_world.entity_infos.updates_received.remove(id);
+                    }
+                }
+
+                chunk_unloaded_events.send(ChunkUnloadedEvent {
+                    entity: player_entity,
+                    pos,
+                });
+            }
             ClientboundGamePacket::HorseScreenOpen(_) => {}
             ClientboundGamePacket::MapItemData(_) => {}
             ClientboundGamePacket::MerchantOffers(_) => {}
             ClientboundGamePacket::MoveVehicle(_) => {}
             ClientboundGamePacket::OpenBook(_) => {}
-            ClientboundGamePacket::OpenScreen(_) => {}
+            ClientboundGamePacket::OpenScreen(p) => {
+                debug!("Got open screen packet {:?}", p);
+
+                let mut system_state: SystemState<(Commands, Query<&mut Inventory>)> =
+                    SystemState::new(ecs);
+                let (mut commands, mut query) = system_state.get_mut(ecs);
+                let mut inventory = query.get_mut(player_entity).unwrap();
+
+                inventory.open_container(
+                    p.container_id as u8,
+                    Menu::new(p.menu_type, p.title.clone()),
+                );
+                commands
+                    .entity(player_entity)
+                    .remove::<WaitingForContainerOpen>();
+
+                system_state.apply(ecs);
+            }
             ClientboundGamePacket::OpenSignEditor(_) => {}
             ClientboundGamePacket::Ping(_) => {}
             ClientboundGamePacket::PlaceGhostRecipe(_) => {}
@@ -929,7 +1516,38 @@ fn process_packet_events(ecs: &mut World) {
                 system_state.apply(ecs);
             }
             ClientboundGamePacket::PlayerLookAt(_) => {}
-            ClientboundGamePacket::RemoveMobEffect(_) => {}
+            ClientboundGamePacket::RemoveMobEffect(p) => {
+                debug!("Got remove mob effect packet {:?}", p);
+
+                let mut system_state: SystemState<(Commands, Query<&LocalPlayer>)> =
+                    SystemState::new(ecs);
+                let (mut commands, query) = system_state.get_mut(ecs);
+                let local_player = query.get(player_entity).unwrap();
+
+                let world = local_player.world.read();
+                let entity = world.entity_by_id(&MinecraftEntityId(p.entity_id));
+                drop(world);
+
+                if let Some(entity) = entity {
+                    let effect = p.effect;
+                    commands.entity(entity).add(RelativeEntityUpdate {
+                        partial_world: local_player.partial_instance.clone(),
+                        update: Box::new(move |entity_mut| {
+                            let mut active_effects = entity_mut.get_mut::<ActiveEffects>().unwrap();
+                            active_effects.remove(&effect);
+                            let mut attributes = entity_mut.get_mut::<Attributes>().unwrap();
+                            update_effect_attribute_modifiers(&mut attributes, effect, None);
+                        }),
+                    });
+                } else {
+                    warn!(
+                        "Got remove mob effect packet for unknown entity id {}",
+                        p.entity_id
+                    );
+                }
+
+                system_state.apply(ecs);
+            }
             ClientboundGamePacket::ResourcePack(_) => {}
             ClientboundGamePacket::Respawn(p) => {
                 debug!("Got respawn packet {:?}", p);
@@ -943,28 +1561,258 @@ fn process_packet_events(ecs: &mut World) {
                 system_state.apply(ecs);
             }
             ClientboundGamePacket::SelectAdvancementsTab(_) => {}
-            ClientboundGamePacket::SetActionBarText(_) => {}
-            ClientboundGamePacket::SetBorderCenter(_) => {}
-            ClientboundGamePacket::SetBorderLerpSize(_) => {}
-            ClientboundGamePacket::SetBorderSize(_) => {}
-            ClientboundGamePacket::SetBorderWarningDelay(_) => {}
-            ClientboundGamePacket::SetBorderWarningDistance(_) => {}
+            ClientboundGamePacket::SetActionBarText(p) => {
+                debug!("Got set action bar text packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut ActionBar>,
+                    EventWriter<SetActionBarEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut set_action_bar_events) = system_state.get_mut(ecs);
+                let mut action_bar = query.get_mut(player_entity).unwrap();
+
+                **action_bar = Some(p.text.clone());
+                set_action_bar_events.send(SetActionBarEvent {
+                    entity: player_entity,
+                    text: p.text.clone(),
+                });
+            }
+            ClientboundGamePacket::SetBorderCenter(p) => {
+                debug!("Got set border center packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get(ecs);
+                let local_player = query.get(player_entity).unwrap();
+                let mut world = local_player.world.write();
+
+                world.world_border.center_x = p.new_center_x;
+                world.world_border.center_z = p.new_center_z;
+            }
+            ClientboundGamePacket::SetBorderLerpSize(p) => {
+                debug!("Got set border lerp size packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get(ecs);
+                let local_player = query.get(player_entity).unwrap();
+                let mut world = local_player.world.write();
+
+                world.world_border.lerp_size_between(
+                    p.old_size,
+                    p.new_size,
+                    Duration::from_millis(p.lerp_time),
+                );
+            }
+            ClientboundGamePacket::SetBorderSize(p) => {
+                debug!("Got set border size packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get(ecs);
+                let local_player = query.get(player_entity).unwrap();
+                let mut world = local_player.world.write();
+
+                world.world_border.set_size(p.size);
+            }
+            ClientboundGamePacket::SetBorderWarningDelay(p) => {
+                debug!("Got set border warning delay packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get(ecs);
+                let local_player = query.get(player_entity).unwrap();
+                let mut world = local_player.world.write();
+
+                world.world_border.warning_time = p.warning_delay;
+            }
+            ClientboundGamePacket::SetBorderWarningDistance(p) => {
+                debug!("Got set border warning distance packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&LocalPlayer>> = SystemState::new(ecs);
+                let query = system_state.get(ecs);
+                let local_player = query.get(player_entity).unwrap();
+                let mut world = local_player.world.write();
+
+                world.world_border.warning_blocks = p.warning_blocks;
+            }
             ClientboundGamePacket::SetCamera(_) => {}
-            ClientboundGamePacket::SetDisplayObjective(_) => {}
-            ClientboundGamePacket::SetObjective(_) => {}
+            ClientboundGamePacket::SetDisplayObjective(p) => {
+                debug!("Got set display objective packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut Scoreboard>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut scoreboard = query.get_mut(player_entity).unwrap();
+
+                let Some(slot) = DisplaySlot::from_id(p.slot) else {
+                    warn!("Unknown display slot {}", p.slot);
+                    continue;
+                };
+                if p.objective_name.is_empty() {
+                    scoreboard.display_slots.remove(&slot);
+                } else {
+                    scoreboard
+                        .display_slots
+                        .insert(slot, p.objective_name.clone());
+                }
+            }
+            ClientboundGamePacket::SetObjective(p) => {
+                debug!("Got set objective packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut Scoreboard>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut scoreboard = query.get_mut(player_entity).unwrap();
+
+                match &p.method {
+                    ObjectiveMethod::Add(info) | ObjectiveMethod::Change(info) => {
+                        scoreboard.objectives.insert(
+                            p.objective_name.clone(),
+                            Objective {
+                                name: p.objective_name.clone(),
+                                display_name: info.display_name.clone(),
+                                render_type: info.render_type,
+                            },
+                        );
+                    }
+                    ObjectiveMethod::Remove => {
+                        scoreboard.remove_objective(&p.objective_name);
+                    }
+                }
+            }
             ClientboundGamePacket::SetPassengers(_) => {}
-            ClientboundGamePacket::SetPlayerTeam(_) => {}
-            ClientboundGamePacket::SetScore(_) => {}
+            ClientboundGamePacket::SetPlayerTeam(p) => {
+                debug!("Got set player team packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut Scoreboard>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut scoreboard = query.get_mut(player_entity).unwrap();
+
+                match &p.method {
+                    TeamMethod::Add((parameters, players)) => {
+                        scoreboard.teams.insert(
+                            p.name.clone(),
+                            Team::new(p.name.clone(), parameters.clone()),
+                        );
+                        scoreboard.add_players_to_team(&p.name, players.clone());
+                    }
+                    TeamMethod::Remove => {
+                        scoreboard.teams.remove(&p.name);
+                    }
+                    TeamMethod::Change(parameters) => {
+                        if let Some(team) = scoreboard.teams.get_mut(&p.name) {
+                            team.update(parameters.clone());
+                        } else {
+                            warn!("Got team update for unknown team {}", p.name);
+                        }
+                    }
+                    TeamMethod::Join(players) => {
+                        scoreboard.add_players_to_team(&p.name, players.clone());
+                    }
+                    TeamMethod::Leave(players) => {
+                        if let Some(team) = scoreboard.teams.get_mut(&p.name) {
+                            for player in players {
+                                team.members.remove(player);
+                            }
+                        }
+                    }
+                }
+            }
+            ClientboundGamePacket::SetScore(p) => {
+                debug!("Got set score packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Scoreboard>,
+                    EventWriter<UpdateScoreEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut update_score_events) = system_state.get_mut(ecs);
+                let mut scoreboard = query.get_mut(player_entity).unwrap();
+
+                let update = ScoreUpdate {
+                    owner: p.owner.clone(),
+                    objective_name: p.objective_name.clone(),
+                    score: match p.method {
+                        ScoreMethod::Change { score } => Some(score),
+                        ScoreMethod::Remove => None,
+                    },
+                };
+                if update.objective_name.is_none() && update.score.is_some() {
+                    warn!("Got a score change without an objective: {:?}", p);
+                    continue;
+                }
+                scoreboard.update_score(&update);
+                update_score_events.send(UpdateScoreEvent {
+                    entity: player_entity,
+                    update,
+                });
+            }
             ClientboundGamePacket::SetSimulationDistance(_) => {}
-            ClientboundGamePacket::SetSubtitleText(_) => {}
-            ClientboundGamePacket::SetTitleText(_) => {}
-            ClientboundGamePacket::SetTitlesAnimation(_) => {}
+            ClientboundGamePacket::SetSubtitleText(p) => {
+                debug!("Got set subtitle text packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Titles>,
+                    EventWriter<SetSubtitleEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut set_subtitle_events) = system_state.get_mut(ecs);
+                let mut titles = query.get_mut(player_entity).unwrap();
+
+                titles.subtitle = Some(p.text.clone());
+                set_subtitle_events.send(SetSubtitleEvent {
+                    entity: player_entity,
+                    text: p.text.clone(),
+                });
+            }
+            ClientboundGamePacket::SetTitleText(p) => {
+                debug!("Got set title text packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut Titles>,
+                    EventWriter<SetTitleEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut set_title_events) = system_state.get_mut(ecs);
+                let mut titles = query.get_mut(player_entity).unwrap();
+
+                titles.set_title(p.text.clone());
+                set_title_events.send(SetTitleEvent {
+                    entity: player_entity,
+                    text: p.text.clone(),
+                });
+            }
+            ClientboundGamePacket::SetTitlesAnimation(p) => {
+                debug!("Got set titles animation packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut Titles>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut titles = query.get_mut(player_entity).unwrap();
+
+                titles.set_times(p.fade_in, p.stay, p.fade_out);
+            }
             ClientboundGamePacket::SoundEntity(_) => {}
             ClientboundGamePacket::StopSound(_) => {}
-            ClientboundGamePacket::TabList(_) => {}
+            ClientboundGamePacket::TabList(p) => {
+                debug!("Got tab list packet {:?}", p);
+
+                let mut system_state: SystemState<(
+                    Query<&mut TabListHeaderFooter>,
+                    EventWriter<TabListHeaderFooterEvent>,
+                )> = SystemState::new(ecs);
+                let (mut query, mut tab_list_header_footer_events) = system_state.get_mut(ecs);
+                let mut header_footer = query.get_mut(player_entity).unwrap();
+
+                header_footer.header = p.header.clone();
+                header_footer.footer = p.footer.clone();
+                tab_list_header_footer_events.send(TabListHeaderFooterEvent {
+                    entity: player_entity,
+                    header_footer: header_footer.clone(),
+                });
+            }
             ClientboundGamePacket::TagQuery(_) => {}
             ClientboundGamePacket::TakeItemEntity(_) => {}
-            ClientboundGamePacket::ContainerClose(_) => {}
+            ClientboundGamePacket::ContainerClose(p) => {
+                debug!("Got container close packet {:?}", p);
+
+                let mut system_state: SystemState<Query<&mut Inventory>> = SystemState::new(ecs);
+                let mut query = system_state.get_mut(ecs);
+                let mut inventory = query.get_mut(player_entity).unwrap();
+
+                inventory.close_container();
+            }
             ClientboundGamePacket::ChatPreview(_) => {}
             ClientboundGamePacket::CustomSound(_) => {}
             ClientboundGamePacket::PlayerChatHeader(_) => {}
@@ -1011,3 +1859,59 @@ impl PacketReceiver {
         // receiver is automatically closed when it's dropped
     }
 }
+
+/// Change the boss bar with the given id and return a copy of it, or warn and
+/// return `None` if we don't know about it.
+fn update_boss_bar(
+    boss_bars: &mut BossBars,
+    id: Uuid,
+    update: impl FnOnce(&mut BossBar),
+) -> Option<BossBar> {
+    let Some(bar) = boss_bars.get_mut(&id) else {
+        warn!("Got boss event packet for unknown boss bar {id}");
+        return None;
+    };
+    update(bar);
+    Some(bar.clone())
+}
+
+/// Apply the sky light and block light from a light update to a chunk.
+fn update_chunk_light(chunk: &mut Chunk, light_data: &ClientboundLightUpdatePacketData) {
+    chunk.update_light(
+        LightLayer::Sky,
+        &light_data.sky_y_mask,
+        &light_data.empty_sky_y_mask,
+        &light_data.sky_updates,
+    );
+    chunk.update_light(
+        LightLayer::Block,
+        &light_data.block_y_mask,
+        &light_data.empty_block_y_mask,
+        &light_data.block_updates,
+    );
+}
+
+/// Replace the block entities in a chunk with the ones that were sent in a
+/// chunk packet.
+fn set_chunk_block_entities(chunk: &mut Chunk, block_entities: &[PacketBlockEntity]) {
+    chunk.block_entities.clear();
+    for block_entity in block_entities {
+        let Ok(kind) = azalea_registry::BlockEntityKind::try_from(block_entity.type_ as u32) else {
+            warn!("Unknown block entity type {}", block_entity.type_);
+            continue;
+        };
+        // the x and z are packed into one byte, and the y is a signed short
+        let pos = ChunkBlockPos::new(
+            block_entity.packed_xz >> 4,
+            block_entity.y as i16 as i32,
+            block_entity.packed_xz & 15,
+        );
+        chunk.block_entities.insert(
+            pos,
+            BlockEntity {
+                kind,
+                data: block_entity.data.clone(),
+            },
+        );
+    }
+}
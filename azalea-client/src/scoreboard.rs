@@ -0,0 +1,233 @@
+//! Keep track of the scoreboard objectives, scores and teams that the server
+//! sends us, so you can read things like the sidebar.
+
+use std::collections::{HashMap, HashSet};
+
+use azalea_chat::{style::ChatFormatting, FormattedText};
+use azalea_protocol::packets::game::{
+    clientbound_set_objective_packet::RenderType, clientbound_set_player_team_packet::Parameters,
+};
+use bevy_ecs::component::Component;
+
+use crate::Client;
+
+impl Client {
+    /// Get the scoreboard for this client, which contains the objectives,
+    /// scores and teams that the server sent us.
+    ///
+    /// ```rust,no_run
+    /// # use azalea_client::{Client, scoreboard::DisplaySlot};
+    /// # fn example(bot: Client) {
+    /// let scoreboard = bot.scoreboard();
+    /// if let Some(objective) = scoreboard.objective_in_slot(DisplaySlot::Sidebar) {
+    ///     for (owner, score) in scoreboard.scores_for(&objective.name) {
+    ///         println!("{owner}: {score}");
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn scoreboard(&self) -> Scoreboard {
+        let mut ecs = self.ecs.lock();
+        self.query::<&Scoreboard>(&mut ecs).clone()
+    }
+}
+
+/// A component that contains the scoreboard state the server sent to a local
+/// player.
+#[derive(Component, Clone, Debug, Default)]
+pub struct Scoreboard {
+    /// The objectives, indexed by their name.
+    pub objectives: HashMap<String, Objective>,
+    /// The name of the objective that's shown in each display slot.
+    pub display_slots: HashMap<DisplaySlot, String>,
+    /// The scores of each owner (usually a player name), indexed by the name
+    /// of the objective.
+    pub scores: HashMap<String, HashMap<String, i32>>,
+    /// The teams, indexed by their name.
+    pub teams: HashMap<String, Team>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Objective {
+    pub name: String,
+    pub display_name: FormattedText,
+    pub render_type: RenderType,
+}
+
+/// Where an objective is shown on the client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DisplaySlot {
+    /// The tab list.
+    List,
+    Sidebar,
+    /// Under the name tags of players.
+    BelowName,
+    /// The sidebar, but only for players on a team with the given color.
+    TeamSidebar(ChatFormatting),
+}
+
+impl DisplaySlot {
+    /// Get the display slot from the id that's used in the set display
+    /// objective packet.
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(DisplaySlot::List),
+            1 => Some(DisplaySlot::Sidebar),
+            2 => Some(DisplaySlot::BelowName),
+            // the ids after that are the colors in the same order as the
+            // formatting codes
+            3..=18 => Some(DisplaySlot::TeamSidebar(
+                ChatFormatting::FORMATTERS[id as usize - 3],
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Team {
+    pub name: String,
+    pub display_name: FormattedText,
+    pub prefix: FormattedText,
+    pub suffix: FormattedText,
+    pub color: ChatFormatting,
+    pub allow_friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+    /// `always`, `never`, `hideForOtherTeams` or `hideForOwnTeam`.
+    pub nametag_visibility: String,
+    /// `always`, `never`, `pushOtherTeams` or `pushOwnTeam`.
+    pub collision_rule: String,
+    /// The names of the players (or other score owners) in this team.
+    pub members: HashSet<String>,
+}
+
+impl Team {
+    pub fn new(name: String, parameters: Parameters) -> Self {
+        let mut team = Self {
+            name,
+            display_name: FormattedText::default(),
+            prefix: FormattedText::default(),
+            suffix: FormattedText::default(),
+            color: ChatFormatting::Reset,
+            allow_friendly_fire: true,
+            see_friendly_invisibles: true,
+            nametag_visibility: "always".to_string(),
+            collision_rule: "always".to_string(),
+            members: HashSet::new(),
+        };
+        team.update(parameters);
+        team
+    }
+
+    /// Set everything except the name and members of the team.
+    pub fn update(&mut self, parameters: Parameters) {
+        self.display_name = parameters.display_name;
+        self.prefix = parameters.player_prefix;
+        self.suffix = parameters.player_suffix;
+        self.color = parameters.color;
+        self.allow_friendly_fire = parameters.options & 0x01 != 0;
+        self.see_friendly_invisibles = parameters.options & 0x02 != 0;
+        self.nametag_visibility = parameters.nametag_visibility;
+        self.collision_rule = parameters.collision_rule;
+    }
+}
+
+/// A score that was changed or removed.
+#[derive(Clone, Debug)]
+pub struct ScoreUpdate {
+    /// The owner of the score, usually a player name.
+    pub owner: String,
+    /// The name of the objective. If this is `None`, then every score of the
+    /// owner was removed.
+    pub objective_name: Option<String>,
+    /// The new score, or `None` if it was removed.
+    pub score: Option<i32>,
+}
+
+impl Scoreboard {
+    /// Get the objective that's shown in the given display slot.
+    pub fn objective_in_slot(&self, slot: DisplaySlot) -> Option<&Objective> {
+        self.display_slots
+            .get(&slot)
+            .and_then(|name| self.objectives.get(name))
+    }
+
+    /// Get the score that the owner has for the given objective.
+    pub fn score(&self, owner: &str, objective_name: &str) -> Option<i32> {
+        self.scores
+            .get(owner)
+            .and_then(|scores| scores.get(objective_name))
+            .copied()
+    }
+
+    /// Get all the scores for an objective, sorted from highest to lowest like
+    /// in the sidebar.
+    pub fn scores_for(&self, objective_name: &str) -> Vec<(&str, i32)> {
+        let mut scores = self
+            .scores
+            .iter()
+            .filter_map(|(owner, scores)| {
+                scores
+                    .get(objective_name)
+                    .map(|score| (owner.as_str(), *score))
+            })
+            .collect::<Vec<_>>();
+        scores.sort_by(|(a_owner, a_score), (b_owner, b_score)| {
+            b_score.cmp(a_score).then(a_owner.cmp(b_owner))
+        });
+        scores
+    }
+
+    /// Get the team that a player (or other score owner) is in.
+    pub fn player_team(&self, name: &str) -> Option<&Team> {
+        self.teams.values().find(|team| team.members.contains(name))
+    }
+
+    /// Remove an objective, along with its scores and the display slots it
+    /// was in.
+    pub fn remove_objective(&mut self, name: &str) {
+        self.objectives.remove(name);
+        self.display_slots
+            .retain(|_, objective_name| objective_name != name);
+        for scores in self.scores.values_mut() {
+            scores.remove(name);
+        }
+        self.scores.retain(|_, scores| !scores.is_empty());
+    }
+
+    /// Apply a [`ScoreUpdate`] to the scoreboard.
+    pub fn update_score(&mut self, update: &ScoreUpdate) {
+        match (&update.objective_name, update.score) {
+            (Some(objective_name), Some(score)) => {
+                self.scores
+                    .entry(update.owner.clone())
+                    .or_default()
+                    .insert(objective_name.clone(), score);
+            }
+            (Some(objective_name), None) => {
+                if let Some(scores) = self.scores.get_mut(&update.owner) {
+                    scores.remove(objective_name);
+                    if scores.is_empty() {
+                        self.scores.remove(&update.owner);
+                    }
+                }
+            }
+            (None, _) => {
+                self.scores.remove(&update.owner);
+            }
+        }
+    }
+
+    /// Add players to a team, removing them from whatever team they were in
+    /// before.
+    pub fn add_players_to_team(&mut self, team_name: &str, players: Vec<String>) {
+        for player in players {
+            for team in self.teams.values_mut() {
+                team.members.remove(&player);
+            }
+            if let Some(team) = self.teams.get_mut(team_name) {
+                team.members.insert(player);
+            }
+        }
+    }
+}
@@ -18,8 +18,12 @@ impl BitSet {
         }
     }
 
+    /// Get the bit at the given index. Like in Java, this returns false if the
+    /// index is past the end of the set.
     pub fn index(&self, index: usize) -> bool {
-        (self.data[index / 64] & (1u64 << (index % 64))) != 0
+        self.data
+            .get(index / 64)
+            .is_some_and(|word| (word & (1u64 << (index % 64))) != 0)
     }
 
     fn check_range(&self, from_index: usize, to_index: usize) {
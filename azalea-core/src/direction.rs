@@ -1,6 +1,8 @@
 use azalea_buf::McBuf;
 
-#[derive(Clone, Copy, Debug, McBuf, Default)]
+use crate::BlockPos;
+
+#[derive(Clone, Copy, Debug, McBuf, Default, PartialEq, Eq, Hash)]
 pub enum Direction {
     #[default]
     Down = 0,
@@ -11,6 +13,21 @@ pub enum Direction {
     East,
 }
 
+impl Direction {
+    /// Get the offset of a block in this direction, like `0, 1, 0` for
+    /// [`Direction::Up`].
+    pub fn normal(self) -> BlockPos {
+        match self {
+            Direction::Down => BlockPos::new(0, -1, 0),
+            Direction::Up => BlockPos::new(0, 1, 0),
+            Direction::North => BlockPos::new(0, 0, -1),
+            Direction::South => BlockPos::new(0, 0, 1),
+            Direction::West => BlockPos::new(-1, 0, 0),
+            Direction::East => BlockPos::new(1, 0, 0),
+        }
+    }
+}
+
 // TODO: make azalea_block use this instead of FacingCardinal
 #[derive(Clone, Copy, Debug, McBuf)]
 pub enum CardinalDirection {
@@ -83,6 +100,17 @@ impl CardinalDirection {
     }
 }
 
+impl From<CardinalDirection> for Direction {
+    fn from(direction: CardinalDirection) -> Self {
+        match direction {
+            CardinalDirection::North => Direction::North,
+            CardinalDirection::South => Direction::South,
+            CardinalDirection::West => Direction::West,
+            CardinalDirection::East => Direction::East,
+        }
+    }
+}
+
 impl Axis {
     /// Pick x, y, or z from the arguments depending on the axis.
     #[inline]
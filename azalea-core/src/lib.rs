@@ -57,6 +57,12 @@ pub fn binary_search(mut min: i32, max: i32, predicate: &dyn Fn(i32) -> bool) ->
     min
 }
 
+/// Convert a rotation that was sent as a byte (where 256 is a full turn) into
+/// degrees.
+pub fn rotation_from_byte(rotation: i8) -> f32 {
+    (rotation as i32 * 360) as f32 / 256.
+}
+
 pub fn lcm(a: u32, b: u32) -> u64 {
     let gcd = gcd(a, b);
     (a as u64) * (b / gcd) as u64
@@ -91,4 +97,12 @@ mod tests {
         assert_eq!(gcd(12, 7), 1);
         assert_eq!(gcd(7, 12), 1);
     }
+
+    #[test]
+    fn test_rotation_from_byte() {
+        assert_eq!(rotation_from_byte(0), 0.);
+        assert_eq!(rotation_from_byte(64), 90.);
+        assert_eq!(rotation_from_byte(-64), -90.);
+        assert_eq!(rotation_from_byte(-128), -180.);
+    }
 }
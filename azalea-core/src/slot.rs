@@ -4,14 +4,14 @@ use azalea_buf::{BufReadError, McBuf, McBufReadable, McBufWritable};
 use azalea_nbt::Tag;
 use std::io::{Cursor, Write};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum Slot {
     #[default]
     Empty,
     Present(SlotData),
 }
 
-#[derive(Debug, Clone, McBuf)]
+#[derive(Debug, Clone, PartialEq, McBuf)]
 pub struct SlotData {
     #[var]
     pub id: u32,
@@ -19,6 +19,111 @@ pub struct SlotData {
     pub nbt: Tag,
 }
 
+impl Slot {
+    /// Whether this slot doesn't contain any items.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Slot::Empty => true,
+            Slot::Present(i) => i.count == 0,
+        }
+    }
+
+    /// The number of items in this slot, or 0 if it's empty.
+    pub fn count(&self) -> u8 {
+        match self {
+            Slot::Empty => 0,
+            Slot::Present(i) => i.count,
+        }
+    }
+
+    /// Take up to `count` items out of this slot and return them. If all the
+    /// items are taken, this slot becomes [`Slot::Empty`].
+    pub fn split(&mut self, count: u8) -> Slot {
+        let Slot::Present(i) = self else {
+            return Slot::Empty;
+        };
+        let count = count.min(i.count);
+        if count == 0 {
+            return Slot::Empty;
+        }
+        let mut taken = i.clone();
+        taken.count = count;
+        i.count -= count;
+        if i.count == 0 {
+            *self = Slot::Empty;
+        }
+        Slot::Present(taken)
+    }
+}
+
+impl SlotData {
+    /// Whether this item has the same id and NBT as another item, meaning they
+    /// can be stacked together.
+    pub fn is_same_item_and_nbt(&self, other: &SlotData) -> bool {
+        self.id == other.id && self.nbt == other.nbt
+    }
+
+    /// Get the compound tag with the item's NBT data, or `None` if it doesn't
+    /// have any.
+    pub fn tag(&self) -> Option<&Tag> {
+        let compound = self.nbt.as_compound()?;
+        // the root compound is wrapped in another compound that has its name
+        match compound.get("") {
+            Some(root @ Tag::Compound(_)) => Some(root),
+            _ => Some(&self.nbt),
+        }
+    }
+
+    /// How much durability the item has lost. This is 0 for items that can't
+    /// be damaged.
+    pub fn damage(&self) -> u32 {
+        match self
+            .tag()
+            .and_then(|tag| tag.as_compound())
+            .and_then(|compound| compound.get("Damage"))
+        {
+            Some(Tag::Int(damage)) => (*damage).max(0) as u32,
+            _ => 0,
+        }
+    }
+
+    /// Get the level of the given enchantment on this item, or 0 if it doesn't
+    /// have it. The enchantment is a resource location like
+    /// `minecraft:efficiency`.
+    pub fn enchantment_level(&self, enchantment: &str) -> u32 {
+        let enchantment = enchantment
+            .strip_prefix("minecraft:")
+            .unwrap_or(enchantment);
+
+        let Some(compound) = self.tag().and_then(|tag| tag.as_compound()) else {
+            return 0;
+        };
+        let Some(enchantments) = compound.get("Enchantments").and_then(|t| t.as_list()) else {
+            return 0;
+        };
+
+        for entry in enchantments {
+            let Some(entry) = entry.as_compound() else {
+                continue;
+            };
+            let Some(id) = entry.get("id").and_then(|t| t.as_string()) else {
+                continue;
+            };
+            if id.strip_prefix("minecraft:").unwrap_or(id) != enchantment {
+                continue;
+            }
+            let level = match entry.get("lvl") {
+                Some(Tag::Short(lvl)) => *lvl as i32,
+                Some(Tag::Int(lvl)) => *lvl,
+                Some(Tag::Byte(lvl)) => *lvl as i32,
+                _ => 0,
+            };
+            return level.clamp(0, 255) as u32;
+        }
+        0
+    }
+}
+
 impl McBufReadable for Slot {
     fn read_from(buf: &mut Cursor<&[u8]>) -> Result<Self, BufReadError> {
         let slot = Option::<SlotData>::read_from(buf)?;
@@ -38,3 +143,64 @@ impl McBufWritable for Slot {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_slot() {
+        let mut slot = Slot::Present(SlotData {
+            id: 1,
+            count: 5,
+            nbt: Tag::End,
+        });
+        let taken = slot.split(3);
+        assert_eq!(taken.count(), 3);
+        assert_eq!(slot.count(), 2);
+
+        let taken = slot.split(10);
+        assert_eq!(taken.count(), 2);
+        assert_eq!(slot, Slot::Empty);
+        assert!(slot.is_empty());
+    }
+
+    #[test]
+    fn test_enchantment_level() {
+        let enchantment = |id: &str, lvl: i16| {
+            Tag::Compound(
+                vec![
+                    ("id".to_string(), Tag::String(id.to_string())),
+                    ("lvl".to_string(), Tag::Short(lvl)),
+                ]
+                .into_iter()
+                .collect(),
+            )
+        };
+        let item = SlotData {
+            id: 1,
+            count: 1,
+            nbt: Tag::Compound(
+                vec![(
+                    "".to_string(),
+                    Tag::Compound(
+                        vec![(
+                            "Enchantments".to_string(),
+                            Tag::List(vec![
+                                enchantment("minecraft:unbreaking", 3),
+                                enchantment("minecraft:efficiency", 5),
+                            ]),
+                        )]
+                        .into_iter()
+                        .collect(),
+                    ),
+                )]
+                .into_iter()
+                .collect(),
+            ),
+        };
+        assert_eq!(item.enchantment_level("minecraft:efficiency"), 5);
+        assert_eq!(item.enchantment_level("unbreaking"), 3);
+        assert_eq!(item.enchantment_level("minecraft:aqua_affinity"), 0);
+    }
+}
@@ -4,16 +4,18 @@ mod mergers;
 mod shape;
 mod world_collisions;
 
-use azalea_core::{Axis, Vec3, AABB, EPSILON};
+use azalea_block::{Block, BlockState};
+use azalea_core::{Axis, BlockPos, Vec3, AABB, EPSILON};
 use azalea_world::{
     entity::{self},
-    Instance, MoveEntityError,
+    Instance, MoveEntityError, WorldBorder,
 };
 pub use blocks::BlockWithShape;
 pub use discrete_voxel_shape::*;
 pub use shape::*;
 
-use self::world_collisions::get_block_collisions;
+pub(crate) use self::world_collisions::get_block_collisions;
+pub use self::world_collisions::{get_entity_collisions, SolidEntityQuery};
 
 pub enum MoverType {
     Own,
@@ -52,16 +54,32 @@ pub enum MoverType {
 
 //     return var4;
 // }
-fn collide(movement: &Vec3, world: &Instance, physics: &entity::Physics) -> Vec3 {
+fn collide(
+    movement: &Vec3,
+    world: &Instance,
+    entity_collisions: &[AABB],
+    position: &entity::Position,
+    physics: &entity::Physics,
+) -> Vec3 {
     let entity_bounding_box = physics.bounding_box;
-    // TODO: get_entity_collisions
-    // let entity_collisions = world.get_entity_collisions(self,
-    // entity_bounding_box.expand_towards(movement));
-    let entity_collisions = Vec::new();
+    let movement_aabb = entity_bounding_box
+        .expand_towards(movement)
+        .inflate(1.0e-7, 1.0e-7, 1.0e-7);
+    let entity_collisions = entity_collisions
+        .iter()
+        .filter(|aabb| aabb.intersects_aabb(&movement_aabb))
+        .map(|aabb| VoxelShape::from(*aabb))
+        .collect();
     if movement.length_sqr() == 0.0 {
         *movement
     } else {
-        collide_bounding_box(movement, &entity_bounding_box, world, entity_collisions)
+        collide_bounding_box(
+            movement,
+            &entity_bounding_box,
+            position,
+            world,
+            entity_collisions,
+        )
     }
 
     // TODO: stepping (for stairs and stuff)
@@ -69,13 +87,20 @@ fn collide(movement: &Vec3, world: &Instance, physics: &entity::Physics) -> Vec3
     // collided_movement
 }
 
-/// Move an entity by a given delta, checking for collisions.
+/// Move an entity by a given delta, checking for collisions with blocks and
+/// with the bounding boxes in `entity_collisions`, which you can get from
+/// [`get_entity_collisions`].
+///
+/// If `sneaking` is true, the entity won't move off the edge of the block it's
+/// standing on.
 pub fn move_colliding(
-    _mover_type: &MoverType,
+    mover_type: &MoverType,
     movement: &Vec3,
     world: &Instance,
+    entity_collisions: &[AABB],
     position: &mut entity::Position,
     physics: &mut entity::Physics,
+    sneaking: bool,
 ) -> Result<(), MoveEntityError> {
     // TODO: do all these
 
@@ -90,15 +115,24 @@ pub fn move_colliding(
     //     }
     // }
 
-    // if (this.stuckSpeedMultiplier.lengthSqr() > 1.0E-7D) {
-    //     var2 = var2.multiply(this.stuckSpeedMultiplier);
-    //     this.stuckSpeedMultiplier = Vec3.ZERO;
-    //     this.setDeltaMovement(Vec3.ZERO);
-    // }
+    let mut movement = *movement;
+    if physics.stuck_speed_multiplier.length_squared() > 1.0e-7 {
+        let stuck_speed_multiplier = physics.stuck_speed_multiplier;
+        movement = movement.multiply(
+            stuck_speed_multiplier.x,
+            stuck_speed_multiplier.y,
+            stuck_speed_multiplier.z,
+        );
+        physics.stuck_speed_multiplier = Vec3::default();
+        physics.delta = Vec3::default();
+    }
 
-    // movement = this.maybeBackOffFromEdge(movement, moverType);
+    if sneaking && matches!(mover_type, MoverType::Own | MoverType::Player) {
+        movement = maybe_back_off_from_edge(&movement, world, physics);
+    }
+    let movement = &movement;
 
-    let collide_result = collide(movement, world, physics);
+    let collide_result = collide(movement, world, entity_collisions, position, physics);
 
     let move_distance = collide_result.length_sqr();
 
@@ -122,6 +156,7 @@ pub fn move_colliding(
     let vertical_collision = movement.y != collide_result.y;
     let on_ground = vertical_collision && movement.y < 0.;
     physics.on_ground = on_ground;
+    physics.horizontal_collision = horizontal_collision;
 
     // TODO: minecraft checks for a "minor" horizontal collision here
 
@@ -159,11 +194,14 @@ pub fn move_colliding(
 
     // sounds
 
-    // this.tryCheckInsideBlocks();
+    check_inside_blocks(world, position, physics);
+
+    let block_speed_factor = block_speed_factor(world, position) as f64;
+    physics.delta = physics
+        .delta
+        .multiply(block_speed_factor, 1., block_speed_factor);
 
-    // float var25 = this.getBlockSpeedFactor();
-    // this.setDeltaMovement(this.getDeltaMovement().multiply((double)var25, 1.0D,
-    // (double)var25)); if (this.level.getBlockStatesIfLoaded(this.
+    // if (this.level.getBlockStatesIfLoaded(this.
     // getBoundingBox().deflate(1.0E-6D)).noneMatch((var0) -> {
     //    return var0.is(BlockTags.FIRE) || var0.is(Blocks.LAVA);
     // })) {
@@ -183,9 +221,139 @@ pub fn move_colliding(
     Ok(())
 }
 
+/// How high an entity can step up without jumping. This is always 0.6 for
+/// players.
+// TODO: this should be used for stepping up blocks too
+const MAX_UP_STEP: f64 = 0.6;
+
+/// Shorten the movement so a sneaking entity doesn't walk off the edge of the
+/// block it's standing on.
+fn maybe_back_off_from_edge(movement: &Vec3, world: &Instance, physics: &entity::Physics) -> Vec3 {
+    // TODO: vanilla also does this while falling a short distance onto a block,
+    // and not while creative flying
+    if movement.y > 0. || !physics.on_ground {
+        return *movement;
+    }
+
+    const STEP: f64 = 0.05;
+    // move the value closer to 0 by one step
+    let back_off = |value: f64| {
+        if (-STEP..STEP).contains(&value) {
+            0.
+        } else if value > 0. {
+            value - STEP
+        } else {
+            value + STEP
+        }
+    };
+    let no_collision = |x: f64, z: f64| {
+        get_block_collisions(
+            world,
+            physics.bounding_box.move_relative(x, -MAX_UP_STEP, z),
+        )
+        .next()
+        .is_none()
+    };
+
+    let mut x = movement.x;
+    let mut z = movement.z;
+    while x != 0. && no_collision(x, 0.) {
+        x = back_off(x);
+    }
+    while z != 0. && no_collision(0., z) {
+        z = back_off(z);
+    }
+    while x != 0. && z != 0. && no_collision(x, z) {
+        x = back_off(x);
+        z = back_off(z);
+    }
+
+    Vec3 {
+        x,
+        y: movement.y,
+        z,
+    }
+}
+
+/// Let the blocks that the entity is inside of affect it, like cobwebs slowing
+/// it down.
+fn check_inside_blocks(
+    world: &Instance,
+    position: &entity::Position,
+    physics: &mut entity::Physics,
+) {
+    // the bounding box isn't updated until the end of the tick
+    let aabb = physics.dimensions.make_bounding_box(position);
+    let min = BlockPos::from(Vec3 {
+        x: aabb.min_x + 1.0e-7,
+        y: aabb.min_y + 1.0e-7,
+        z: aabb.min_z + 1.0e-7,
+    });
+    let max = BlockPos::from(Vec3 {
+        x: aabb.max_x - 1.0e-7,
+        y: aabb.max_y - 1.0e-7,
+        z: aabb.max_z - 1.0e-7,
+    });
+    let feet_block_pos = BlockPos::from(**position);
+
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            for z in min.z..=max.z {
+                let pos = BlockPos::new(x, y, z);
+                let Some(block_state) = world.chunks.get_block_state(&pos) else {
+                    continue;
+                };
+                // TODO: honey blocks make entities slide down their sides, and
+                // sweet berry bushes don't slow down foxes or bees
+                match azalea_registry::Block::from(block_state) {
+                    azalea_registry::Block::Cobweb => {
+                        physics.stuck_speed_multiplier = Vec3::new(0.25, 0.05f32 as f64, 0.25);
+                    }
+                    azalea_registry::Block::SweetBerryBush => {
+                        physics.stuck_speed_multiplier =
+                            Vec3::new(0.8f32 as f64, 0.75, 0.8f32 as f64);
+                    }
+                    azalea_registry::Block::PowderSnow if pos == feet_block_pos => {
+                        physics.stuck_speed_multiplier =
+                            Vec3::new(0.9f32 as f64, 1.5, 0.9f32 as f64);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// What the entity's horizontal movement should be multiplied by because of
+/// the block it's in or standing on, like soul sand.
+// TODO: soul speed, and this is always 1 while flying
+fn block_speed_factor(world: &Instance, position: &entity::Position) -> f32 {
+    let block_at_pos = world
+        .chunks
+        .get_block_state(&BlockPos::from(**position))
+        .unwrap_or(BlockState::AIR);
+    let speed_factor = Box::<dyn Block>::from(block_at_pos).behavior().speed_factor;
+    // the block below doesn't matter if we're in water
+    if speed_factor != 1.
+        || matches!(
+            azalea_registry::Block::from(block_at_pos),
+            azalea_registry::Block::Water | azalea_registry::Block::BubbleColumn
+        )
+    {
+        return speed_factor;
+    }
+
+    let block_below = world
+        .chunks
+        .get_block_state(&crate::get_block_pos_below_that_affects_movement(position))
+        .unwrap_or(BlockState::AIR);
+    Box::<dyn Block>::from(block_below).behavior().speed_factor
+}
+
 fn collide_bounding_box(
     movement: &Vec3,
     entity_bounding_box: &AABB,
+    entity_position: &Vec3,
     world: &Instance,
     entity_collisions: Vec<VoxelShape>,
 ) -> Vec3 {
@@ -195,15 +363,54 @@ fn collide_bounding_box(
         collision_boxes.extend(entity_collisions);
     }
 
-    // TODO: world border
+    let movement_aabb = entity_bounding_box.expand_towards(movement);
 
-    let block_collisions =
-        get_block_collisions(world, entity_bounding_box.expand_towards(movement));
+    if world
+        .world_border
+        .is_inside_close_to_border(entity_position, &movement_aabb)
+    {
+        collision_boxes.push(world_border_shape(&world.world_border));
+    }
+
+    let block_collisions = get_block_collisions(world, movement_aabb);
     let block_collisions = block_collisions.collect::<Vec<_>>();
     collision_boxes.extend(block_collisions);
     collide_with_shapes(movement, *entity_bounding_box, &collision_boxes)
 }
 
+/// A shape that fills everything outside of the world border.
+///
+/// Vanilla makes this with `Shapes.join(Shapes.INFINITY, Shapes.box(minX,
+/// -inf, minZ, maxX, inf, maxZ), BooleanOp.ONLY_FIRST)`, we make the resulting
+/// shape directly.
+fn world_border_shape(world_border: &WorldBorder) -> VoxelShape {
+    let mut shape = BitSetDiscreteVoxelShape::new(3, 1, 3);
+    for x in 0..3 {
+        for z in 0..3 {
+            // the middle is the inside of the border
+            if x != 1 || z != 1 {
+                shape.fill(x, 0, z);
+            }
+        }
+    }
+    VoxelShape::Array(ArrayVoxelShape::new(
+        DiscreteVoxelShape::BitSet(shape),
+        vec![
+            f64::NEG_INFINITY,
+            world_border.min_x(),
+            world_border.max_x(),
+            f64::INFINITY,
+        ],
+        vec![f64::NEG_INFINITY, f64::INFINITY],
+        vec![
+            f64::NEG_INFINITY,
+            world_border.min_z(),
+            world_border.max_z(),
+            f64::INFINITY,
+        ],
+    ))
+}
+
 fn collide_with_shapes(
     movement: &Vec3,
     mut entity_box: AABB,
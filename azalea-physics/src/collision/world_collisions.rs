@@ -2,7 +2,17 @@ use super::Shapes;
 use crate::collision::{BlockWithShape, VoxelShape, AABB};
 use azalea_block::BlockState;
 use azalea_core::{ChunkPos, ChunkSectionPos, Cursor3d, CursorIterationType, EPSILON};
-use azalea_world::{Chunk, Instance};
+use azalea_world::{
+    entity::{
+        metadata::{Boat, ChestBoat, Shulker},
+        Dead, Local, Physics, WorldName,
+    },
+    Chunk, Instance,
+};
+use bevy_ecs::{
+    query::{Or, With, Without},
+    system::Query,
+};
 use parking_lot::RwLock;
 use std::sync::Arc;
 
@@ -10,6 +20,32 @@ pub fn get_block_collisions(world: &Instance, aabb: AABB) -> BlockCollisions<'_>
     BlockCollisions::new(world, aabb)
 }
 
+/// A query for the entities that other entities can't move through, which are
+/// boats and shulkers.
+pub type SolidEntityQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Physics, &'static WorldName),
+    (
+        Or<(With<Boat>, With<ChestBoat>, With<Shulker>)>,
+        Without<Local>,
+        Without<Dead>,
+    ),
+>;
+
+/// Get the bounding boxes of all the solid entities in the world, which should
+/// be passed to [`move_colliding`](super::move_colliding).
+pub fn get_entity_collisions(
+    solid_entities: &SolidEntityQuery,
+    world_name: &WorldName,
+) -> Vec<AABB> {
+    solid_entities
+        .iter()
+        .filter(|(_, entity_world_name)| *entity_world_name == world_name)
+        .map(|(physics, _)| physics.bounding_box)
+        .collect()
+}
+
 pub struct BlockCollisions<'a> {
     pub world: &'a Instance,
     pub aabb: AABB,
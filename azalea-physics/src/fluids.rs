@@ -0,0 +1,273 @@
+//! Water and lava. This figures out whether entities are in a fluid and pushes
+//! them around with the flow.
+
+use azalea_block::{BlockState, FluidKind, FluidState};
+use azalea_core::{BlockPos, ChunkPos, Vec3, AABB};
+use azalea_world::{
+    entity::{EntityKind, Local, Physics, WorldName},
+    Instance, InstanceContainer,
+};
+use bevy_ecs::{
+    query::With,
+    system::{Query, Res},
+};
+
+use crate::collision::{block_shape, get_block_collisions, BlockWithShape};
+
+/// Update the `is_in_water`, `is_in_lava`, `water_height` and `lava_height`
+/// fields in [`Physics`], and push entities with the flow of the fluid they're
+/// in.
+pub fn update_in_water_state_and_do_fluid_pushing(
+    mut query: Query<(&mut Physics, &EntityKind, &WorldName), With<Local>>,
+    world_container: Res<InstanceContainer>,
+) {
+    for (mut physics, kind, world_name) in &mut query {
+        let Some(world_lock) = world_container.get(world_name) else {
+            continue;
+        };
+        let world = world_lock.read();
+
+        let is_player = **kind == azalea_registry::EntityKind::Player;
+
+        // TODO: entities in boats aren't in water
+        let (is_in_water, water_height) = update_fluid_height_and_do_fluid_pushing(
+            &mut physics,
+            &world,
+            FluidKind::Water,
+            0.014,
+            is_player,
+        );
+        physics.is_in_water = is_in_water;
+        physics.water_height = water_height;
+
+        // TODO: lava pushes harder (0.007) in ultrawarm dimensions like the nether
+        let (is_in_lava, lava_height) = update_fluid_height_and_do_fluid_pushing(
+            &mut physics,
+            &world,
+            FluidKind::Lava,
+            0.0023333333333333335,
+            is_player,
+        );
+        physics.is_in_lava = is_in_lava;
+        physics.lava_height = lava_height;
+    }
+}
+
+/// Returns whether the entity is touching the fluid, and how deep it is in it.
+fn update_fluid_height_and_do_fluid_pushing(
+    physics: &mut Physics,
+    world: &Instance,
+    checking_fluid: FluidKind,
+    fluid_push_factor: f64,
+    is_player: bool,
+) -> (bool, f64) {
+    let aabb = physics.bounding_box.deflate(0.001, 0.001, 0.001);
+
+    let min_x = aabb.min_x.floor() as i32;
+    let min_y = aabb.min_y.floor() as i32;
+    let min_z = aabb.min_z.floor() as i32;
+    let max_x = aabb.max_x.ceil() as i32;
+    let max_y = aabb.max_y.ceil() as i32;
+    let max_z = aabb.max_z.ceil() as i32;
+
+    // vanilla doesn't push entities at all if they're touching an unloaded chunk
+    let min_chunk = ChunkPos::from(&BlockPos::new(min_x, 0, min_z));
+    let max_chunk = ChunkPos::from(&BlockPos::new(max_x, 0, max_z));
+    for chunk_x in min_chunk.x..=max_chunk.x {
+        for chunk_z in min_chunk.z..=max_chunk.z {
+            if world.chunks.get(&ChunkPos::new(chunk_x, chunk_z)).is_none() {
+                return (false, 0.);
+            }
+        }
+    }
+
+    let mut fluid_height = 0.;
+    let mut in_fluid = false;
+    let mut additional_player_delta = Vec3::default();
+    let mut num_fluids_being_touched = 0;
+
+    for x in min_x..max_x {
+        for y in min_y..max_y {
+            for z in min_z..max_z {
+                let pos = BlockPos::new(x, y, z);
+                let Some(fluid_at_cur_pos) = world.get_fluid_state(&pos) else {
+                    continue;
+                };
+                if fluid_at_cur_pos.kind != checking_fluid {
+                    continue;
+                }
+                let fluid_max_y = y as f64 + get_fluid_height(world, &pos, &fluid_at_cur_pos);
+                if fluid_max_y < aabb.min_y {
+                    continue;
+                }
+                in_fluid = true;
+                fluid_height = f64::max(fluid_max_y - aabb.min_y, fluid_height);
+
+                let mut flow = get_flow(world, &pos, &fluid_at_cur_pos);
+                if fluid_height < 0.4 {
+                    flow = flow.scale(fluid_height);
+                }
+                additional_player_delta += flow;
+                num_fluids_being_touched += 1;
+            }
+        }
+    }
+
+    if additional_player_delta.length_squared() > 0. {
+        additional_player_delta =
+            additional_player_delta.scale(1. / num_fluids_being_touched as f64);
+        if !is_player {
+            additional_player_delta = additional_player_delta.normalize();
+        }
+
+        let delta = physics.delta;
+        additional_player_delta = additional_player_delta.scale(fluid_push_factor);
+        const MIN_PUSH: f64 = 0.0045000000000000005;
+        if delta.x.abs() < 0.003
+            && delta.z.abs() < 0.003
+            && additional_player_delta.length_squared() < MIN_PUSH * MIN_PUSH
+        {
+            additional_player_delta = additional_player_delta.normalize().scale(MIN_PUSH);
+        }
+
+        physics.delta += additional_player_delta;
+    }
+
+    (in_fluid, fluid_height)
+}
+
+/// How high the fluid in the block goes, from 0 to 1. This is always 1 if
+/// there's more of the same fluid above it.
+pub fn get_fluid_height(world: &Instance, pos: &BlockPos, fluid: &FluidState) -> f64 {
+    let fluid_above = world.get_fluid_state(&pos.up(1)).unwrap_or_default();
+    if fluid_above.kind == fluid.kind {
+        1.
+    } else {
+        fluid.own_height() as f64
+    }
+}
+
+/// The direction that the fluid at the given position is flowing in, as a
+/// normalized vector.
+pub fn get_flow(world: &Instance, pos: &BlockPos, fluid: &FluidState) -> Vec3 {
+    let mut z_flow: f64 = 0.;
+    let mut x_flow: f64 = 0.;
+
+    for (x, z) in HORIZONTAL_OFFSETS {
+        let adjacent_pos = BlockPos::new(pos.x + x, pos.y, pos.z + z);
+        let adjacent_fluid = world.get_fluid_state(&adjacent_pos).unwrap_or_default();
+        if !affects_flow(fluid, &adjacent_fluid) {
+            continue;
+        }
+        let mut adjacent_fluid_height = adjacent_fluid.own_height();
+        let mut flow_strength = 0.;
+        if adjacent_fluid_height == 0. {
+            if !blocks_motion(world, &adjacent_pos) {
+                let below_pos = adjacent_pos.down(1);
+                let fluid_below = world.get_fluid_state(&below_pos).unwrap_or_default();
+                if affects_flow(fluid, &fluid_below) {
+                    adjacent_fluid_height = fluid_below.own_height();
+                    if adjacent_fluid_height > 0. {
+                        flow_strength = fluid.own_height() - (adjacent_fluid_height - 0.8888889);
+                    }
+                }
+            }
+        } else if adjacent_fluid_height > 0. {
+            flow_strength = fluid.own_height() - adjacent_fluid_height;
+        }
+
+        if flow_strength != 0. {
+            x_flow += (x as f32 * flow_strength) as f64;
+            z_flow += (z as f32 * flow_strength) as f64;
+        }
+    }
+
+    let mut flow = Vec3::new(x_flow, 0., z_flow);
+    if fluid.falling {
+        for (x, z) in HORIZONTAL_OFFSETS {
+            let adjacent_pos = BlockPos::new(pos.x + x, pos.y, pos.z + z);
+            if is_solid_face(world, fluid, &adjacent_pos)
+                || is_solid_face(world, fluid, &adjacent_pos.up(1))
+            {
+                flow = flow.normalize() + Vec3::new(0., -6., 0.);
+                break;
+            }
+        }
+    }
+
+    flow.normalize()
+}
+
+/// Whether the eyes of an entity are in the given fluid, which is what decides
+/// whether it's underwater.
+pub fn is_eye_in_fluid(world: &Instance, eye_position: Vec3, fluid: FluidKind) -> bool {
+    // vanilla moves the eyes down a little bit for this check
+    let eye_y = eye_position.y - 0.1111111119389534;
+    let eye_block_pos = BlockPos::from(Vec3::new(eye_position.x, eye_y, eye_position.z));
+    let Some(fluid_at_eyes) = world.get_fluid_state(&eye_block_pos) else {
+        return false;
+    };
+    if fluid_at_eyes.kind != fluid {
+        return false;
+    }
+    let fluid_max_y =
+        eye_block_pos.y as f64 + get_fluid_height(world, &eye_block_pos, &fluid_at_eyes);
+    fluid_max_y > eye_y
+}
+
+/// Whether there's no block collisions or fluids in the bounding box.
+pub fn is_free(world: &Instance, aabb: &AABB) -> bool {
+    get_block_collisions(world, *aabb).next().is_none() && !contains_any_liquid(world, aabb)
+}
+
+fn contains_any_liquid(world: &Instance, aabb: &AABB) -> bool {
+    let min_x = aabb.min_x.floor() as i32;
+    let min_y = aabb.min_y.floor() as i32;
+    let min_z = aabb.min_z.floor() as i32;
+    let max_x = aabb.max_x.ceil() as i32;
+    let max_y = aabb.max_y.ceil() as i32;
+    let max_z = aabb.max_z.ceil() as i32;
+
+    for x in min_x..max_x {
+        for y in min_y..max_y {
+            for z in min_z..max_z {
+                let fluid = world
+                    .get_fluid_state(&BlockPos::new(x, y, z))
+                    .unwrap_or_default();
+                if !fluid.is_empty() {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// North, south, west and east, in the same order as vanilla.
+const HORIZONTAL_OFFSETS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+/// Whether the fluid next to this one should change which way it flows. This is
+/// true for the same fluid and for blocks without any fluid.
+fn affects_flow(fluid: &FluidState, adjacent_fluid: &FluidState) -> bool {
+    adjacent_fluid.is_empty() || adjacent_fluid.kind == fluid.kind
+}
+
+// TODO: vanilla uses the block's material here, which isn't quite the same
+// thing as having a collision shape
+fn blocks_motion(world: &Instance, pos: &BlockPos) -> bool {
+    let block_state = world.chunks.get_block_state(pos).unwrap_or(BlockState::AIR);
+    !block_state.shape().is_empty()
+}
+
+// TODO: vanilla checks whether the side of the block is sturdy, and ice never
+// counts
+fn is_solid_face(world: &Instance, fluid: &FluidState, adjacent_pos: &BlockPos) -> bool {
+    let block_state = world
+        .chunks
+        .get_block_state(adjacent_pos)
+        .unwrap_or(BlockState::AIR);
+    if FluidState::from(block_state).kind == fluid.kind {
+        return false;
+    }
+    block_state.shape() == &block_shape()
+}
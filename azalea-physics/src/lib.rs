@@ -2,25 +2,31 @@
 #![feature(trait_alias)]
 
 pub mod collision;
+pub mod fluids;
+pub mod simulation;
 
 use azalea_block::{Block, BlockState};
-use azalea_core::{BlockPos, Vec3};
+use azalea_core::{BlockPos, Vec3, AABB};
+use azalea_registry::{tags, MobEffect};
 use azalea_world::{
     entity::{
-        metadata::Sprinting, move_relative, Attributes, Jumping, Local, Physics, Position,
-        WorldName,
+        metadata::{AbstractLiving, FallFlying, Sprinting},
+        move_relative, view_vector, ActiveEffects, Attributes, Dead, EyeHeight, Jumping, Local,
+        Physics, Position, Sneaking, WorldName,
     },
     Instance, InstanceContainer,
 };
 use bevy_app::{App, CoreSchedule, IntoSystemAppConfigs, Plugin};
 use bevy_ecs::{
+    component::Component,
     entity::Entity,
-    event::{EventReader, EventWriter},
+    event::EventReader,
     query::With,
     schedule::{IntoSystemConfig, IntoSystemConfigs, SystemSet},
     system::{Query, Res},
 };
-use collision::{move_colliding, MoverType};
+use collision::{get_entity_collisions, move_colliding, MoverType, SolidEntityQuery};
+use fluids::{is_free, update_in_water_state_and_do_fluid_pushing};
 
 /// A Bevy [`SystemSet`] for running physics that makes entities do things.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
@@ -32,7 +38,13 @@ impl Plugin for PhysicsPlugin {
         app.add_event::<ForceJumpEvent>()
             .add_system(force_jump_listener.before(azalea_world::entity::update_bounding_box))
             .add_systems(
-                (ai_step, travel)
+                (
+                    update_in_water_state_and_do_fluid_pushing,
+                    ai_step,
+                    travel,
+                    boost_with_fireworks,
+                    push_entities,
+                )
                     .chain()
                     .in_set(PhysicsSet)
                     .in_schedule(CoreSchedule::FixedUpdate),
@@ -42,28 +54,104 @@ impl Plugin for PhysicsPlugin {
 
 /// Move the entity with the given acceleration while handling friction,
 /// gravity, collisions, and some other stuff.
+#[allow(clippy::type_complexity)]
 fn travel(
-    mut query: Query<(&mut Physics, &mut Position, &Attributes, &WorldName), With<Local>>,
+    mut query: Query<
+        (
+            &mut Physics,
+            &mut Position,
+            &Attributes,
+            &ActiveEffects,
+            Option<&Sprinting>,
+            Option<&mut FallFlying>,
+            &Jumping,
+            &Sneaking,
+            &EyeHeight,
+            &WorldName,
+        ),
+        With<Local>,
+    >,
+    solid_entities: SolidEntityQuery,
     world_container: Res<InstanceContainer>,
 ) {
-    for (mut physics, mut position, attributes, world_name) in &mut query {
+    for (
+        mut physics,
+        mut position,
+        attributes,
+        active_effects,
+        sprinting,
+        fall_flying,
+        jumping,
+        sneaking,
+        eye_height,
+        world_name,
+    ) in &mut query
+    {
         let world_lock = world_container
             .get(world_name)
             .expect("All entities should be in a valid world");
         let world = world_lock.read();
+        let entity_collisions = get_entity_collisions(&solid_entities, world_name);
         // if !self.is_effective_ai() && !self.is_controlled_by_local_instance() {
         //     // this.calculateEntityAnimation(this, this instanceof FlyingAnimal);
         //     return;
         // }
 
-        let gravity: f64 = 0.08;
+        let mut gravity: f64 = 0.08;
 
-        // TODO: slow falling effect
-        // let is_falling = self.delta.y <= 0.;
+        let is_falling = physics.delta.y <= 0.;
+        if is_falling && active_effects.has(MobEffect::SlowFalling) {
+            gravity = 0.01;
+        }
 
-        // TODO: fluids
+        let sprinting = sprinting.is_some_and(|s| **s);
 
-        // TODO: elytra
+        // TODO: entities that can walk on fluids, like striders
+        if physics.is_in_water {
+            travel_in_water(
+                &world,
+                &entity_collisions,
+                &mut physics,
+                &mut position,
+                active_effects,
+                sprinting,
+                **sneaking,
+                gravity,
+                is_falling,
+            );
+            continue;
+        }
+        if physics.is_in_lava {
+            travel_in_lava(
+                &world,
+                &entity_collisions,
+                &mut physics,
+                &mut position,
+                **eye_height,
+                sprinting,
+                **sneaking,
+                gravity,
+                is_falling,
+            );
+            continue;
+        }
+
+        if let Some(mut fall_flying) = fall_flying.filter(|f| ***f) {
+            travel_fall_flying(
+                &world,
+                &entity_collisions,
+                &mut physics,
+                &mut position,
+                gravity,
+                **sneaking,
+            );
+            // the server stops us from fall flying when we land, so do the same
+            // thing here so we don't have to wait for it to tell us
+            if physics.on_ground {
+                **fall_flying = false;
+            }
+            continue;
+        }
 
         let block_pos_below = get_block_pos_below_that_affects_movement(&position);
 
@@ -81,15 +169,22 @@ fn travel(
         };
 
         // this applies the current delta
+        let speed = get_friction_influenced_speed(&physics, attributes, block_friction, sprinting);
         let mut movement = handle_relative_friction_and_calculate_movement(
-            block_friction,
+            speed,
             &world,
+            &entity_collisions,
             &mut physics,
             &mut position,
-            attributes,
+            **jumping,
+            **sneaking,
         );
 
-        movement.y -= gravity;
+        if let Some(amplifier) = active_effects.amplifier(MobEffect::Levitation) {
+            movement.y += (0.05 * (amplifier as f64 + 1.) - movement.y) * 0.2;
+        } else {
+            movement.y -= gravity;
+        }
 
         // if (this.shouldDiscardFriction()) {
         //     this.setDeltaMovement(movement.x, yMovement, movement.z);
@@ -110,19 +205,275 @@ fn travel(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn travel_in_water(
+    world: &Instance,
+    entity_collisions: &[AABB],
+    physics: &mut Physics,
+    position: &mut Position,
+    active_effects: &ActiveEffects,
+    sprinting: bool,
+    sneaking: bool,
+    gravity: f64,
+    is_falling: bool,
+) {
+    let old_y = position.y;
+    let mut friction: f32 = if sprinting { 0.9 } else { 0.8 };
+    // TODO: depth strider
+    if active_effects.has(MobEffect::DolphinsGrace) {
+        friction = 0.96;
+    }
+
+    move_relative(physics, 0.02, &acceleration(physics));
+    move_colliding(
+        &MoverType::Own,
+        &physics.delta.clone(),
+        world,
+        entity_collisions,
+        position,
+        physics,
+        sneaking,
+    )
+    .expect("Entity should exist.");
+
+    if physics.horizontal_collision && on_climbable(world, position) {
+        physics.delta.y = 0.2;
+    }
+    physics.delta = physics
+        .delta
+        .multiply(friction as f64, 0.8f32 as f64, friction as f64);
+    physics.delta = fluid_falling_adjusted_movement(gravity, is_falling, physics.delta, sprinting);
+
+    step_out_of_fluid(world, physics, position, old_y);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn travel_in_lava(
+    world: &Instance,
+    entity_collisions: &[AABB],
+    physics: &mut Physics,
+    position: &mut Position,
+    eye_height: f32,
+    sprinting: bool,
+    sneaking: bool,
+    gravity: f64,
+    is_falling: bool,
+) {
+    let old_y = position.y;
+    move_relative(physics, 0.02, &acceleration(physics));
+    move_colliding(
+        &MoverType::Own,
+        &physics.delta.clone(),
+        world,
+        entity_collisions,
+        position,
+        physics,
+        sneaking,
+    )
+    .expect("Entity should exist.");
+
+    if physics.lava_height <= fluid_jump_threshold(eye_height) {
+        physics.delta = physics.delta.multiply(0.5, 0.8f32 as f64, 0.5);
+        physics.delta =
+            fluid_falling_adjusted_movement(gravity, is_falling, physics.delta, sprinting);
+    } else {
+        physics.delta = physics.delta.scale(0.5);
+    }
+    physics.delta.y -= gravity / 4.;
+
+    step_out_of_fluid(world, physics, position, old_y);
+}
+
+/// Glide with an elytra. Looking down makes the entity go faster, and looking
+/// up turns that speed into height.
+// TODO: the entity's bounding box should be 0.6 blocks tall while it's fall
+// flying
+fn travel_fall_flying(
+    world: &Instance,
+    entity_collisions: &[AABB],
+    physics: &mut Physics,
+    position: &mut Position,
+    gravity: f64,
+    sneaking: bool,
+) {
+    let look_angle = view_vector(physics);
+    let x_rot = physics.x_rot * 0.017453292;
+    let horizontal_look = f64::sqrt(look_angle.x * look_angle.x + look_angle.z * look_angle.z);
+    let horizontal_speed =
+        f64::sqrt(physics.delta.x * physics.delta.x + physics.delta.z * physics.delta.z);
+    let look_length = look_angle.length_squared().sqrt();
+    let x_rot_cos = f32::cos(x_rot);
+    let lift = (x_rot_cos as f64 * x_rot_cos as f64 * f64::min(1., look_length / 0.4)) as f32;
+
+    let mut delta = physics.delta;
+    delta.y += gravity * (-1. + lift as f64 * 0.75);
+    if delta.y < 0. && horizontal_look > 0. {
+        // turn falling speed into forward speed
+        let converted = delta.y * -0.1 * lift as f64;
+        delta += Vec3 {
+            x: look_angle.x * converted / horizontal_look,
+            y: converted,
+            z: look_angle.z * converted / horizontal_look,
+        };
+    }
+    if x_rot < 0. && horizontal_look > 0. {
+        // looking up turns forward speed into height
+        let converted = horizontal_speed * -f32::sin(x_rot) as f64 * 0.04;
+        delta += Vec3 {
+            x: -look_angle.x * converted / horizontal_look,
+            y: converted * 3.2,
+            z: -look_angle.z * converted / horizontal_look,
+        };
+    }
+    if horizontal_look > 0. {
+        // turn towards where we're looking
+        delta.x += (look_angle.x / horizontal_look * horizontal_speed - delta.x) * 0.1;
+        delta.z += (look_angle.z / horizontal_look * horizontal_speed - delta.z) * 0.1;
+    }
+
+    physics.delta = delta.multiply(0.99f32 as f64, 0.98f32 as f64, 0.99f32 as f64);
+    move_colliding(
+        &MoverType::Own,
+        &physics.delta.clone(),
+        world,
+        entity_collisions,
+        position,
+        physics,
+        sneaking,
+    )
+    .expect("Entity should exist.");
+}
+
+/// A component for the firework rockets that are pushing an entity forward
+/// while it's flying with an elytra. Each number is how many more ticks that
+/// rocket will keep pushing for.
+///
+/// Use [`FireworkBoosts::add_rocket`] when using a firework rocket while fall
+/// flying.
+#[derive(Component, Clone, Debug, Default)]
+pub struct FireworkBoosts(pub Vec<u32>);
+
+impl FireworkBoosts {
+    /// Start being pushed by a new firework rocket. `flight_duration` is the
+    /// `Flight` value in the rocket's NBT, which is 1 for rockets crafted with
+    /// one gunpowder.
+    pub fn add_rocket(&mut self, flight_duration: u8) {
+        // vanilla adds a random number of ticks from 0 to 11 to the lifetime,
+        // so use the average
+        let lifetime = 10 * (flight_duration as u32 + 1) + 6;
+        self.0.push(lifetime);
+    }
+}
+
+/// Push fall flying entities with the firework rockets attached to them.
+pub fn boost_with_fireworks(
+    mut query: Query<(&mut Physics, &mut FireworkBoosts, Option<&FallFlying>)>,
+) {
+    for (mut physics, mut firework_boosts, fall_flying) in &mut query {
+        if firework_boosts.0.is_empty() {
+            continue;
+        }
+        // rockets keep going if we stop fall flying, they just don't push us
+        if fall_flying.is_some_and(|f| **f) {
+            let look_angle = view_vector(&physics);
+            for _ in &firework_boosts.0 {
+                let delta = physics.delta;
+                physics.delta += Vec3 {
+                    x: look_angle.x * 0.1 + (look_angle.x * 1.5 - delta.x) * 0.5,
+                    y: look_angle.y * 0.1 + (look_angle.y * 1.5 - delta.y) * 0.5,
+                    z: look_angle.z * 0.1 + (look_angle.z * 1.5 - delta.z) * 0.5,
+                };
+            }
+        }
+        firework_boosts.0.retain_mut(|ticks_left| {
+            *ticks_left -= 1;
+            *ticks_left > 0
+        });
+    }
+}
+
+/// Gives the entity a boost upwards if it's swimming into the side of a block
+/// that it can get out onto.
+fn step_out_of_fluid(world: &Instance, physics: &mut Physics, position: &Position, old_y: f64) {
+    let delta = physics.delta;
+    let bounding_box = physics.dimensions.make_bounding_box(position);
+    if physics.horizontal_collision
+        && is_free(
+            world,
+            &bounding_box.move_relative(
+                delta.x,
+                delta.y + 0.6f32 as f64 - position.y + old_y,
+                delta.z,
+            ),
+        )
+    {
+        physics.delta.y = 0.3f32 as f64;
+    }
+}
+
+/// Makes entities sink slowly in fluids instead of falling at the normal speed.
+fn fluid_falling_adjusted_movement(
+    gravity: f64,
+    is_falling: bool,
+    delta: Vec3,
+    sprinting: bool,
+) -> Vec3 {
+    if gravity == 0. || sprinting {
+        return delta;
+    }
+    let y = if is_falling
+        && (delta.y - 0.005).abs() >= 0.003
+        && (delta.y - gravity / 16.).abs() < 0.003
+    {
+        -0.003
+    } else {
+        delta.y - gravity / 16.
+    };
+    Vec3 { y, ..delta }
+}
+
+/// How deep an entity has to be in a fluid before jumping makes it swim up
+/// instead of jumping normally.
+fn fluid_jump_threshold(eye_height: f32) -> f64 {
+    if eye_height < 0.4 {
+        0.
+    } else {
+        0.4
+    }
+}
+
+fn acceleration(physics: &Physics) -> Vec3 {
+    Vec3 {
+        x: physics.xxa as f64,
+        y: physics.yya as f64,
+        z: physics.zza as f64,
+    }
+}
+
 /// applies air resistance, calls self.travel(), and some other random
 /// stuff.
+#[allow(clippy::type_complexity)]
 pub fn ai_step(
     mut query: Query<
-        (Entity, &mut Physics, Option<&Jumping>),
+        (
+            &mut Physics,
+            Option<&Jumping>,
+            &EyeHeight,
+            &Position,
+            Option<&Sprinting>,
+            &ActiveEffects,
+            &WorldName,
+        ),
         With<Local>,
         // TODO: ai_step should only run for players in loaded chunks
         // With<LocalPlayerInLoadedChunk> maybe there should be an InLoadedChunk/InUnloadedChunk
         // component?
     >,
-    mut force_jump_events: EventWriter<ForceJumpEvent>,
+    world_container: Res<InstanceContainer>,
 ) {
-    for (entity, mut physics, jumping) in &mut query {
+    for (mut physics, jumping, eye_height, position, sprinting, active_effects, world_name) in
+        &mut query
+    {
         // vanilla does movement interpolation here, doesn't really matter much for a
         // bot though
 
@@ -138,10 +489,34 @@ pub fn ai_step(
 
         if let Some(jumping) = jumping {
             if **jumping {
-                // TODO: jumping in liquids and jump delay
+                // TODO: jump delay
+
+                let fluid_height = if physics.is_in_lava {
+                    physics.lava_height
+                } else {
+                    physics.water_height
+                };
+                let in_water = physics.is_in_water && fluid_height > 0.;
+                let fluid_jump_threshold = fluid_jump_threshold(**eye_height);
+                let can_jump_from_ground =
+                    physics.on_ground && fluid_height <= fluid_jump_threshold;
 
-                if physics.on_ground {
-                    force_jump_events.send(ForceJumpEvent(entity));
+                if (in_water || physics.is_in_lava) && !can_jump_from_ground {
+                    // swim up
+                    physics.delta.y += 0.04f32 as f64;
+                } else if physics.on_ground || in_water && fluid_height <= fluid_jump_threshold {
+                    // this has to happen right away instead of with a ForceJumpEvent,
+                    // otherwise we'd still be on the ground next tick and jump twice
+                    let Some(world_lock) = world_container.get(world_name) else {
+                        continue;
+                    };
+                    jump_from_ground(
+                        &world_lock.read(),
+                        &mut physics,
+                        position,
+                        sprinting.is_some_and(|s| **s),
+                        active_effects,
+                    );
                 }
             }
         }
@@ -149,8 +524,76 @@ pub fn ai_step(
         physics.xxa *= 0.98;
         physics.zza *= 0.98;
 
-        // TODO: freezing, pushEntities, drowning damage (in their own systems,
-        // after `travel`)
+        // TODO: freezing, drowning damage (in their own systems, after
+        // `travel`)
+    }
+}
+
+/// Push local entities away from the living entities that they're inside of,
+/// like when players are standing in a crowd.
+///
+/// On a vanilla client, other entities push the local player but the local
+/// player doesn't push anything, so entities that aren't [`Local`] are never
+/// pushed here.
+// TODO: boats and minecarts push entities too, and teams can disable pushing
+#[allow(clippy::type_complexity)]
+pub fn push_entities(
+    mut query: Query<(
+        Entity,
+        &mut Physics,
+        &Position,
+        &WorldName,
+        Option<&Local>,
+        Option<&AbstractLiving>,
+        Option<&Dead>,
+    )>,
+    world_container: Res<InstanceContainer>,
+) {
+    let pushers = query
+        .iter()
+        .filter(|(_, _, _, _, _, living, _)| living.is_some())
+        .map(|(entity, physics, position, world_name, _, _, _)| {
+            (entity, physics.bounding_box, **position, world_name.clone())
+        })
+        .collect::<Vec<_>>();
+    if pushers.is_empty() {
+        return;
+    }
+
+    for (entity, mut physics, position, world_name, local, _, dead) in &mut query {
+        if local.is_none() || dead.is_some() {
+            continue;
+        }
+        let Some(world_lock) = world_container.get(world_name) else {
+            continue;
+        };
+        // entities on ladders can't be pushed
+        if on_climbable(&world_lock.read(), position) {
+            continue;
+        }
+
+        for (pusher, pusher_bounding_box, pusher_position, pusher_world_name) in &pushers {
+            if *pusher == entity
+                || pusher_world_name != world_name
+                || !pusher_bounding_box.intersects_aabb(&physics.bounding_box)
+            {
+                continue;
+            }
+
+            let mut x_distance = pusher_position.x - position.x;
+            let mut z_distance = pusher_position.z - position.z;
+            let mut distance = f64::max(x_distance.abs(), z_distance.abs());
+            if distance < 0.009999999776482582 {
+                continue;
+            }
+            distance = distance.sqrt();
+            x_distance /= distance;
+            z_distance /= distance;
+            let scale = f64::min(1. / distance, 1.) * 0.05000000074505806;
+            physics.delta.x -= x_distance * scale;
+            physics.delta.z -= z_distance * scale;
+            physics.has_impulse = true;
+        }
     }
 }
 
@@ -158,39 +601,57 @@ pub fn ai_step(
 pub struct ForceJumpEvent(pub Entity);
 
 pub fn force_jump_listener(
-    mut query: Query<(&mut Physics, &Position, &Sprinting, &WorldName)>,
+    mut query: Query<(
+        &mut Physics,
+        &Position,
+        &Sprinting,
+        &ActiveEffects,
+        &WorldName,
+    )>,
     world_container: Res<InstanceContainer>,
     mut events: EventReader<ForceJumpEvent>,
 ) {
     for event in events.iter() {
-        if let Ok((mut physics, position, sprinting, world_name)) = query.get_mut(event.0) {
+        if let Ok((mut physics, position, sprinting, active_effects, world_name)) =
+            query.get_mut(event.0)
+        {
             let world_lock = world_container
                 .get(world_name)
                 .expect("All entities should be in a valid world");
             let world = world_lock.read();
 
-            let jump_power: f64 = jump_power(&world, position) as f64 + jump_boost_power();
-            let old_delta_movement = physics.delta;
-            physics.delta = Vec3 {
-                x: old_delta_movement.x,
-                y: jump_power,
-                z: old_delta_movement.z,
-            };
-            if **sprinting {
-                // sprint jumping gives some extra velocity
-                let y_rot = physics.y_rot * 0.017453292;
-                physics.delta += Vec3 {
-                    x: (-f32::sin(y_rot) * 0.2) as f64,
-                    y: 0.,
-                    z: (f32::cos(y_rot) * 0.2) as f64,
-                };
-            }
-
-            physics.has_impulse = true;
+            jump_from_ground(&world, &mut physics, position, **sprinting, active_effects);
         }
     }
 }
 
+fn jump_from_ground(
+    world: &Instance,
+    physics: &mut Physics,
+    position: &Position,
+    sprinting: bool,
+    active_effects: &ActiveEffects,
+) {
+    let jump_power: f64 = jump_power(world, position) as f64 + jump_boost_power(active_effects);
+    let old_delta_movement = physics.delta;
+    physics.delta = Vec3 {
+        x: old_delta_movement.x,
+        y: jump_power,
+        z: old_delta_movement.z,
+    };
+    if sprinting {
+        // sprint jumping gives some extra velocity
+        let y_rot = physics.y_rot * 0.017453292;
+        physics.delta += Vec3 {
+            x: (-f32::sin(y_rot) * 0.2) as f64,
+            y: 0.,
+            z: (f32::cos(y_rot) * 0.2) as f64,
+        };
+    }
+
+    physics.has_impulse = true;
+}
+
 fn get_block_pos_below_that_affects_movement(position: &Position) -> BlockPos {
     BlockPos::new(
         position.x.floor() as i32,
@@ -201,51 +662,103 @@ fn get_block_pos_below_that_affects_movement(position: &Position) -> BlockPos {
 }
 
 fn handle_relative_friction_and_calculate_movement(
-    block_friction: f32,
+    speed: f32,
     world: &Instance,
+    entity_collisions: &[AABB],
     physics: &mut Physics,
     position: &mut Position,
-    attributes: &Attributes,
+    jumping: bool,
+    sneaking: bool,
 ) -> Vec3 {
-    move_relative(
-        physics,
-        get_friction_influenced_speed(physics, attributes, block_friction),
-        &Vec3 {
-            x: physics.xxa as f64,
-            y: physics.yya as f64,
-            z: physics.zza as f64,
-        },
-    );
-    // entity.delta = entity.handle_on_climbable(entity.delta);
+    move_relative(physics, speed, &acceleration(physics));
+    if on_climbable(world, position) {
+        physics.delta = handle_on_climbable(world, position, physics.delta, sneaking);
+    }
     move_colliding(
         &MoverType::Own,
         &physics.delta.clone(),
         world,
+        entity_collisions,
         position,
         physics,
+        sneaking,
     )
     .expect("Entity should exist.");
-    // let delta_movement = entity.delta;
-    // ladders
-    //   if ((entity.horizontalCollision || entity.jumping) && (entity.onClimbable()
-    // || entity.getFeetBlockState().is(Blocks.POWDER_SNOW) &&
-    // PowderSnowBlock.canEntityWalkOnPowderSnow(entity))) {      var3 = new
-    // Vec3(var3.x, 0.2D, var3.z);   }
-    // TODO: powdered snow
-
-    physics.delta
+
+    // climb up when pressing against a ladder or jumping while on it
+    // TODO: walking on powder snow with leather boots
+    let mut movement = physics.delta;
+    if (physics.horizontal_collision || jumping) && on_climbable(world, position) {
+        movement.y = 0.2;
+    }
+    movement
+}
+
+/// Whether the entity is in a block that it can climb, like a ladder or vines.
+// TODO: open trapdoors above ladders are climbable too, and spectators can't
+// climb anything
+pub fn on_climbable(world: &Instance, position: &Position) -> bool {
+    let Some(block_state) = world.chunks.get_block_state(&BlockPos::from(**position)) else {
+        return false;
+    };
+    tags::blocks::CLIMBABLE.contains(&azalea_registry::Block::from(block_state))
+}
+
+/// Limit how fast the entity can move while it's on a ladder, and stop it
+/// from sliding down if it's sneaking.
+fn handle_on_climbable(world: &Instance, position: &Position, delta: Vec3, sneaking: bool) -> Vec3 {
+    const MAX_SPEED: f64 = 0.15f32 as f64;
+    let x = delta.x.clamp(-MAX_SPEED, MAX_SPEED);
+    let z = delta.z.clamp(-MAX_SPEED, MAX_SPEED);
+    let mut y = f64::max(delta.y, -MAX_SPEED);
+
+    let in_scaffolding = world
+        .chunks
+        .get_block_state(&BlockPos::from(**position))
+        .is_some_and(|block_state| {
+            azalea_registry::Block::from(block_state) == azalea_registry::Block::Scaffolding
+        });
+    // TODO: only players can stop themselves from sliding down
+    if y < 0. && !in_scaffolding && sneaking {
+        y = 0.;
+    }
+
+    Vec3 { x, y, z }
 }
 
 // private float getFrictionInfluencedSpeed(float friction) {
 //     return this.onGround ? this.getSpeed() * (0.21600002F / (friction *
 // friction * friction)) : this.flyingSpeed; }
-fn get_friction_influenced_speed(physics: &Physics, attributes: &Attributes, friction: f32) -> f32 {
-    // TODO: have speed & flying_speed fields in entity
+fn get_friction_influenced_speed(
+    physics: &Physics,
+    attributes: &Attributes,
+    friction: f32,
+    sprinting: bool,
+) -> f32 {
     if physics.on_ground {
         let speed: f32 = attributes.speed.calculate() as f32;
         speed * (0.216f32 / (friction * friction * friction))
     } else {
-        // entity.flying_speed
+        flying_speed(sprinting)
+    }
+}
+
+/// The speed that a player accelerates at while they're in the air.
+///
+/// This isn't the `generic.flying_speed` attribute, that one's only used by
+/// mobs like bees and parrots. Vanilla sets the player's flying speed in
+/// `Player.aiStep`:
+/// ```java
+/// this.flyingSpeed = 0.02F;
+/// if (this.isSprinting()) {
+///     this.flyingSpeed += 0.006F;
+/// }
+/// ```
+// TODO: use the flying speed from the player abilities when creative flying
+fn flying_speed(sprinting: bool) -> f32 {
+    if sprinting {
+        0.02 + 0.006
+    } else {
         0.02
     }
 }
@@ -284,18 +797,12 @@ fn jump_power(world: &Instance, position: &Position) -> f32 {
     0.42 * block_jump_factor(world, position)
 }
 
-fn jump_boost_power() -> f64 {
-    // TODO: potion effects
-    // if let Some(effects) = entity.effects() {
-    //     if let Some(jump_effect) = effects.get(&Effect::Jump) {
-    //         0.1 * (jump_effect.amplifier + 1) as f32
-    //     } else {
-    //         0.
-    //     }
-    // } else {
-    //     0.
-    // }
-    0.
+fn jump_boost_power(active_effects: &ActiveEffects) -> f64 {
+    if let Some(amplifier) = active_effects.amplifier(MobEffect::JumpBoost) {
+        (0.1 * (amplifier as f32 + 1.)) as f64
+    } else {
+        0.
+    }
 }
 
 #[cfg(test)]
@@ -305,7 +812,7 @@ mod tests {
     use super::*;
     use azalea_core::{ChunkPos, ResourceLocation};
     use azalea_world::{
-        entity::{EntityBundle, EntityPlugin, MinecraftEntityId},
+        entity::{metadata::Boat, EntityBundle, EntityPlugin, MinecraftEntityId},
         Chunk, PartialInstance,
     };
     use bevy_app::App;
@@ -374,6 +881,85 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_slow_falling() {
+        let mut app = make_test_app();
+        let _world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+
+        let mut entity_bundle = EntityBundle::new(
+            Uuid::nil(),
+            Vec3 {
+                x: 0.,
+                y: 70.,
+                z: 0.,
+            },
+            azalea_registry::EntityKind::Zombie,
+            ResourceLocation::new("minecraft:overworld"),
+        );
+        entity_bundle.active_effects.insert(
+            MobEffect::SlowFalling,
+            azalea_world::entity::MobEffectInstance::from_flags(0, 200, 0),
+        );
+        let entity = app
+            .world
+            .spawn((entity_bundle, MinecraftEntityId(0), Local))
+            .id();
+        app.world.run_schedule(CoreSchedule::FixedUpdate);
+        app.update();
+        {
+            let entity_physics = app.world.get::<Physics>(entity).unwrap().clone();
+            // slow falling makes the gravity 0.01 instead of 0.08
+            assert_eq!(entity_physics.delta.y, -0.01 * 0.98);
+        }
+    }
+
+    #[test]
+    fn test_world_border_collision() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        world_lock.write().world_border.set_size(10.);
+
+        let entity = app
+            .world
+            .spawn((
+                EntityBundle::new(
+                    Uuid::nil(),
+                    Vec3 {
+                        x: 4.,
+                        y: 70.,
+                        z: 0.,
+                    },
+                    azalea_registry::EntityKind::Player,
+                    ResourceLocation::new("minecraft:overworld"),
+                ),
+                MinecraftEntityId(0),
+                Local,
+            ))
+            .id();
+        app.world.get_mut::<Physics>(entity).unwrap().delta = Vec3 {
+            x: 2.,
+            y: 0.,
+            z: 0.,
+        };
+        app.world.run_schedule(CoreSchedule::FixedUpdate);
+        app.update();
+        let entity_pos = *app.world.get::<Position>(entity).unwrap();
+        // the border is at x=5 and the player is 0.6 blocks wide
+        assert!(
+            (entity_pos.x - 4.7).abs() < 1e-6,
+            "Entity x ({}) should've been stopped by the world border",
+            entity_pos.x
+        );
+    }
     #[test]
     fn test_collision() {
         let mut app = make_test_app();
@@ -659,4 +1245,423 @@ mod tests {
         let entity_pos = app.world.get::<Position>(entity).unwrap();
         assert_eq!(entity_pos.y, 70.5);
     }
+
+    #[test]
+    fn test_sink_slowly_in_water() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        let mut partial_world = PartialInstance::default();
+
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        for y in 68..=72 {
+            partial_world.chunks.set_block_state(
+                &BlockPos { x: 0, y, z: 0 },
+                azalea_registry::Block::Water.into(),
+                &mut world_lock.write().chunks,
+            );
+        }
+        let entity = app
+            .world
+            .spawn((
+                EntityBundle::new(
+                    Uuid::nil(),
+                    Vec3 {
+                        x: 0.5,
+                        y: 70.,
+                        z: 0.5,
+                    },
+                    azalea_registry::EntityKind::Player,
+                    ResourceLocation::new("minecraft:overworld"),
+                ),
+                MinecraftEntityId(0),
+                Local,
+            ))
+            .id();
+
+        app.world.run_schedule(CoreSchedule::FixedUpdate);
+        app.update();
+        {
+            let entity_physics = app.world.get::<Physics>(entity).unwrap();
+            assert!(entity_physics.is_in_water);
+            assert!(!entity_physics.is_in_lava);
+            // water replaces normal gravity with a much slower sinking speed
+            assert_eq!(entity_physics.delta.y, -0.005);
+        }
+        for _ in 0..20 {
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+        }
+        let entity_pos = app.world.get::<Position>(entity).unwrap();
+        assert!(
+            entity_pos.y > 69.,
+            "Entity y ({}) sank too fast in water",
+            entity_pos.y
+        );
+    }
+
+    #[test]
+    fn test_sneaking_doesnt_fall_off_edge() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        let mut partial_world = PartialInstance::default();
+
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        partial_world.chunks.set_block_state(
+            &BlockPos { x: 0, y: 69, z: 0 },
+            azalea_registry::Block::Stone.into(),
+            &mut world_lock.write().chunks,
+        );
+        let mut entity_bundle = EntityBundle::new(
+            Uuid::nil(),
+            Vec3 {
+                x: 0.5,
+                y: 70.,
+                z: 0.5,
+            },
+            azalea_registry::EntityKind::Player,
+            ResourceLocation::new("minecraft:overworld"),
+        );
+        *entity_bundle.sneaking = true;
+        let entity = app
+            .world
+            .spawn((entity_bundle, MinecraftEntityId(0), Local))
+            .id();
+
+        for _ in 0..40 {
+            // walk towards positive z
+            app.world.get_mut::<Physics>(entity).unwrap().zza = 1.;
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+        }
+        let entity_pos = *app.world.get::<Position>(entity).unwrap();
+        assert_eq!(entity_pos.y, 70.);
+        // the edge of the player's bounding box should still be over the block
+        assert!(
+            entity_pos.z > 1. && entity_pos.z < 1.3,
+            "Entity z ({}) should be at the edge of the block",
+            entity_pos.z
+        );
+    }
+
+    #[test]
+    fn test_climb_ladder() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        let mut partial_world = PartialInstance::default();
+
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        partial_world.chunks.set_block_state(
+            &BlockPos { x: 0, y: 69, z: 0 },
+            azalea_registry::Block::Stone.into(),
+            &mut world_lock.write().chunks,
+        );
+        for y in 70..80 {
+            partial_world.chunks.set_block_state(
+                &BlockPos { x: 0, y, z: 0 },
+                azalea_registry::Block::Ladder.into(),
+                &mut world_lock.write().chunks,
+            );
+        }
+        let mut entity_bundle = EntityBundle::new(
+            Uuid::nil(),
+            Vec3 {
+                x: 0.5,
+                y: 70.,
+                z: 0.5,
+            },
+            azalea_registry::EntityKind::Player,
+            ResourceLocation::new("minecraft:overworld"),
+        );
+        *entity_bundle.jumping = true;
+        let entity = app
+            .world
+            .spawn((entity_bundle, MinecraftEntityId(0), Local))
+            .id();
+
+        for _ in 0..20 {
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+        }
+        let entity_pos = *app.world.get::<Position>(entity).unwrap();
+        assert!(
+            entity_pos.y > 72.,
+            "Entity y ({}) should've climbed up the ladder",
+            entity_pos.y
+        );
+    }
+
+    #[test]
+    fn test_jump_once() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        let mut partial_world = PartialInstance::default();
+
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        partial_world.chunks.set_block_state(
+            &BlockPos { x: 0, y: 69, z: 0 },
+            azalea_registry::Block::Stone.into(),
+            &mut world_lock.write().chunks,
+        );
+        let mut entity_bundle = EntityBundle::new(
+            Uuid::nil(),
+            Vec3 {
+                x: 0.5,
+                y: 70.,
+                z: 0.5,
+            },
+            azalea_registry::EntityKind::Player,
+            ResourceLocation::new("minecraft:overworld"),
+        );
+        entity_bundle.physics.on_ground = true;
+        *entity_bundle.jumping = true;
+        let entity = app
+            .world
+            .spawn((entity_bundle, MinecraftEntityId(0), Local))
+            .id();
+
+        app.world.run_schedule(CoreSchedule::FixedUpdate);
+        app.update();
+        let first_y = app.world.get::<Position>(entity).unwrap().y;
+        // we jump and start moving up on the same tick
+        assert!(first_y > 70.4, "Entity y ({first_y}) should've jumped");
+
+        let mut max_y = first_y;
+        for _ in 0..10 {
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+            max_y = max_y.max(app.world.get::<Position>(entity).unwrap().y);
+        }
+        // a single jump goes about 1.25 blocks high, jumping again before we
+        // leave the ground would take us higher
+        assert!(
+            max_y < 71.3,
+            "Entity y ({max_y}) went too high, so it probably jumped twice"
+        );
+        assert!(max_y > 71.2, "Entity y ({max_y}) didn't go high enough");
+    }
+
+    #[test]
+    fn test_elytra_glide_and_land() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        let mut partial_world = PartialInstance::default();
+
+        for chunk_z in 0..4 {
+            partial_world.chunks.set(
+                &ChunkPos { x: 0, z: chunk_z },
+                Some(Chunk::default()),
+                &mut world_lock.write().chunks,
+            );
+            for z in chunk_z * 16..chunk_z * 16 + 16 {
+                partial_world.chunks.set_block_state(
+                    &BlockPos { x: 0, y: 64, z },
+                    azalea_registry::Block::Stone.into(),
+                    &mut world_lock.write().chunks,
+                );
+            }
+        }
+        let entity = app
+            .world
+            .spawn((
+                EntityBundle::new(
+                    Uuid::nil(),
+                    Vec3 {
+                        x: 0.5,
+                        y: 70.,
+                        z: 0.5,
+                    },
+                    azalea_registry::EntityKind::Player,
+                    ResourceLocation::new("minecraft:overworld"),
+                ),
+                MinecraftEntityId(0),
+                Local,
+                FallFlying(true),
+                FireworkBoosts::default(),
+            ))
+            .id();
+
+        // we're looking straight ahead (south), so we should glide forward while
+        // falling slowly
+        for _ in 0..10 {
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+        }
+        let entity_pos = *app.world.get::<Position>(entity).unwrap();
+        assert!(
+            entity_pos.z > 0.5,
+            "Entity z ({}) should've moved forward",
+            entity_pos.z
+        );
+        // falling normally for 10 ticks would've taken us below y=67
+        assert!(
+            entity_pos.y > 69.,
+            "Entity y ({}) should've fallen slower than usual",
+            entity_pos.y
+        );
+        assert!(**app.world.get::<FallFlying>(entity).unwrap());
+
+        for _ in 0..100 {
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+        }
+        let entity_pos = *app.world.get::<Position>(entity).unwrap();
+        assert_eq!(entity_pos.y, 65.);
+        assert!(
+            !**app.world.get::<FallFlying>(entity).unwrap(),
+            "Entity should've stopped fall flying after landing"
+        );
+    }
+
+    #[test]
+    fn test_land_on_boat() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        let mut partial_world = PartialInstance::default();
+
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        app.world.spawn((
+            EntityBundle::new(
+                Uuid::from_u128(1),
+                Vec3 {
+                    x: 0.5,
+                    y: 70.,
+                    z: 0.5,
+                },
+                azalea_registry::EntityKind::Boat,
+                ResourceLocation::new("minecraft:overworld"),
+            ),
+            MinecraftEntityId(1),
+            Boat,
+        ));
+        let entity = app
+            .world
+            .spawn((
+                EntityBundle::new(
+                    Uuid::nil(),
+                    Vec3 {
+                        x: 0.5,
+                        y: 72.,
+                        z: 0.5,
+                    },
+                    azalea_registry::EntityKind::Player,
+                    ResourceLocation::new("minecraft:overworld"),
+                ),
+                MinecraftEntityId(0),
+                Local,
+            ))
+            .id();
+
+        for _ in 0..20 {
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+        }
+        let entity_pos = *app.world.get::<Position>(entity).unwrap();
+        // the boat is 0.5625 blocks tall
+        assert_eq!(entity_pos.y, 70.5625);
+        assert!(app.world.get::<Physics>(entity).unwrap().on_ground);
+    }
+
+    #[test]
+    fn test_players_push_each_other() {
+        let mut app = make_test_app();
+        let world_lock = app.world.resource_mut::<InstanceContainer>().insert(
+            ResourceLocation::new("minecraft:overworld"),
+            384,
+            -64,
+        );
+        let mut partial_world = PartialInstance::default();
+
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        for x in 0..16 {
+            partial_world.chunks.set_block_state(
+                &BlockPos { x, y: 69, z: 0 },
+                azalea_registry::Block::Stone.into(),
+                &mut world_lock.write().chunks,
+            );
+        }
+        let mut spawn_player = |id: u32, x: f64| {
+            app.world
+                .spawn((
+                    EntityBundle::new(
+                        Uuid::from_u128(id as u128),
+                        Vec3 { x, y: 70., z: 0.5 },
+                        azalea_registry::EntityKind::Player,
+                        ResourceLocation::new("minecraft:overworld"),
+                    ),
+                    MinecraftEntityId(id),
+                    Local,
+                    AbstractLiving,
+                ))
+                .id()
+        };
+        let left = spawn_player(0, 7.4);
+        let right = spawn_player(1, 7.6);
+
+        for _ in 0..20 {
+            app.world.run_schedule(CoreSchedule::FixedUpdate);
+            app.update();
+        }
+        let left_pos = *app.world.get::<Position>(left).unwrap();
+        let right_pos = *app.world.get::<Position>(right).unwrap();
+        assert!(
+            left_pos.x < 7.4,
+            "Left entity x ({}) should've been pushed left",
+            left_pos.x
+        );
+        assert!(
+            right_pos.x > 7.6,
+            "Right entity x ({}) should've been pushed right",
+            right_pos.x
+        );
+        // once they stop overlapping they shouldn't get pushed anymore
+        assert!(right_pos.x - left_pos.x >= 0.6);
+    }
 }
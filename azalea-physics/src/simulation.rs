@@ -0,0 +1,427 @@
+//! Run physics ahead of time without touching the real ECS world, so you can
+//! figure out where an entity will be after doing something for a few ticks.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+};
+
+use azalea_core::{ChunkPos, ResourceLocation, Vec3};
+use azalea_world::{
+    entity::{
+        attributes::sprinting_modifier, metadata::Sprinting, set_rotation, update_bounding_box,
+        Attributes, EntityBundle, Jumping, Local, Physics, Position, Sneaking,
+    },
+    Chunk, ChunkStorage, Instance, InstanceContainer,
+};
+use bevy_app::{App, CoreSchedule};
+use bevy_ecs::entity::Entity;
+use parking_lot::RwLock;
+use uuid::Uuid;
+
+use crate::PhysicsPlugin;
+
+/// What the simulated entity is doing during a tick. These are the same
+/// inputs that a client controls.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SimulationInput {
+    /// How much we're trying to walk forward, where 1 is forward and -1 is
+    /// backwards. This is the same as the `forward_impulse` in the client's
+    /// `PhysicsState`.
+    pub forward_impulse: f32,
+    /// How much we're trying to walk sideways, from -1 to 1. This is the same
+    /// as the `left_impulse` in the client's `PhysicsState`.
+    pub left_impulse: f32,
+    pub jumping: bool,
+    /// Sneaking makes us walk at 30% of the speed and stops us from walking
+    /// off the edges of blocks.
+    pub sneaking: bool,
+    pub sprinting: bool,
+    /// The `y_rot` and `x_rot` to look in before moving, or `None` to keep
+    /// looking in the same direction.
+    pub rotation: Option<(f32, f32)>,
+}
+
+/// The state of the simulated entity at the end of a tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimulatedTick {
+    pub position: Vec3,
+    pub delta: Vec3,
+    pub on_ground: bool,
+    pub horizontal_collision: bool,
+}
+
+/// A copy of an entity that we can run physics on without affecting the real
+/// entity.
+///
+/// The simulation has its own ECS world with a snapshot of the [`Instance`].
+/// The chunks in the snapshot are shared with the real instance rather than
+/// copied, so block updates that happen while the simulation exists will be
+/// visible to it, but chunks won't get unloaded from under it.
+///
+/// ```
+/// # use azalea_physics::simulation::{Simulation, SimulationInput};
+/// # use azalea_world::entity::{Attributes, Physics, Position};
+/// # fn example(world: &azalea_world::Instance, physics: &Physics, position: &Position, attributes: &Attributes) {
+/// let mut simulation = Simulation::new(world, physics, position, attributes);
+/// // where will we be after jumping forward for 10 ticks?
+/// let trajectory = simulation.run(
+///     [SimulationInput {
+///         forward_impulse: 1.,
+///         jumping: true,
+///         ..Default::default()
+///     }; 10],
+/// );
+/// let final_position = trajectory.last().unwrap().position;
+/// # }
+/// ```
+pub struct Simulation {
+    app: App,
+    entity: Entity,
+    // we have to keep strong references to these so they don't get dropped,
+    // since the container only has weak references
+    world: Arc<RwLock<Instance>>,
+    _chunks: Vec<Arc<RwLock<Chunk>>>,
+}
+
+impl Simulation {
+    /// Create a simulation for an entity that's in the given world.
+    ///
+    /// Only the physics, position and attributes of the entity are copied, so
+    /// things like status effects won't affect the simulation.
+    // TODO: boats and shulkers aren't solid in the simulation since we don't
+    // copy any other entities
+    pub fn new(
+        world: &Instance,
+        physics: &Physics,
+        position: &Position,
+        attributes: &Attributes,
+    ) -> Self {
+        let world_name = ResourceLocation::new("azalea:simulation");
+
+        let chunks = world
+            .chunks
+            .chunks
+            .values()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>();
+        let snapshot = Arc::new(RwLock::new(Instance {
+            chunks: ChunkStorage {
+                height: world.chunks.height,
+                min_y: world.chunks.min_y,
+                chunks: world.chunks.chunks.clone(),
+            },
+            entities_by_chunk: Default::default(),
+            entity_by_id: Default::default(),
+            world_border: world.world_border.clone(),
+            biome_registry: world.biome_registry.clone(),
+        }));
+
+        let mut instance_container = InstanceContainer::new();
+        instance_container
+            .worlds
+            .insert(world_name.clone(), Arc::downgrade(&snapshot));
+
+        let mut app = App::new();
+        app.add_plugin(PhysicsPlugin)
+            .add_system(update_bounding_box)
+            .insert_resource(instance_container);
+
+        let mut entity_bundle = EntityBundle::new(
+            Uuid::nil(),
+            **position,
+            azalea_registry::EntityKind::Player,
+            world_name,
+        );
+        entity_bundle.physics = physics.clone();
+        entity_bundle.attributes = attributes.clone();
+        let entity = app
+            .world
+            .spawn((entity_bundle, Sprinting(false), Local))
+            .id();
+
+        Self {
+            app,
+            entity,
+            world: snapshot,
+            _chunks: chunks,
+        }
+    }
+
+    /// Put the simulated entity in a new state so the simulation can be used
+    /// again, which is a lot faster than making a new one.
+    ///
+    /// This also updates the snapshot of the world, but it only keeps the
+    /// chunks that are at most `chunk_radius` chunks away from the position,
+    /// so the entity shouldn't be simulated for long enough to leave them.
+    pub fn reset(
+        &mut self,
+        world: &Instance,
+        physics: &Physics,
+        position: &Position,
+        attributes: &Attributes,
+        chunk_radius: i32,
+    ) {
+        let center = ChunkPos::from(**position);
+        let mut chunks = Vec::new();
+        let mut weak_chunks = HashMap::new();
+        for x in -chunk_radius..=chunk_radius {
+            for z in -chunk_radius..=chunk_radius {
+                let chunk_pos = ChunkPos::new(center.x + x, center.z + z);
+                if let Some(chunk) = world.chunks.get(&chunk_pos) {
+                    weak_chunks.insert(chunk_pos, Arc::downgrade(&chunk));
+                    chunks.push(chunk);
+                }
+            }
+        }
+        {
+            let mut snapshot = self.world.write();
+            snapshot.chunks = ChunkStorage {
+                height: world.chunks.height,
+                min_y: world.chunks.min_y,
+                chunks: weak_chunks,
+            };
+            snapshot.world_border = world.world_border.clone();
+        }
+        self._chunks = chunks;
+
+        let mut entity_mut = self.app.world.entity_mut(self.entity);
+        *entity_mut.get_mut::<Physics>().unwrap() = physics.clone();
+        *entity_mut.get_mut::<Position>().unwrap() = *position;
+        *entity_mut.get_mut::<Attributes>().unwrap() = attributes.clone();
+        **entity_mut.get_mut::<Jumping>().unwrap() = false;
+        **entity_mut.get_mut::<Sneaking>().unwrap() = false;
+        **entity_mut.get_mut::<Sprinting>().unwrap() = false;
+    }
+
+    /// Run the physics for one tick with the given input.
+    pub fn tick(&mut self, input: &SimulationInput) -> SimulatedTick {
+        let mut entity_mut = self.app.world.entity_mut(self.entity);
+
+        **entity_mut.get_mut::<Jumping>().unwrap() = input.jumping;
+        **entity_mut.get_mut::<Sneaking>().unwrap() = input.sneaking;
+        **entity_mut.get_mut::<Sprinting>().unwrap() = input.sprinting;
+        {
+            let mut attributes = entity_mut.get_mut::<Attributes>().unwrap();
+            attributes.speed.remove(&sprinting_modifier().uuid);
+            if input.sprinting {
+                attributes
+                    .speed
+                    .insert(sprinting_modifier())
+                    .expect("we just removed the modifier");
+            }
+        }
+
+        let mut physics = entity_mut.get_mut::<Physics>().unwrap();
+        if let Some((y_rot, x_rot)) = input.rotation {
+            set_rotation(&mut physics, y_rot, x_rot);
+        }
+        // this is the same thing as the client's local_player_ai_step
+        let multiplier = if input.sneaking { 0.3 } else { 1. };
+        physics.xxa = input.left_impulse * multiplier;
+        physics.zza = input.forward_impulse * multiplier;
+
+        self.app.world.run_schedule(CoreSchedule::FixedUpdate);
+        // this runs the systems that aren't in the fixed schedule, like the one
+        // for updating the bounding box
+        self.app.update();
+
+        let physics = self.physics();
+        SimulatedTick {
+            position: self.position(),
+            delta: physics.delta,
+            on_ground: physics.on_ground,
+            horizontal_collision: physics.horizontal_collision,
+        }
+    }
+
+    /// Run the physics for every input in order, and return where the entity
+    /// was at the end of each tick.
+    pub fn run(&mut self, inputs: impl IntoIterator<Item = SimulationInput>) -> Vec<SimulatedTick> {
+        inputs.into_iter().map(|input| self.tick(&input)).collect()
+    }
+
+    /// The current position of the simulated entity.
+    pub fn position(&self) -> Vec3 {
+        **self.app.world.get::<Position>(self.entity).unwrap()
+    }
+
+    /// The current physics state of the simulated entity.
+    pub fn physics(&self) -> &Physics {
+        self.app.world.get::<Physics>(self.entity).unwrap()
+    }
+}
+
+/// Simulate an entity doing the given inputs, and return where it was at the
+/// end of each tick. This is a shortcut for [`Simulation::new`] and
+/// [`Simulation::run`].
+pub fn simulate(
+    world: &Instance,
+    physics: &Physics,
+    position: &Position,
+    attributes: &Attributes,
+    inputs: impl IntoIterator<Item = SimulationInput>,
+) -> Vec<SimulatedTick> {
+    Simulation::new(world, physics, position, attributes).run(inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_core::{BlockPos, ChunkPos};
+    use azalea_world::PartialInstance;
+
+    fn make_world() -> (Arc<RwLock<Instance>>, PartialInstance) {
+        let mut instance_container = InstanceContainer::new();
+        let world_lock =
+            instance_container.insert(ResourceLocation::new("minecraft:overworld"), 384, -64);
+        let mut partial_world = PartialInstance::default();
+        partial_world.chunks.set(
+            &ChunkPos { x: 0, z: 0 },
+            Some(Chunk::default()),
+            &mut world_lock.write().chunks,
+        );
+        for x in 0..16 {
+            for z in 0..16 {
+                partial_world.chunks.set_block_state(
+                    &BlockPos { x, y: 69, z },
+                    azalea_registry::Block::Stone.into(),
+                    &mut world_lock.write().chunks,
+                );
+            }
+        }
+        (world_lock, partial_world)
+    }
+
+    fn make_player(position: Vec3) -> EntityBundle {
+        let mut entity_bundle = EntityBundle::new(
+            Uuid::nil(),
+            position,
+            azalea_registry::EntityKind::Player,
+            ResourceLocation::new("minecraft:overworld"),
+        );
+        entity_bundle.physics.on_ground = true;
+        entity_bundle
+    }
+
+    #[test]
+    fn test_simulate_jump() {
+        let (world_lock, _partial_world) = make_world();
+        let player = make_player(Vec3::new(8.5, 70., 8.5));
+
+        let trajectory = simulate(
+            &world_lock.read(),
+            &player.physics,
+            &player.position,
+            &player.attributes,
+            [SimulationInput {
+                jumping: true,
+                ..Default::default()
+            }]
+            .into_iter()
+            .chain([SimulationInput::default(); 19]),
+        );
+        let highest_y = trajectory
+            .iter()
+            .map(|tick| tick.position.y)
+            .fold(f64::MIN, f64::max);
+        assert!(
+            (71.2..71.3).contains(&highest_y),
+            "Jumping should go up about 1.25 blocks, but we got to y={highest_y}"
+        );
+        assert!(trajectory.last().unwrap().on_ground);
+    }
+
+    #[test]
+    fn test_simulate_walk_into_wall() {
+        let (world_lock, partial_world) = make_world();
+        partial_world.chunks.set_block_state(
+            &BlockPos { x: 8, y: 70, z: 10 },
+            azalea_registry::Block::Stone.into(),
+            &mut world_lock.write().chunks,
+        );
+        let player = make_player(Vec3::new(8.5, 70., 8.5));
+
+        // y_rot 0 is facing south (positive z)
+        let trajectory = simulate(
+            &world_lock.read(),
+            &player.physics,
+            &player.position,
+            &player.attributes,
+            [SimulationInput {
+                forward_impulse: 1.,
+                rotation: Some((0., 0.)),
+                ..Default::default()
+            }; 20],
+        );
+        let last_tick = trajectory.last().unwrap();
+        assert!((last_tick.position.z - 9.7).abs() < 1e-6);
+        assert!(last_tick.horizontal_collision);
+    }
+
+    #[test]
+    fn test_simulation_doesnt_affect_world() {
+        let (world_lock, _partial_world) = make_world();
+        let player = make_player(Vec3::new(8.5, 70., 8.5));
+
+        let mut simulation = Simulation::new(
+            &world_lock.read(),
+            &player.physics,
+            &player.position,
+            &player.attributes,
+        );
+        simulation.run(
+            [SimulationInput {
+                forward_impulse: 1.,
+                ..Default::default()
+            }; 5],
+        );
+        assert_ne!(simulation.position(), *player.position);
+        assert!(world_lock.read().entity_by_id.is_empty());
+        assert!(world_lock.read().entities_by_chunk.is_empty());
+    }
+
+    #[test]
+    fn test_reset_simulation() {
+        let (world_lock, partial_world) = make_world();
+        let player = make_player(Vec3::new(8.5, 70., 8.5));
+        let inputs = [SimulationInput {
+            forward_impulse: 1.,
+            rotation: Some((0., 0.)),
+            ..Default::default()
+        }; 20];
+
+        let mut simulation = Simulation::new(
+            &world_lock.read(),
+            &player.physics,
+            &player.position,
+            &player.attributes,
+        );
+        let first_run = simulation.run(inputs);
+
+        simulation.reset(
+            &world_lock.read(),
+            &player.physics,
+            &player.position,
+            &player.attributes,
+            1,
+        );
+        assert_eq!(simulation.position(), *player.position);
+        assert_eq!(simulation.run(inputs), first_run);
+
+        // blocks that were placed since the simulation was made are there too
+        partial_world.chunks.set_block_state(
+            &BlockPos { x: 8, y: 70, z: 10 },
+            azalea_registry::Block::Stone.into(),
+            &mut world_lock.write().chunks,
+        );
+        simulation.reset(
+            &world_lock.read(),
+            &player.physics,
+            &player.position,
+            &player.attributes,
+            1,
+        );
+        assert!(simulation.run(inputs).last().unwrap().horizontal_collision);
+    }
+}
@@ -1,5 +1,5 @@
 use azalea_buf::McBuf;
-use azalea_core::{ResourceLocation, Vec3};
+use azalea_core::{rotation_from_byte, ResourceLocation, Vec3};
 use azalea_protocol_macros::ClientboundGamePacket;
 use azalea_world::entity::{metadata::apply_default_metadata, EntityBundle};
 use uuid::Uuid;
@@ -42,7 +42,16 @@ impl ClientboundAddEntityPacket {
     /// must apply the metadata after inserting the bundle with
     /// [`Self::apply_metadata`].
     pub fn as_entity_bundle(&self, world_name: ResourceLocation) -> EntityBundle {
-        EntityBundle::new(self.uuid, self.position, self.entity_type, world_name)
+        let mut bundle = EntityBundle::new(self.uuid, self.position, self.entity_type, world_name);
+        bundle.physics.x_rot = rotation_from_byte(self.x_rot);
+        bundle.physics.y_rot = rotation_from_byte(self.y_rot);
+        *bundle.head_rotation = rotation_from_byte(self.y_head_rot);
+        bundle.physics.delta = Vec3 {
+            x: self.x_vel as f64 / 8000.,
+            y: self.y_vel as f64 / 8000.,
+            z: self.z_vel as f64 / 8000.,
+        };
+        bundle
     }
 
     /// Apply the default metadata for the given entity.
@@ -1,5 +1,5 @@
 use azalea_buf::McBuf;
-use azalea_core::{ResourceLocation, Vec3};
+use azalea_core::{rotation_from_byte, ResourceLocation, Vec3};
 use azalea_protocol_macros::ClientboundGamePacket;
 use azalea_registry::EntityKind;
 use azalea_world::entity::{metadata::PlayerMetadataBundle, EntityBundle, PlayerBundle};
@@ -13,14 +13,19 @@ pub struct ClientboundAddPlayerPacket {
     pub id: u32,
     pub uuid: Uuid,
     pub position: Vec3,
-    pub x_rot: i8,
     pub y_rot: i8,
+    pub x_rot: i8,
 }
 
 impl ClientboundAddPlayerPacket {
     pub fn as_player_bundle(&self, world_name: ResourceLocation) -> PlayerBundle {
+        let mut entity =
+            EntityBundle::new(self.uuid, self.position, EntityKind::Player, world_name);
+        entity.physics.x_rot = rotation_from_byte(self.x_rot);
+        entity.physics.y_rot = rotation_from_byte(self.y_rot);
+        *entity.head_rotation = entity.physics.y_rot;
         PlayerBundle {
-            entity: EntityBundle::new(self.uuid, self.position, EntityKind::Player, world_name),
+            entity,
             metadata: PlayerMetadataBundle::default(),
         }
     }
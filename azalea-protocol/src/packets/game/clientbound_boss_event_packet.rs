@@ -76,19 +76,19 @@ impl McBufWritable for Operation {
 
 #[derive(Clone, Debug, McBuf)]
 pub struct AddOperation {
-    name: FormattedText,
-    progress: f32,
-    style: Style,
-    properties: Properties,
+    pub name: FormattedText,
+    pub progress: f32,
+    pub style: Style,
+    pub properties: Properties,
 }
 
 #[derive(Clone, Debug, McBuf)]
 pub struct Style {
-    color: BossBarColor,
-    overlay: BossBarOverlay,
+    pub color: BossBarColor,
+    pub overlay: BossBarOverlay,
 }
 
-#[derive(McBuf, Clone, Copy, Debug)]
+#[derive(McBuf, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BossBarColor {
     Pink = 0,
     Blue = 1,
@@ -99,7 +99,7 @@ pub enum BossBarColor {
     White = 6,
 }
 
-#[derive(McBuf, Clone, Copy, Debug)]
+#[derive(McBuf, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BossBarOverlay {
     Progress = 0,
     Notched6 = 1,
@@ -115,6 +115,42 @@ pub mod registry {
         pub world_type: Tag,
     }
 
+    impl RegistryRoot {
+        /// Get the ids and names of the biomes in the registry. The biomes in
+        /// chunk data are stored as these ids.
+        #[cfg(feature = "strict_registry")]
+        pub fn biome_names(&self) -> Vec<(u32, ResourceLocation)> {
+            self.world_type
+                .value
+                .iter()
+                .map(|biome| (biome.id, biome.name.clone()))
+                .collect()
+        }
+
+        /// Get the ids and names of the biomes in the registry. The biomes in
+        /// chunk data are stored as these ids.
+        #[cfg(not(feature = "strict_registry"))]
+        pub fn biome_names(&self) -> Vec<(u32, ResourceLocation)> {
+            let Some(values) = self
+                .world_type
+                .as_compound()
+                .and_then(|world_type| world_type.get("value"))
+                .and_then(|values| values.as_list())
+            else {
+                return Vec::new();
+            };
+            values
+                .iter()
+                .filter_map(|value| {
+                    let value = value.as_compound()?;
+                    let id = *value.get("id")?.as_int()?;
+                    let name = value.get("name")?.as_string()?;
+                    Some((id as u32, ResourceLocation::new(name)))
+                })
+                .collect()
+        }
+    }
+
     /// A collection of values for a certain type of registry data.
     #[derive(Debug, Clone, Serialize, Deserialize)]
     #[cfg_attr(feature = "strict_registry", serde(deny_unknown_fields))]
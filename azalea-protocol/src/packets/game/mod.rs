@@ -11,6 +11,7 @@ pub mod clientbound_block_update_packet;
 pub mod clientbound_boss_event_packet;
 pub mod clientbound_change_difficulty_packet;
 pub mod clientbound_chat_preview_packet;
+pub mod clientbound_clear_titles_packet;
 pub mod clientbound_command_suggestions_packet;
 pub mod clientbound_commands_packet;
 pub mod clientbound_container_close_packet;
@@ -228,6 +229,7 @@ declare_state_packets!(
         0x0a: clientbound_boss_event_packet::ClientboundBossEventPacket,
         0x0b: clientbound_change_difficulty_packet::ClientboundChangeDifficultyPacket,
         0x0c: clientbound_chat_preview_packet::ClientboundChatPreviewPacket,
+        0x0d: clientbound_clear_titles_packet::ClientboundClearTitlesPacket,
         0x0e: clientbound_command_suggestions_packet::ClientboundCommandSuggestionsPacket,
         0x0f: clientbound_commands_packet::ClientboundCommandsPacket,
         0x10: clientbound_container_close_packet::ClientboundContainerClosePacket,
@@ -20,6 +20,17 @@ pub struct BlockHitResult {
     pub inside: bool,
 }
 
+impl From<azalea_core::BlockHitResult> for BlockHitResult {
+    fn from(hit_result: azalea_core::BlockHitResult) -> Self {
+        Self {
+            block_pos: hit_result.block_pos,
+            direction: hit_result.direction,
+            location: hit_result.location,
+            inside: hit_result.inside,
+        }
+    }
+}
+
 impl McBufWritable for BlockHitResult {
     fn write_into(&self, buf: &mut impl Write) -> Result<(), std::io::Error> {
         self.block_pos.write_into(buf)?;
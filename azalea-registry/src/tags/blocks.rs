@@ -0,0 +1,826 @@
+//! Block tags from the vanilla data pack.
+//!
+//! This file isn't generated by the codegen, so it has to be kept up to date
+//! by hand. The contents of each tag are copied from
+//! `data/minecraft/tags/blocks/` in the server jar, with any tags they include
+//! flattened into them.
+
+use std::collections::HashSet;
+
+use once_cell::sync::Lazy;
+
+use crate::Block;
+
+/// Blocks that are mined faster with an axe.
+pub static MINEABLE_AXE: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::OakPlanks,
+        Block::SprucePlanks,
+        Block::BirchPlanks,
+        Block::JunglePlanks,
+        Block::AcaciaPlanks,
+        Block::DarkOakPlanks,
+        Block::MangrovePlanks,
+        Block::OakLog,
+        Block::SpruceLog,
+        Block::BirchLog,
+        Block::JungleLog,
+        Block::AcaciaLog,
+        Block::DarkOakLog,
+        Block::MangroveLog,
+        Block::MangroveRoots,
+        Block::StrippedSpruceLog,
+        Block::StrippedBirchLog,
+        Block::StrippedJungleLog,
+        Block::StrippedAcaciaLog,
+        Block::StrippedDarkOakLog,
+        Block::StrippedOakLog,
+        Block::StrippedMangroveLog,
+        Block::OakWood,
+        Block::SpruceWood,
+        Block::BirchWood,
+        Block::JungleWood,
+        Block::AcaciaWood,
+        Block::DarkOakWood,
+        Block::MangroveWood,
+        Block::StrippedOakWood,
+        Block::StrippedSpruceWood,
+        Block::StrippedBirchWood,
+        Block::StrippedJungleWood,
+        Block::StrippedAcaciaWood,
+        Block::StrippedDarkOakWood,
+        Block::StrippedMangroveWood,
+        Block::NoteBlock,
+        Block::Bookshelf,
+        Block::OakStairs,
+        Block::Chest,
+        Block::CraftingTable,
+        Block::OakSign,
+        Block::SpruceSign,
+        Block::BirchSign,
+        Block::AcaciaSign,
+        Block::JungleSign,
+        Block::DarkOakSign,
+        Block::MangroveSign,
+        Block::OakDoor,
+        Block::Ladder,
+        Block::OakWallSign,
+        Block::SpruceWallSign,
+        Block::BirchWallSign,
+        Block::AcaciaWallSign,
+        Block::JungleWallSign,
+        Block::DarkOakWallSign,
+        Block::MangroveWallSign,
+        Block::OakPressurePlate,
+        Block::SprucePressurePlate,
+        Block::BirchPressurePlate,
+        Block::JunglePressurePlate,
+        Block::AcaciaPressurePlate,
+        Block::DarkOakPressurePlate,
+        Block::MangrovePressurePlate,
+        Block::Jukebox,
+        Block::OakFence,
+        Block::Pumpkin,
+        Block::CarvedPumpkin,
+        Block::JackOLantern,
+        Block::OakTrapdoor,
+        Block::SpruceTrapdoor,
+        Block::BirchTrapdoor,
+        Block::JungleTrapdoor,
+        Block::AcaciaTrapdoor,
+        Block::DarkOakTrapdoor,
+        Block::MangroveTrapdoor,
+        Block::BrownMushroomBlock,
+        Block::RedMushroomBlock,
+        Block::MushroomStem,
+        Block::Melon,
+        Block::Vine,
+        Block::GlowLichen,
+        Block::OakFenceGate,
+        Block::Cocoa,
+        Block::SpruceStairs,
+        Block::BirchStairs,
+        Block::JungleStairs,
+        Block::OakButton,
+        Block::SpruceButton,
+        Block::BirchButton,
+        Block::JungleButton,
+        Block::AcaciaButton,
+        Block::DarkOakButton,
+        Block::MangroveButton,
+        Block::TrappedChest,
+        Block::DaylightDetector,
+        Block::AcaciaStairs,
+        Block::DarkOakStairs,
+        Block::MangroveStairs,
+        Block::WhiteBanner,
+        Block::OrangeBanner,
+        Block::MagentaBanner,
+        Block::LightBlueBanner,
+        Block::YellowBanner,
+        Block::LimeBanner,
+        Block::PinkBanner,
+        Block::GrayBanner,
+        Block::LightGrayBanner,
+        Block::CyanBanner,
+        Block::PurpleBanner,
+        Block::BlueBanner,
+        Block::BrownBanner,
+        Block::GreenBanner,
+        Block::RedBanner,
+        Block::BlackBanner,
+        Block::WhiteWallBanner,
+        Block::OrangeWallBanner,
+        Block::MagentaWallBanner,
+        Block::LightBlueWallBanner,
+        Block::YellowWallBanner,
+        Block::LimeWallBanner,
+        Block::PinkWallBanner,
+        Block::GrayWallBanner,
+        Block::LightGrayWallBanner,
+        Block::CyanWallBanner,
+        Block::PurpleWallBanner,
+        Block::BlueWallBanner,
+        Block::BrownWallBanner,
+        Block::GreenWallBanner,
+        Block::RedWallBanner,
+        Block::BlackWallBanner,
+        Block::OakSlab,
+        Block::SpruceSlab,
+        Block::BirchSlab,
+        Block::JungleSlab,
+        Block::AcaciaSlab,
+        Block::DarkOakSlab,
+        Block::MangroveSlab,
+        Block::SpruceFenceGate,
+        Block::BirchFenceGate,
+        Block::JungleFenceGate,
+        Block::AcaciaFenceGate,
+        Block::DarkOakFenceGate,
+        Block::MangroveFenceGate,
+        Block::SpruceFence,
+        Block::BirchFence,
+        Block::JungleFence,
+        Block::AcaciaFence,
+        Block::DarkOakFence,
+        Block::MangroveFence,
+        Block::SpruceDoor,
+        Block::BirchDoor,
+        Block::JungleDoor,
+        Block::AcaciaDoor,
+        Block::DarkOakDoor,
+        Block::MangroveDoor,
+        Block::ChorusPlant,
+        Block::ChorusFlower,
+        Block::BambooSapling,
+        Block::Bamboo,
+        Block::Loom,
+        Block::Barrel,
+        Block::CartographyTable,
+        Block::FletchingTable,
+        Block::Lectern,
+        Block::SmithingTable,
+        Block::Campfire,
+        Block::SoulCampfire,
+        Block::WarpedStem,
+        Block::StrippedWarpedStem,
+        Block::WarpedHyphae,
+        Block::StrippedWarpedHyphae,
+        Block::CrimsonStem,
+        Block::StrippedCrimsonStem,
+        Block::CrimsonHyphae,
+        Block::StrippedCrimsonHyphae,
+        Block::CrimsonPlanks,
+        Block::WarpedPlanks,
+        Block::CrimsonSlab,
+        Block::WarpedSlab,
+        Block::CrimsonPressurePlate,
+        Block::WarpedPressurePlate,
+        Block::CrimsonFence,
+        Block::WarpedFence,
+        Block::CrimsonTrapdoor,
+        Block::WarpedTrapdoor,
+        Block::CrimsonFenceGate,
+        Block::WarpedFenceGate,
+        Block::CrimsonStairs,
+        Block::WarpedStairs,
+        Block::CrimsonButton,
+        Block::WarpedButton,
+        Block::CrimsonDoor,
+        Block::WarpedDoor,
+        Block::CrimsonSign,
+        Block::WarpedSign,
+        Block::CrimsonWallSign,
+        Block::WarpedWallSign,
+        Block::Composter,
+        Block::BeeNest,
+        Block::Beehive,
+        Block::BigDripleaf,
+        Block::BigDripleafStem,
+        Block::SmallDripleaf,
+    ])
+});
+
+/// Blocks that are mined faster with a hoe.
+pub static MINEABLE_HOE: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::OakLeaves,
+        Block::SpruceLeaves,
+        Block::BirchLeaves,
+        Block::JungleLeaves,
+        Block::AcaciaLeaves,
+        Block::DarkOakLeaves,
+        Block::MangroveLeaves,
+        Block::AzaleaLeaves,
+        Block::FloweringAzaleaLeaves,
+        Block::Sponge,
+        Block::WetSponge,
+        Block::HayBlock,
+        Block::NetherWartBlock,
+        Block::DriedKelpBlock,
+        Block::WarpedWartBlock,
+        Block::Shroomlight,
+        Block::Target,
+        Block::SculkSensor,
+        Block::Sculk,
+        Block::SculkVein,
+        Block::SculkCatalyst,
+        Block::SculkShrieker,
+        Block::MossCarpet,
+        Block::MossBlock,
+    ])
+});
+
+/// Blocks that are mined faster with a pickaxe.
+pub static MINEABLE_PICKAXE: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::Stone,
+        Block::Granite,
+        Block::PolishedGranite,
+        Block::Diorite,
+        Block::PolishedDiorite,
+        Block::Andesite,
+        Block::PolishedAndesite,
+        Block::Cobblestone,
+        Block::GoldOre,
+        Block::DeepslateGoldOre,
+        Block::IronOre,
+        Block::DeepslateIronOre,
+        Block::CoalOre,
+        Block::DeepslateCoalOre,
+        Block::NetherGoldOre,
+        Block::LapisOre,
+        Block::DeepslateLapisOre,
+        Block::LapisBlock,
+        Block::Dispenser,
+        Block::Sandstone,
+        Block::ChiseledSandstone,
+        Block::CutSandstone,
+        Block::PoweredRail,
+        Block::DetectorRail,
+        Block::StickyPiston,
+        Block::Piston,
+        Block::PistonHead,
+        Block::GoldBlock,
+        Block::IronBlock,
+        Block::Bricks,
+        Block::MossyCobblestone,
+        Block::Obsidian,
+        Block::Spawner,
+        Block::DiamondOre,
+        Block::DeepslateDiamondOre,
+        Block::DiamondBlock,
+        Block::Furnace,
+        Block::Rail,
+        Block::CobblestoneStairs,
+        Block::StonePressurePlate,
+        Block::IronDoor,
+        Block::RedstoneOre,
+        Block::DeepslateRedstoneOre,
+        Block::Ice,
+        Block::Netherrack,
+        Block::Basalt,
+        Block::PolishedBasalt,
+        Block::StoneBricks,
+        Block::MossyStoneBricks,
+        Block::CrackedStoneBricks,
+        Block::ChiseledStoneBricks,
+        Block::PackedMud,
+        Block::MudBricks,
+        Block::IronBars,
+        Block::Chain,
+        Block::BrickStairs,
+        Block::StoneBrickStairs,
+        Block::MudBrickStairs,
+        Block::NetherBricks,
+        Block::NetherBrickFence,
+        Block::NetherBrickStairs,
+        Block::EnchantingTable,
+        Block::BrewingStand,
+        Block::Cauldron,
+        Block::WaterCauldron,
+        Block::LavaCauldron,
+        Block::PowderSnowCauldron,
+        Block::EndStone,
+        Block::SandstoneStairs,
+        Block::EmeraldOre,
+        Block::DeepslateEmeraldOre,
+        Block::EnderChest,
+        Block::EmeraldBlock,
+        Block::CobblestoneWall,
+        Block::MossyCobblestoneWall,
+        Block::Anvil,
+        Block::ChippedAnvil,
+        Block::DamagedAnvil,
+        Block::LightWeightedPressurePlate,
+        Block::HeavyWeightedPressurePlate,
+        Block::RedstoneBlock,
+        Block::NetherQuartzOre,
+        Block::Hopper,
+        Block::QuartzBlock,
+        Block::ChiseledQuartzBlock,
+        Block::QuartzPillar,
+        Block::QuartzStairs,
+        Block::ActivatorRail,
+        Block::Dropper,
+        Block::WhiteTerracotta,
+        Block::OrangeTerracotta,
+        Block::MagentaTerracotta,
+        Block::LightBlueTerracotta,
+        Block::YellowTerracotta,
+        Block::LimeTerracotta,
+        Block::PinkTerracotta,
+        Block::GrayTerracotta,
+        Block::LightGrayTerracotta,
+        Block::CyanTerracotta,
+        Block::PurpleTerracotta,
+        Block::BlueTerracotta,
+        Block::BrownTerracotta,
+        Block::GreenTerracotta,
+        Block::RedTerracotta,
+        Block::BlackTerracotta,
+        Block::IronTrapdoor,
+        Block::Prismarine,
+        Block::PrismarineBricks,
+        Block::DarkPrismarine,
+        Block::PrismarineStairs,
+        Block::PrismarineBrickStairs,
+        Block::DarkPrismarineStairs,
+        Block::PrismarineSlab,
+        Block::PrismarineBrickSlab,
+        Block::DarkPrismarineSlab,
+        Block::Terracotta,
+        Block::CoalBlock,
+        Block::PackedIce,
+        Block::RedSandstone,
+        Block::ChiseledRedSandstone,
+        Block::CutRedSandstone,
+        Block::RedSandstoneStairs,
+        Block::StoneSlab,
+        Block::SmoothStoneSlab,
+        Block::SandstoneSlab,
+        Block::CutSandstoneSlab,
+        Block::PetrifiedOakSlab,
+        Block::CobblestoneSlab,
+        Block::BrickSlab,
+        Block::StoneBrickSlab,
+        Block::MudBrickSlab,
+        Block::NetherBrickSlab,
+        Block::QuartzSlab,
+        Block::RedSandstoneSlab,
+        Block::CutRedSandstoneSlab,
+        Block::PurpurSlab,
+        Block::SmoothStone,
+        Block::SmoothSandstone,
+        Block::SmoothQuartz,
+        Block::SmoothRedSandstone,
+        Block::PurpurBlock,
+        Block::PurpurPillar,
+        Block::PurpurStairs,
+        Block::EndStoneBricks,
+        Block::MagmaBlock,
+        Block::RedNetherBricks,
+        Block::BoneBlock,
+        Block::Observer,
+        Block::ShulkerBox,
+        Block::WhiteShulkerBox,
+        Block::OrangeShulkerBox,
+        Block::MagentaShulkerBox,
+        Block::LightBlueShulkerBox,
+        Block::YellowShulkerBox,
+        Block::LimeShulkerBox,
+        Block::PinkShulkerBox,
+        Block::GrayShulkerBox,
+        Block::LightGrayShulkerBox,
+        Block::CyanShulkerBox,
+        Block::PurpleShulkerBox,
+        Block::BlueShulkerBox,
+        Block::BrownShulkerBox,
+        Block::GreenShulkerBox,
+        Block::RedShulkerBox,
+        Block::BlackShulkerBox,
+        Block::WhiteGlazedTerracotta,
+        Block::OrangeGlazedTerracotta,
+        Block::MagentaGlazedTerracotta,
+        Block::LightBlueGlazedTerracotta,
+        Block::YellowGlazedTerracotta,
+        Block::LimeGlazedTerracotta,
+        Block::PinkGlazedTerracotta,
+        Block::GrayGlazedTerracotta,
+        Block::LightGrayGlazedTerracotta,
+        Block::CyanGlazedTerracotta,
+        Block::PurpleGlazedTerracotta,
+        Block::BlueGlazedTerracotta,
+        Block::BrownGlazedTerracotta,
+        Block::GreenGlazedTerracotta,
+        Block::RedGlazedTerracotta,
+        Block::BlackGlazedTerracotta,
+        Block::WhiteConcrete,
+        Block::OrangeConcrete,
+        Block::MagentaConcrete,
+        Block::LightBlueConcrete,
+        Block::YellowConcrete,
+        Block::LimeConcrete,
+        Block::PinkConcrete,
+        Block::GrayConcrete,
+        Block::LightGrayConcrete,
+        Block::CyanConcrete,
+        Block::PurpleConcrete,
+        Block::BlueConcrete,
+        Block::BrownConcrete,
+        Block::GreenConcrete,
+        Block::RedConcrete,
+        Block::BlackConcrete,
+        Block::DeadTubeCoralBlock,
+        Block::DeadBrainCoralBlock,
+        Block::DeadBubbleCoralBlock,
+        Block::DeadFireCoralBlock,
+        Block::DeadHornCoralBlock,
+        Block::TubeCoralBlock,
+        Block::BrainCoralBlock,
+        Block::BubbleCoralBlock,
+        Block::FireCoralBlock,
+        Block::HornCoralBlock,
+        Block::BlueIce,
+        Block::Conduit,
+        Block::PolishedGraniteStairs,
+        Block::SmoothRedSandstoneStairs,
+        Block::MossyStoneBrickStairs,
+        Block::PolishedDioriteStairs,
+        Block::MossyCobblestoneStairs,
+        Block::EndStoneBrickStairs,
+        Block::StoneStairs,
+        Block::SmoothSandstoneStairs,
+        Block::SmoothQuartzStairs,
+        Block::GraniteStairs,
+        Block::AndesiteStairs,
+        Block::RedNetherBrickStairs,
+        Block::PolishedAndesiteStairs,
+        Block::DioriteStairs,
+        Block::PolishedGraniteSlab,
+        Block::SmoothRedSandstoneSlab,
+        Block::MossyStoneBrickSlab,
+        Block::PolishedDioriteSlab,
+        Block::MossyCobblestoneSlab,
+        Block::EndStoneBrickSlab,
+        Block::SmoothSandstoneSlab,
+        Block::SmoothQuartzSlab,
+        Block::GraniteSlab,
+        Block::AndesiteSlab,
+        Block::RedNetherBrickSlab,
+        Block::PolishedAndesiteSlab,
+        Block::DioriteSlab,
+        Block::BrickWall,
+        Block::PrismarineWall,
+        Block::RedSandstoneWall,
+        Block::MossyStoneBrickWall,
+        Block::GraniteWall,
+        Block::StoneBrickWall,
+        Block::MudBrickWall,
+        Block::NetherBrickWall,
+        Block::AndesiteWall,
+        Block::RedNetherBrickWall,
+        Block::SandstoneWall,
+        Block::EndStoneBrickWall,
+        Block::DioriteWall,
+        Block::Smoker,
+        Block::BlastFurnace,
+        Block::Grindstone,
+        Block::Stonecutter,
+        Block::Bell,
+        Block::Lantern,
+        Block::SoulLantern,
+        Block::WarpedNylium,
+        Block::CrimsonNylium,
+        Block::NetheriteBlock,
+        Block::AncientDebris,
+        Block::CryingObsidian,
+        Block::RespawnAnchor,
+        Block::Lodestone,
+        Block::Blackstone,
+        Block::BlackstoneStairs,
+        Block::BlackstoneWall,
+        Block::BlackstoneSlab,
+        Block::PolishedBlackstone,
+        Block::PolishedBlackstoneBricks,
+        Block::CrackedPolishedBlackstoneBricks,
+        Block::ChiseledPolishedBlackstone,
+        Block::PolishedBlackstoneBrickSlab,
+        Block::PolishedBlackstoneBrickStairs,
+        Block::PolishedBlackstoneBrickWall,
+        Block::GildedBlackstone,
+        Block::PolishedBlackstoneStairs,
+        Block::PolishedBlackstoneSlab,
+        Block::PolishedBlackstonePressurePlate,
+        Block::PolishedBlackstoneWall,
+        Block::ChiseledNetherBricks,
+        Block::CrackedNetherBricks,
+        Block::QuartzBricks,
+        Block::AmethystBlock,
+        Block::BuddingAmethyst,
+        Block::AmethystCluster,
+        Block::LargeAmethystBud,
+        Block::MediumAmethystBud,
+        Block::SmallAmethystBud,
+        Block::Tuff,
+        Block::Calcite,
+        Block::OxidizedCopper,
+        Block::WeatheredCopper,
+        Block::ExposedCopper,
+        Block::CopperBlock,
+        Block::CopperOre,
+        Block::DeepslateCopperOre,
+        Block::OxidizedCutCopper,
+        Block::WeatheredCutCopper,
+        Block::ExposedCutCopper,
+        Block::CutCopper,
+        Block::OxidizedCutCopperStairs,
+        Block::WeatheredCutCopperStairs,
+        Block::ExposedCutCopperStairs,
+        Block::CutCopperStairs,
+        Block::OxidizedCutCopperSlab,
+        Block::WeatheredCutCopperSlab,
+        Block::ExposedCutCopperSlab,
+        Block::CutCopperSlab,
+        Block::WaxedCopperBlock,
+        Block::WaxedWeatheredCopper,
+        Block::WaxedExposedCopper,
+        Block::WaxedOxidizedCopper,
+        Block::WaxedOxidizedCutCopper,
+        Block::WaxedWeatheredCutCopper,
+        Block::WaxedExposedCutCopper,
+        Block::WaxedCutCopper,
+        Block::WaxedOxidizedCutCopperStairs,
+        Block::WaxedWeatheredCutCopperStairs,
+        Block::WaxedExposedCutCopperStairs,
+        Block::WaxedCutCopperStairs,
+        Block::WaxedOxidizedCutCopperSlab,
+        Block::WaxedWeatheredCutCopperSlab,
+        Block::WaxedExposedCutCopperSlab,
+        Block::WaxedCutCopperSlab,
+        Block::LightningRod,
+        Block::PointedDripstone,
+        Block::DripstoneBlock,
+        Block::Deepslate,
+        Block::CobbledDeepslate,
+        Block::CobbledDeepslateStairs,
+        Block::CobbledDeepslateSlab,
+        Block::CobbledDeepslateWall,
+        Block::PolishedDeepslate,
+        Block::PolishedDeepslateStairs,
+        Block::PolishedDeepslateSlab,
+        Block::PolishedDeepslateWall,
+        Block::DeepslateTiles,
+        Block::DeepslateTileStairs,
+        Block::DeepslateTileSlab,
+        Block::DeepslateTileWall,
+        Block::DeepslateBricks,
+        Block::DeepslateBrickStairs,
+        Block::DeepslateBrickSlab,
+        Block::DeepslateBrickWall,
+        Block::ChiseledDeepslate,
+        Block::CrackedDeepslateBricks,
+        Block::CrackedDeepslateTiles,
+        Block::SmoothBasalt,
+        Block::RawIronBlock,
+        Block::RawCopperBlock,
+        Block::RawGoldBlock,
+    ])
+});
+
+/// Blocks that are mined faster with a shovel.
+pub static MINEABLE_SHOVEL: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::GrassBlock,
+        Block::Dirt,
+        Block::CoarseDirt,
+        Block::Podzol,
+        Block::Sand,
+        Block::RedSand,
+        Block::Gravel,
+        Block::MuddyMangroveRoots,
+        Block::Farmland,
+        Block::Snow,
+        Block::SnowBlock,
+        Block::Clay,
+        Block::SoulSand,
+        Block::SoulSoil,
+        Block::Mycelium,
+        Block::DirtPath,
+        Block::WhiteConcretePowder,
+        Block::OrangeConcretePowder,
+        Block::MagentaConcretePowder,
+        Block::LightBlueConcretePowder,
+        Block::YellowConcretePowder,
+        Block::LimeConcretePowder,
+        Block::PinkConcretePowder,
+        Block::GrayConcretePowder,
+        Block::LightGrayConcretePowder,
+        Block::CyanConcretePowder,
+        Block::PurpleConcretePowder,
+        Block::BlueConcretePowder,
+        Block::BrownConcretePowder,
+        Block::GreenConcretePowder,
+        Block::RedConcretePowder,
+        Block::BlackConcretePowder,
+        Block::PowderSnow,
+        Block::RootedDirt,
+        Block::Mud,
+    ])
+});
+
+/// Blocks that need at least a diamond tool to drop anything.
+pub static NEEDS_DIAMOND_TOOL: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::Obsidian,
+        Block::NetheriteBlock,
+        Block::AncientDebris,
+        Block::CryingObsidian,
+        Block::RespawnAnchor,
+    ])
+});
+
+/// Blocks that need at least an iron tool to drop anything.
+pub static NEEDS_IRON_TOOL: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::GoldOre,
+        Block::DeepslateGoldOre,
+        Block::GoldBlock,
+        Block::DiamondOre,
+        Block::DeepslateDiamondOre,
+        Block::DiamondBlock,
+        Block::RedstoneOre,
+        Block::DeepslateRedstoneOre,
+        Block::EmeraldOre,
+        Block::DeepslateEmeraldOre,
+        Block::EmeraldBlock,
+        Block::RawGoldBlock,
+    ])
+});
+
+/// Blocks that need at least a stone tool to drop anything.
+pub static NEEDS_STONE_TOOL: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::IronOre,
+        Block::DeepslateIronOre,
+        Block::LapisOre,
+        Block::DeepslateLapisOre,
+        Block::LapisBlock,
+        Block::IronBlock,
+        Block::OxidizedCopper,
+        Block::WeatheredCopper,
+        Block::ExposedCopper,
+        Block::CopperBlock,
+        Block::CopperOre,
+        Block::DeepslateCopperOre,
+        Block::OxidizedCutCopper,
+        Block::WeatheredCutCopper,
+        Block::ExposedCutCopper,
+        Block::CutCopper,
+        Block::OxidizedCutCopperStairs,
+        Block::WeatheredCutCopperStairs,
+        Block::ExposedCutCopperStairs,
+        Block::CutCopperStairs,
+        Block::OxidizedCutCopperSlab,
+        Block::WeatheredCutCopperSlab,
+        Block::ExposedCutCopperSlab,
+        Block::CutCopperSlab,
+        Block::WaxedCopperBlock,
+        Block::WaxedWeatheredCopper,
+        Block::WaxedExposedCopper,
+        Block::WaxedOxidizedCopper,
+        Block::WaxedOxidizedCutCopper,
+        Block::WaxedWeatheredCutCopper,
+        Block::WaxedExposedCutCopper,
+        Block::WaxedCutCopper,
+        Block::WaxedOxidizedCutCopperStairs,
+        Block::WaxedWeatheredCutCopperStairs,
+        Block::WaxedExposedCutCopperStairs,
+        Block::WaxedCutCopperStairs,
+        Block::WaxedOxidizedCutCopperSlab,
+        Block::WaxedWeatheredCutCopperSlab,
+        Block::WaxedExposedCutCopperSlab,
+        Block::WaxedCutCopperSlab,
+        Block::LightningRod,
+        Block::RawIronBlock,
+        Block::RawCopperBlock,
+    ])
+});
+
+/// Every type of leaves.
+pub static LEAVES: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::OakLeaves,
+        Block::SpruceLeaves,
+        Block::BirchLeaves,
+        Block::JungleLeaves,
+        Block::AcaciaLeaves,
+        Block::DarkOakLeaves,
+        Block::MangroveLeaves,
+        Block::AzaleaLeaves,
+        Block::FloweringAzaleaLeaves,
+    ])
+});
+
+/// Every color of wool.
+pub static WOOL: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::WhiteWool,
+        Block::OrangeWool,
+        Block::MagentaWool,
+        Block::LightBlueWool,
+        Block::YellowWool,
+        Block::LimeWool,
+        Block::PinkWool,
+        Block::GrayWool,
+        Block::LightGrayWool,
+        Block::CyanWool,
+        Block::PurpleWool,
+        Block::BlueWool,
+        Block::BrownWool,
+        Block::GreenWool,
+        Block::RedWool,
+        Block::BlackWool,
+    ])
+});
+
+/// Blocks that entities can climb, like ladders and vines.
+pub static CLIMBABLE: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::Ladder,
+        Block::Vine,
+        Block::Scaffolding,
+        Block::WeepingVines,
+        Block::WeepingVinesPlant,
+        Block::TwistingVines,
+        Block::TwistingVinesPlant,
+        Block::CaveVines,
+        Block::CaveVinesPlant,
+    ])
+});
+
+/// Doors that players can open by hand, which is every door except iron ones.
+pub static WOODEN_DOORS: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::OakDoor,
+        Block::SpruceDoor,
+        Block::BirchDoor,
+        Block::JungleDoor,
+        Block::AcaciaDoor,
+        Block::DarkOakDoor,
+        Block::MangroveDoor,
+        Block::CrimsonDoor,
+        Block::WarpedDoor,
+    ])
+});
+
+/// Trapdoors that players can open by hand, which is every trapdoor except iron
+/// ones.
+pub static WOODEN_TRAPDOORS: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::OakTrapdoor,
+        Block::SpruceTrapdoor,
+        Block::BirchTrapdoor,
+        Block::JungleTrapdoor,
+        Block::AcaciaTrapdoor,
+        Block::DarkOakTrapdoor,
+        Block::MangroveTrapdoor,
+        Block::CrimsonTrapdoor,
+        Block::WarpedTrapdoor,
+    ])
+});
+
+/// Every type of fence gate.
+pub static FENCE_GATES: Lazy<HashSet<Block>> = Lazy::new(|| {
+    HashSet::from_iter(vec![
+        Block::OakFenceGate,
+        Block::SpruceFenceGate,
+        Block::BirchFenceGate,
+        Block::JungleFenceGate,
+        Block::AcaciaFenceGate,
+        Block::DarkOakFenceGate,
+        Block::MangroveFenceGate,
+        Block::CrimsonFenceGate,
+        Block::WarpedFenceGate,
+    ])
+});
@@ -0,0 +1,4 @@
+//! Tags are sets of registry entries that share some behavior, like which
+//! tool is used to mine a block.
+
+pub mod blocks;
@@ -0,0 +1,17 @@
+//! Blocks that store extra data, like chests, signs and spawners. See
+//! <https://minecraft.fandom.com/wiki/Block_entity>.
+
+use azalea_registry::BlockEntityKind;
+
+/// The data for a block entity that the server sent us.
+///
+/// The server only sends the data that the client needs to render the block,
+/// so for example the contents of a chest aren't included but the text on a
+/// sign and the mob in a spawner are.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockEntity {
+    pub kind: BlockEntityKind,
+    /// The NBT data of the block entity. This is usually a compound tag, but
+    /// its contents depend on the kind of block entity.
+    pub data: azalea_nbt::Tag,
+}
@@ -1,8 +1,10 @@
+use crate::block_entity::BlockEntity;
+use crate::light::{self, LightLayer, SectionLight};
 use crate::palette::PalettedContainer;
 use crate::palette::PalettedContainerType;
 use azalea_block::BlockState;
 use azalea_buf::{BufReadError, McBufReadable, McBufWritable};
-use azalea_core::{BlockPos, ChunkBlockPos, ChunkPos, ChunkSectionBlockPos};
+use azalea_core::{BitSet, BlockPos, ChunkBlockPos, ChunkPos, ChunkSectionBlockPos};
 use log::{debug, trace, warn};
 use parking_lot::RwLock;
 use std::{
@@ -22,8 +24,10 @@ pub struct PartialChunkStorage {
     pub view_center: ChunkPos,
     chunk_radius: u32,
     view_range: u32,
-    // chunks is a list of size chunk_radius * chunk_radius
-    chunks: Vec<Option<Arc<RwLock<Chunk>>>>,
+    // chunks is a list of size chunk_radius * chunk_radius. the position of
+    // each chunk is stored too since the chunk that's in a slot might be from
+    // before the view center moved.
+    chunks: Vec<Option<(ChunkPos, Arc<RwLock<Chunk>>)>>,
 }
 
 /// A storage for chunks where they're only stored weakly, so if they're not
@@ -36,13 +40,19 @@ pub struct ChunkStorage {
     pub chunks: HashMap<ChunkPos, Weak<RwLock<Chunk>>>,
 }
 
-/// A single chunk in a world (16*?*16 blocks). This only contains the blocks
-/// and biomes. You can derive the height of the chunk from the number of
-/// sections, but you need a [`ChunkStorage`] to get the minimum Y
-/// coordinate.
+/// A single chunk in a world (16*?*16 blocks). This only contains the blocks,
+/// block entities, biomes and light. You can derive the height of the chunk
+/// from the number of sections, but you need a [`ChunkStorage`] to get the
+/// minimum Y coordinate.
 #[derive(Debug)]
 pub struct Chunk {
     pub sections: Vec<Section>,
+    /// The light in the section right below the bottom of the world.
+    pub light_below: SectionLight,
+    /// The light in the section right above the top of the world.
+    pub light_above: SectionLight,
+    /// The block entities in the chunk, like chests and signs.
+    pub block_entities: HashMap<ChunkBlockPos, BlockEntity>,
 }
 
 /// A section of a chunk, i.e. a 16*16*16 block area.
@@ -51,6 +61,7 @@ pub struct Section {
     pub block_count: u16,
     pub states: PalettedContainer,
     pub biomes: PalettedContainer,
+    pub light: SectionLight,
 }
 
 impl Default for Section {
@@ -59,6 +70,7 @@ impl Default for Section {
             block_count: 0,
             states: PalettedContainer::new(&PalettedContainerType::BlockStates).unwrap(),
             biomes: PalettedContainer::new(&PalettedContainerType::Biomes).unwrap(),
+            light: SectionLight::default(),
         }
     }
 }
@@ -67,6 +79,9 @@ impl Default for Chunk {
     fn default() -> Self {
         Chunk {
             sections: vec![Section::default(); (384 / 16) as usize],
+            light_below: SectionLight::default(),
+            light_above: SectionLight::default(),
+            block_entities: HashMap::new(),
         }
     }
 }
@@ -145,18 +160,10 @@ impl PartialChunkStorage {
         }
 
         let index = self.get_index(pos);
-        self.chunks[index].as_ref()
-    }
-    /// Get a mutable reference to a [`Chunk`] within render distance, or
-    /// `None` if it's not loaded. Use [`ChunkStorage::get`] to get
-    /// a chunk from the shared storage.
-    pub fn limited_get_mut(&mut self, pos: &ChunkPos) -> Option<&mut Option<Arc<RwLock<Chunk>>>> {
-        if !self.in_range(pos) {
-            return None;
+        match &self.chunks[index] {
+            Some((chunk_pos, chunk)) if chunk_pos == pos => Some(chunk),
+            _ => None,
         }
-
-        let index = self.get_index(pos);
-        Some(&mut self.chunks[index])
     }
 
     /// Set a chunk in the shared storage and reference it from the limited
@@ -181,14 +188,51 @@ impl PartialChunkStorage {
         chunk: Option<Arc<RwLock<Chunk>>>,
         chunk_storage: &mut ChunkStorage,
     ) {
-        if let Some(chunk) = &chunk {
-            chunk_storage.chunks.insert(*pos, Arc::downgrade(chunk));
-        } else {
-            // don't remove it from the shared storage, since it'll be removed
-            // automatically if this was the last reference
+        let Some(chunk) = chunk else {
+            self.remove(pos, chunk_storage);
+            return;
+        };
+        chunk_storage.chunks.insert(*pos, Arc::downgrade(&chunk));
+        if self.in_range(pos) {
+            let index = self.get_index(pos);
+            self.chunks[index] = Some((*pos, chunk));
+        }
+    }
+
+    /// Stop referencing the chunk at the given position, and remove it from
+    /// the shared storage if nothing else is referencing it. This works even
+    /// if the chunk is no longer in the render distance. Returns whether the
+    /// chunk was loaded.
+    pub fn remove(&mut self, pos: &ChunkPos, chunk_storage: &mut ChunkStorage) -> bool {
+        let index = self.get_index(pos);
+        let removed = match &self.chunks[index] {
+            Some((chunk_pos, _)) if chunk_pos == pos => self.chunks[index].take().is_some(),
+            _ => false,
+        };
+        chunk_storage.remove_if_unused(pos);
+        removed
+    }
+
+    /// Change the render distance. Chunks that don't fit in the new render
+    /// distance are removed.
+    pub fn set_chunk_radius(&mut self, chunk_radius: u32, chunk_storage: &mut ChunkStorage) {
+        if chunk_radius == self.chunk_radius {
+            return;
         }
-        if let Some(chunk_mut) = self.limited_get_mut(pos) {
-            *chunk_mut = chunk;
+        self.chunk_radius = chunk_radius;
+        self.view_range = chunk_radius * 2 + 1;
+        let old_chunks = std::mem::replace(
+            &mut self.chunks,
+            vec![None; (self.view_range * self.view_range) as usize],
+        );
+        for (pos, chunk) in old_chunks.into_iter().flatten() {
+            if self.in_range(&pos) {
+                let index = self.get_index(&pos);
+                self.chunks[index] = Some((pos, chunk));
+            } else {
+                drop(chunk);
+                chunk_storage.remove_if_unused(&pos);
+            }
         }
     }
 }
@@ -205,6 +249,15 @@ impl ChunkStorage {
         self.chunks.get(pos).and_then(|chunk| chunk.upgrade())
     }
 
+    /// Remove the chunk at the given position if no partial storages are
+    /// referencing it anymore. Returns whether it was removed.
+    pub fn remove_if_unused(&mut self, pos: &ChunkPos) -> bool {
+        if self.get(pos).is_some() {
+            return false;
+        }
+        self.chunks.remove(pos).is_some()
+    }
+
     pub fn get_block_state(&self, pos: &BlockPos) -> Option<BlockState> {
         let chunk_pos = ChunkPos::from(pos);
         let chunk = self.get(&chunk_pos)?;
@@ -221,6 +274,61 @@ impl ChunkStorage {
         let mut chunk = chunk.write();
         Some(chunk.get_and_set(&ChunkBlockPos::from(pos), state, self.min_y))
     }
+
+    /// Get the id of the biome at the given position, or `None` if the chunk
+    /// isn't loaded or the position is outside of the world. Use
+    /// [`Instance::get_biome`](crate::Instance::get_biome) to get the name of
+    /// the biome.
+    pub fn get_biome(&self, pos: &BlockPos) -> Option<u32> {
+        let chunk_pos = ChunkPos::from(pos);
+        let chunk = self.get(&chunk_pos)?;
+        let chunk = chunk.read();
+        chunk.get_biome(&ChunkBlockPos::from(pos), self.min_y)
+    }
+
+    /// Get a copy of the block entity at the given position, or `None` if
+    /// there isn't one or the chunk isn't loaded.
+    pub fn get_block_entity(&self, pos: &BlockPos) -> Option<BlockEntity> {
+        let chunk_pos = ChunkPos::from(pos);
+        let chunk = self.get(&chunk_pos)?;
+        let chunk = chunk.read();
+        chunk.block_entities.get(&ChunkBlockPos::from(pos)).cloned()
+    }
+
+    /// Set the block entity at the given position. Returns `false` if the
+    /// chunk isn't loaded.
+    pub fn set_block_entity(&self, pos: &BlockPos, block_entity: BlockEntity) -> bool {
+        let chunk_pos = ChunkPos::from(pos);
+        let Some(chunk) = self.get(&chunk_pos) else {
+            return false;
+        };
+        let mut chunk = chunk.write();
+        chunk
+            .block_entities
+            .insert(ChunkBlockPos::from(pos), block_entity);
+        true
+    }
+
+    /// Get the light level of a block in the given layer, or `None` if the
+    /// chunk isn't loaded.
+    pub fn get_light(&self, layer: LightLayer, pos: &BlockPos) -> Option<u8> {
+        let chunk_pos = ChunkPos::from(pos);
+        let chunk = self.get(&chunk_pos)?;
+        let chunk = chunk.read();
+        Some(chunk.get_light(layer, &ChunkBlockPos::from(pos), self.min_y))
+    }
+
+    /// Get the light level from blocks like torches, or `None` if the chunk
+    /// isn't loaded.
+    pub fn get_block_light(&self, pos: &BlockPos) -> Option<u8> {
+        self.get_light(LightLayer::Block, pos)
+    }
+
+    /// Get the light level from the sky (ignoring the time of day), or `None`
+    /// if the chunk isn't loaded.
+    pub fn get_sky_light(&self, pos: &BlockPos) -> Option<u8> {
+        self.get_light(LightLayer::Sky, pos)
+    }
 }
 
 impl Chunk {
@@ -234,7 +342,12 @@ impl Chunk {
             let section = Section::read_from(buf)?;
             sections.push(section);
         }
-        Ok(Chunk { sections })
+        Ok(Chunk {
+            sections,
+            light_below: SectionLight::default(),
+            light_above: SectionLight::default(),
+            block_entities: HashMap::new(),
+        })
     }
 
     pub fn get(&self, pos: &ChunkBlockPos, min_y: i32) -> Option<BlockState> {
@@ -263,7 +376,9 @@ impl Chunk {
         // TODO: make sure the section exists
         let section = &mut self.sections[section_index as usize];
         let chunk_section_pos = ChunkSectionBlockPos::from(pos);
-        section.get_and_set(chunk_section_pos, state)
+        let previous_state = section.get_and_set(chunk_section_pos, state);
+        self.remove_block_entity_if_replaced(pos, previous_state, state);
+        previous_state
     }
 
     pub fn set(&mut self, pos: &ChunkBlockPos, state: BlockState, min_y: i32) {
@@ -271,7 +386,107 @@ impl Chunk {
         // TODO: make sure the section exists
         let section = &mut self.sections[section_index as usize];
         let chunk_section_pos = ChunkSectionBlockPos::from(pos);
+        let previous_state = section.get(chunk_section_pos.clone());
         section.set(chunk_section_pos, state);
+        self.remove_block_entity_if_replaced(pos, previous_state, state);
+    }
+
+    /// Get the id of the biome at the given position. Biomes are stored in
+    /// 4x4x4 cells, so this is the same for every block in the cell.
+    pub fn get_biome(&self, pos: &ChunkBlockPos, min_y: i32) -> Option<u32> {
+        if pos.y < min_y {
+            return None;
+        }
+        let section = self.sections.get(section_index(pos.y, min_y) as usize)?;
+        Some(section.get_biome(ChunkSectionBlockPos::from(pos)))
+    }
+
+    /// Like vanilla, remove the block entity at the position if the block
+    /// there was changed to a different block. Changing the state of the same
+    /// block (like opening a chest) keeps the block entity.
+    fn remove_block_entity_if_replaced(
+        &mut self,
+        pos: &ChunkBlockPos,
+        previous_state: BlockState,
+        state: BlockState,
+    ) {
+        if previous_state != state
+            && azalea_registry::Block::from(previous_state) != azalea_registry::Block::from(state)
+        {
+            self.block_entities.remove(pos);
+        }
+    }
+
+    /// The light data of every section in the chunk, including the extra
+    /// sections above and below the world. This is in the same order as the
+    /// masks in light update packets.
+    pub fn section_lights(&self) -> impl Iterator<Item = &SectionLight> {
+        std::iter::once(&self.light_below)
+            .chain(self.sections.iter().map(|section| &section.light))
+            .chain(std::iter::once(&self.light_above))
+    }
+
+    /// Mutable version of [`Self::section_lights`].
+    pub fn section_lights_mut(&mut self) -> impl Iterator<Item = &mut SectionLight> {
+        std::iter::once(&mut self.light_below)
+            .chain(self.sections.iter_mut().map(|section| &mut section.light))
+            .chain(std::iter::once(&mut self.light_above))
+    }
+
+    /// Update the light in one layer of the chunk with the data from a light
+    /// update. A section gets the next array in `updates` if its bit is set in
+    /// `mask`, and it's set to complete darkness if its bit is set in
+    /// `empty_mask`.
+    pub fn update_light(
+        &mut self,
+        layer: LightLayer,
+        mask: &BitSet,
+        empty_mask: &BitSet,
+        updates: &[Vec<u8>],
+    ) {
+        light::apply_light_updates(self.section_lights_mut(), layer, mask, empty_mask, updates);
+    }
+
+    /// Get the light level of a block in the given layer, from 0 to 15.
+    pub fn get_light(&self, layer: LightLayer, pos: &ChunkBlockPos, min_y: i32) -> u8 {
+        // the index in section_lights, so the section below the world is 0
+        let light_index = pos.y.div_floor(16) - min_y.div_floor(16) + 1;
+        let light_count = self.sections.len() as i32 + 2;
+        if light_index < 0 {
+            return 0;
+        }
+        if light_index >= light_count {
+            return match layer {
+                LightLayer::Sky => 15,
+                LightLayer::Block => 0,
+            };
+        }
+        let section_pos = ChunkSectionBlockPos::from(pos);
+
+        let mut section_lights = self.section_lights().skip(light_index as usize);
+        match layer {
+            LightLayer::Block => section_lights
+                .next()
+                .and_then(|light| light.block.as_ref())
+                .map_or(0, |data_layer| data_layer.get(section_pos)),
+            LightLayer::Sky => {
+                // like vanilla, if a section doesn't have sky light data then
+                // we use the bottom of the closest section above it that does
+                if let Some(data_layer) = section_lights.next().and_then(|light| light.sky.as_ref())
+                {
+                    return data_layer.get(section_pos);
+                }
+                match section_lights.find_map(|light| light.sky.as_ref()) {
+                    Some(data_layer) => data_layer.get(ChunkSectionBlockPos {
+                        y: 0,
+                        ..section_pos
+                    }),
+                    // TODO: dimensions without a sky (like the nether) should
+                    // always have a sky light of 0
+                    None => 15,
+                }
+            }
+        }
     }
 }
 
@@ -323,6 +538,7 @@ impl McBufReadable for Section {
             block_count,
             states,
             biomes,
+            light: SectionLight::default(),
         })
     }
 }
@@ -346,6 +562,15 @@ impl Section {
         BlockState::try_from(state).unwrap_or(BlockState::AIR)
     }
 
+    fn get_biome(&self, pos: ChunkSectionBlockPos) -> u32 {
+        // biomes are stored in 4x4x4 cells
+        self.biomes.get(
+            pos.x as usize >> 2,
+            pos.y as usize >> 2,
+            pos.z as usize >> 2,
+        )
+    }
+
     fn get_and_set(&mut self, pos: ChunkSectionBlockPos, state: BlockState) -> BlockState {
         let previous_state =
             self.states
@@ -382,6 +607,7 @@ pub fn section_index(y: i32, min_y: i32) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::light::DataLayer;
 
     #[test]
     fn test_section_index() {
@@ -420,4 +646,136 @@ mod tests {
             .get_block_state(&BlockPos { x: 0, y: -65, z: 0 })
             .is_none());
     }
+
+    #[test]
+    fn test_block_entity_removed_when_block_changes() {
+        let mut chunk = Chunk::default();
+        let pos = ChunkBlockPos::new(1, 2, 3);
+        let chest = BlockState::from(azalea_registry::Block::Chest);
+        chunk.set(&pos, chest, -64);
+        chunk.block_entities.insert(
+            pos,
+            BlockEntity {
+                kind: azalea_registry::BlockEntityKind::Chest,
+                data: azalea_nbt::Tag::Compound(Default::default()),
+            },
+        );
+
+        // a different state of the same block keeps the block entity
+        let other_chest = azalea_block::BlockStates::from(azalea_registry::Block::Chest)
+            .into_iter()
+            .find(|&state| state != chest)
+            .unwrap();
+        chunk.set(&pos, other_chest, -64);
+        assert!(chunk.block_entities.contains_key(&pos));
+
+        chunk.set(&pos, BlockState::AIR, -64);
+        assert!(!chunk.block_entities.contains_key(&pos));
+    }
+
+    #[test]
+    fn test_remove_chunk() {
+        let mut chunk_storage = ChunkStorage::default();
+        let mut partial_a = PartialChunkStorage::default();
+        let mut partial_b = PartialChunkStorage::default();
+        let pos = ChunkPos::new(1, 2);
+        partial_a.set(&pos, Some(Chunk::default()), &mut chunk_storage);
+        partial_b.set_with_shared_reference(&pos, chunk_storage.get(&pos), &mut chunk_storage);
+
+        // the other partial storage is still using it
+        assert!(partial_a.remove(&pos, &mut chunk_storage));
+        assert!(partial_a.limited_get(&pos).is_none());
+        assert!(chunk_storage.get(&pos).is_some());
+
+        assert!(partial_b.remove(&pos, &mut chunk_storage));
+        assert!(!chunk_storage.chunks.contains_key(&pos));
+        assert!(!partial_b.remove(&pos, &mut chunk_storage));
+    }
+
+    #[test]
+    fn test_remove_chunk_after_view_center_moved() {
+        let mut chunk_storage = ChunkStorage::default();
+        let mut partial_chunk_storage = PartialChunkStorage::new(1);
+        let old_pos = ChunkPos::new(-1, 0);
+        let new_pos = ChunkPos::new(2, 0);
+        partial_chunk_storage.set(&old_pos, Some(Chunk::default()), &mut chunk_storage);
+
+        // both positions use the same slot, so the old chunk shouldn't be
+        // returned for the new position
+        partial_chunk_storage.view_center = ChunkPos::new(1, 0);
+        assert!(partial_chunk_storage.limited_get(&new_pos).is_none());
+
+        assert!(partial_chunk_storage.remove(&old_pos, &mut chunk_storage));
+        assert!(chunk_storage.get(&old_pos).is_none());
+    }
+
+    #[test]
+    fn test_set_chunk_radius() {
+        let mut chunk_storage = ChunkStorage::default();
+        let mut partial_chunk_storage = PartialChunkStorage::new(3);
+        let near = ChunkPos::new(1, -1);
+        let far = ChunkPos::new(3, 0);
+        partial_chunk_storage.set(&near, Some(Chunk::default()), &mut chunk_storage);
+        partial_chunk_storage.set(&far, Some(Chunk::default()), &mut chunk_storage);
+
+        partial_chunk_storage.set_chunk_radius(2, &mut chunk_storage);
+        assert!(partial_chunk_storage.limited_get(&near).is_some());
+        assert!(chunk_storage.get(&near).is_some());
+        assert!(chunk_storage.get(&far).is_none());
+    }
+
+    #[test]
+    fn test_get_biome() {
+        let mut chunk = Chunk::default();
+        // the 4x4x4 cell from (4, 8, 12) to (7, 11, 15) in the section at y=0
+        chunk.sections[4].biomes.set(1, 2, 3, 5);
+
+        assert_eq!(chunk.get_biome(&ChunkBlockPos::new(4, 8, 12), -64), Some(5));
+        assert_eq!(
+            chunk.get_biome(&ChunkBlockPos::new(7, 11, 15), -64),
+            Some(5)
+        );
+        assert_eq!(
+            chunk.get_biome(&ChunkBlockPos::new(3, 11, 15), -64),
+            Some(0)
+        );
+        assert_eq!(
+            chunk.get_biome(&ChunkBlockPos::new(4, 72, 12), -64),
+            Some(0)
+        );
+        assert_eq!(chunk.get_biome(&ChunkBlockPos::new(4, 320, 12), -64), None);
+    }
+
+    #[test]
+    fn test_get_light() {
+        let mut chunk = Chunk::default();
+        let mut data_layer = DataLayer::new();
+        data_layer.set(ChunkSectionBlockPos::new(1, 0, 2), 9);
+        data_layer.set(ChunkSectionBlockPos::new(1, 3, 2), 4);
+        // the section from y=0 to y=15
+        chunk.sections[4].light.sky = Some(data_layer.clone());
+        chunk.light_below.block = Some(data_layer);
+
+        assert_eq!(
+            chunk.get_light(LightLayer::Sky, &ChunkBlockPos::new(1, 3, 2), -64),
+            4
+        );
+        // sections without data use the bottom of the next section up
+        assert_eq!(
+            chunk.get_light(LightLayer::Sky, &ChunkBlockPos::new(1, -30, 2), -64),
+            9
+        );
+        assert_eq!(
+            chunk.get_light(LightLayer::Sky, &ChunkBlockPos::new(1, 100, 2), -64),
+            15
+        );
+        assert_eq!(
+            chunk.get_light(LightLayer::Block, &ChunkBlockPos::new(1, -77, 2), -64),
+            4
+        );
+        assert_eq!(
+            chunk.get_light(LightLayer::Block, &ChunkBlockPos::new(1, 3, 2), -64),
+            0
+        );
+    }
 }
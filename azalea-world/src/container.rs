@@ -8,7 +8,7 @@ use std::{
     sync::{Arc, Weak},
 };
 
-use crate::{ChunkStorage, Instance};
+use crate::{ChunkStorage, Instance, WorldBorder};
 
 /// A container of [`Instance`]s (aka worlds). Instances are stored as a Weak
 /// pointer here, so if no clients are using an instance it will be forgotten.
@@ -70,6 +70,8 @@ impl InstanceContainer {
                 chunks: ChunkStorage::new(height, min_y),
                 entities_by_chunk: HashMap::new(),
                 entity_by_id: IntMap::default(),
+                world_border: WorldBorder::default(),
+                biome_registry: HashMap::new(),
             }));
             self.worlds.insert(name, Arc::downgrade(&world));
             world
@@ -6,13 +6,90 @@ use std::{
 };
 
 use azalea_buf::{BufReadError, McBuf, McBufReadable, McBufWritable};
+use azalea_registry::Attribute;
 use bevy_ecs::component::Component;
 use thiserror::Error;
 use uuid::{uuid, Uuid};
 
 #[derive(Clone, Debug, Component)]
 pub struct Attributes {
+    pub max_health: AttributeInstance,
+    pub follow_range: AttributeInstance,
+    pub knockback_resistance: AttributeInstance,
+    /// The movement speed of the entity. This is called `movement_speed` in
+    /// vanilla.
     pub speed: AttributeInstance,
+    /// The flying speed of entities like bees and parrots. Note that players
+    /// don't use this attribute, their speed in the air is decided by
+    /// azalea-physics.
+    pub flying_speed: AttributeInstance,
+    pub attack_damage: AttributeInstance,
+    pub attack_knockback: AttributeInstance,
+    pub attack_speed: AttributeInstance,
+    pub armor: AttributeInstance,
+    pub armor_toughness: AttributeInstance,
+    pub luck: AttributeInstance,
+    pub zombie_spawn_reinforcements: AttributeInstance,
+    pub horse_jump_strength: AttributeInstance,
+}
+
+impl Default for Attributes {
+    /// The default attributes of a player.
+    fn default() -> Self {
+        Self {
+            max_health: AttributeInstance::new(20.),
+            follow_range: AttributeInstance::new(32.),
+            knockback_resistance: AttributeInstance::new(0.),
+            speed: AttributeInstance::new(0.1),
+            flying_speed: AttributeInstance::new(0.4),
+            attack_damage: AttributeInstance::new(1.),
+            attack_knockback: AttributeInstance::new(0.),
+            attack_speed: AttributeInstance::new(4.),
+            armor: AttributeInstance::new(0.),
+            armor_toughness: AttributeInstance::new(0.),
+            luck: AttributeInstance::new(0.),
+            zombie_spawn_reinforcements: AttributeInstance::new(0.),
+            horse_jump_strength: AttributeInstance::new(0.7),
+        }
+    }
+}
+
+impl Attributes {
+    pub fn get(&self, attribute: Attribute) -> &AttributeInstance {
+        match attribute {
+            Attribute::GenericMaxHealth => &self.max_health,
+            Attribute::GenericFollowRange => &self.follow_range,
+            Attribute::GenericKnockbackResistance => &self.knockback_resistance,
+            Attribute::GenericMovementSpeed => &self.speed,
+            Attribute::GenericFlyingSpeed => &self.flying_speed,
+            Attribute::GenericAttackDamage => &self.attack_damage,
+            Attribute::GenericAttackKnockback => &self.attack_knockback,
+            Attribute::GenericAttackSpeed => &self.attack_speed,
+            Attribute::GenericArmor => &self.armor,
+            Attribute::GenericArmorToughness => &self.armor_toughness,
+            Attribute::GenericLuck => &self.luck,
+            Attribute::ZombieSpawnReinforcements => &self.zombie_spawn_reinforcements,
+            Attribute::HorseJumpStrength => &self.horse_jump_strength,
+        }
+    }
+
+    pub fn get_mut(&mut self, attribute: Attribute) -> &mut AttributeInstance {
+        match attribute {
+            Attribute::GenericMaxHealth => &mut self.max_health,
+            Attribute::GenericFollowRange => &mut self.follow_range,
+            Attribute::GenericKnockbackResistance => &mut self.knockback_resistance,
+            Attribute::GenericMovementSpeed => &mut self.speed,
+            Attribute::GenericFlyingSpeed => &mut self.flying_speed,
+            Attribute::GenericAttackDamage => &mut self.attack_damage,
+            Attribute::GenericAttackKnockback => &mut self.attack_knockback,
+            Attribute::GenericAttackSpeed => &mut self.attack_speed,
+            Attribute::GenericArmor => &mut self.armor,
+            Attribute::GenericArmorToughness => &mut self.armor_toughness,
+            Attribute::GenericLuck => &mut self.luck,
+            Attribute::ZombieSpawnReinforcements => &mut self.zombie_spawn_reinforcements,
+            Attribute::HorseJumpStrength => &mut self.horse_jump_strength,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -33,21 +110,34 @@ impl AttributeInstance {
         }
     }
 
+    /// Calculate the value of the attribute with all the modifiers applied.
+    ///
+    /// Like in vanilla, additions are applied first, then the base multipliers
+    /// and then the total multipliers.
     pub fn calculate(&self) -> f64 {
-        let mut total = self.base;
-        for modifier in self.modifiers_by_uuid.values() {
-            match modifier.operation {
-                AttributeModifierOperation::Addition => total += modifier.amount,
-                AttributeModifierOperation::MultiplyBase => total += self.base * modifier.amount,
-                _ => {}
-            }
-            if let AttributeModifierOperation::MultiplyTotal = modifier.operation {
-                total *= 1.0 + modifier.amount;
-            }
+        let mut base = self.base;
+        for modifier in self.modifiers(AttributeModifierOperation::Addition) {
+            base += modifier.amount;
+        }
+        let mut total = base;
+        for modifier in self.modifiers(AttributeModifierOperation::MultiplyBase) {
+            total += base * modifier.amount;
+        }
+        for modifier in self.modifiers(AttributeModifierOperation::MultiplyTotal) {
+            total *= 1.0 + modifier.amount;
         }
         total
     }
 
+    fn modifiers(
+        &self,
+        operation: AttributeModifierOperation,
+    ) -> impl Iterator<Item = &AttributeModifier> {
+        self.modifiers_by_uuid
+            .values()
+            .filter(move |modifier| modifier.operation == operation)
+    }
+
     /// Add a new modifier to this attribute.
     pub fn insert(&mut self, modifier: AttributeModifier) -> Result<(), AlreadyPresentError> {
         if self
@@ -66,6 +156,11 @@ impl AttributeInstance {
     pub fn remove(&mut self, uuid: &Uuid) -> Option<AttributeModifier> {
         self.modifiers_by_uuid.remove(uuid)
     }
+
+    /// Remove every modifier from this attribute.
+    pub fn clear_modifiers(&mut self) {
+        self.modifiers_by_uuid.clear();
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -76,7 +171,7 @@ pub struct AttributeModifier {
     pub operation: AttributeModifierOperation,
 }
 
-#[derive(Clone, Debug, Copy, McBuf)]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, McBuf)]
 pub enum AttributeModifierOperation {
     Addition,
     MultiplyBase,
@@ -92,6 +187,24 @@ pub fn sprinting_modifier() -> AttributeModifier {
     }
 }
 
+pub fn speed_modifier(amplifier: u8) -> AttributeModifier {
+    AttributeModifier {
+        uuid: uuid!("91AEAA56-376B-4498-935B-2F7F68070635"),
+        name: format!("effect.minecraft.speed {amplifier}"),
+        amount: 0.20000000298023224 * (amplifier as f64 + 1.),
+        operation: AttributeModifierOperation::MultiplyTotal,
+    }
+}
+
+pub fn slowness_modifier(amplifier: u8) -> AttributeModifier {
+    AttributeModifier {
+        uuid: uuid!("7107DE5E-7CE8-4030-940E-514C1F160890"),
+        name: format!("effect.minecraft.slowness {amplifier}"),
+        amount: -0.15000000596046448 * (amplifier as f64 + 1.),
+        operation: AttributeModifierOperation::MultiplyTotal,
+    }
+}
+
 impl McBufReadable for AttributeModifier {
     fn read_from(buf: &mut Cursor<&[u8]>) -> Result<Self, BufReadError> {
         let uuid = Uuid::read_from(buf)?;
@@ -114,3 +227,39 @@ impl McBufWritable for AttributeModifier {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_applies_operations_in_order() {
+        let mut attribute = AttributeInstance::new(1.);
+        attribute
+            .insert(AttributeModifier {
+                uuid: Uuid::from_u128(0),
+                name: "total".to_string(),
+                amount: 1.,
+                operation: AttributeModifierOperation::MultiplyTotal,
+            })
+            .unwrap();
+        attribute
+            .insert(AttributeModifier {
+                uuid: Uuid::from_u128(1),
+                name: "base".to_string(),
+                amount: 0.5,
+                operation: AttributeModifierOperation::MultiplyBase,
+            })
+            .unwrap();
+        attribute
+            .insert(AttributeModifier {
+                uuid: Uuid::from_u128(2),
+                name: "addition".to_string(),
+                amount: 1.,
+                operation: AttributeModifierOperation::Addition,
+            })
+            .unwrap();
+        // ((1 + 1) + (1 + 1) * 0.5) * (1 + 1)
+        assert_eq!(attribute.calculate(), 6.);
+    }
+}
@@ -3,7 +3,7 @@ use bevy_ecs::{query::Changed, system::Query};
 
 use super::{Physics, Position};
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct EntityDimensions {
     pub width: f32,
     pub height: f32,
@@ -25,6 +25,21 @@ impl EntityDimensions {
     }
 }
 
+impl From<azalea_registry::EntityKind> for EntityDimensions {
+    fn from(kind: azalea_registry::EntityKind) -> Self {
+        use azalea_registry::EntityKind;
+
+        // TODO: get correct entity dimensions by having them codegened somewhere,
+        // for now we only know about the ones that matter for collisions
+        let (width, height) = match kind {
+            EntityKind::Boat | EntityKind::ChestBoat => (1.375, 0.5625),
+            EntityKind::Shulker => (1., 1.),
+            _ => (0.6, 1.8),
+        };
+        Self { width, height }
+    }
+}
+
 /// Sets the position of the entity. This doesn't update the cache in
 /// azalea-world, and should only be used within azalea-world!
 ///
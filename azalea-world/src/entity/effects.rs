@@ -0,0 +1,116 @@
+//! Status effects like Speed or Haste. See
+//! <https://minecraft.fandom.com/wiki/Effect>.
+
+use std::collections::HashMap;
+
+use azalea_registry::MobEffect;
+use bevy_ecs::{component::Component, system::Query};
+use derive_more::{Deref, DerefMut};
+
+use super::{attributes, Attributes};
+
+/// The status effects that an entity currently has.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut)]
+pub struct ActiveEffects(pub HashMap<MobEffect, MobEffectInstance>);
+
+/// A status effect that's applied to an entity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MobEffectInstance {
+    /// The level of the effect minus one, so Haste II has an amplifier of 1.
+    pub amplifier: u8,
+    /// The number of ticks until the effect runs out.
+    ///
+    /// This counts down every tick, but the effect is only removed when the
+    /// server tells us it's gone.
+    pub duration: u32,
+    /// Whether the effect came from a beacon or conduit.
+    pub ambient: bool,
+    /// Whether the effect shows particles.
+    pub visible: bool,
+    /// Whether the effect shows an icon in the inventory and HUD.
+    pub show_icon: bool,
+}
+
+impl MobEffectInstance {
+    /// Create a new effect instance from the flags byte sent in the update mob
+    /// effect packet.
+    pub fn from_flags(amplifier: u8, duration: u32, flags: u8) -> Self {
+        Self {
+            amplifier,
+            duration,
+            ambient: flags & 0x01 != 0,
+            visible: flags & 0x02 != 0,
+            show_icon: flags & 0x04 != 0,
+        }
+    }
+}
+
+impl ActiveEffects {
+    /// Get the amplifier of the given effect, or `None` if the entity doesn't
+    /// have it.
+    pub fn amplifier(&self, effect: MobEffect) -> Option<u8> {
+        self.0.get(&effect).map(|instance| instance.amplifier)
+    }
+
+    pub fn has(&self, effect: MobEffect) -> bool {
+        self.0.contains_key(&effect)
+    }
+}
+
+/// Add or remove the attribute modifiers that an effect applies, like Speed
+/// and Slowness changing the movement speed. Pass `None` as the amplifier if
+/// the effect was removed.
+///
+/// The server also syncs these modifiers in the update attributes packet, but
+/// doing this ourselves means we don't have to wait for it.
+pub fn update_effect_attribute_modifiers(
+    attributes: &mut Attributes,
+    effect: MobEffect,
+    amplifier: Option<u8>,
+) {
+    let modifier_fn = match effect {
+        MobEffect::Speed => attributes::speed_modifier,
+        MobEffect::Slowness => attributes::slowness_modifier,
+        _ => return,
+    };
+    // the uuid doesn't depend on the amplifier
+    attributes.speed.remove(&modifier_fn(0).uuid);
+    if let Some(amplifier) = amplifier {
+        attributes
+            .speed
+            .insert(modifier_fn(amplifier))
+            .expect("we just removed the modifier");
+    }
+}
+
+/// Count down the duration of every entity's effects.
+pub fn tick_active_effects(mut query: Query<&mut ActiveEffects>) {
+    for mut active_effects in &mut query {
+        if active_effects.is_empty() {
+            continue;
+        }
+        for instance in active_effects.values_mut() {
+            instance.duration = instance.duration.saturating_sub(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_flags() {
+        let instance = MobEffectInstance::from_flags(1, 200, 0b110);
+        assert_eq!(
+            instance,
+            MobEffectInstance {
+                amplifier: 1,
+                duration: 200,
+                ambient: false,
+                visible: true,
+                show_icon: true,
+            }
+        );
+    }
+}
@@ -4,12 +4,13 @@
 use crate::{
     deduplicate_entities, deduplicate_local_entities,
     entity::{
-        self, add_dead, update_bounding_box, EntityUuid, MinecraftEntityId, Position, WorldName,
+        self, add_dead, effects::tick_active_effects, update_bounding_box, EntityUuid,
+        MinecraftEntityId, Position, WorldName,
     },
     update_entity_by_id_index, update_uuid_index, InstanceContainer, PartialInstance,
 };
 use azalea_core::ChunkPos;
-use bevy_app::{App, CoreSet, Plugin};
+use bevy_app::{App, CoreSchedule, CoreSet, IntoSystemAppConfig, Plugin};
 use bevy_ecs::{
     component::Component,
     entity::Entity,
@@ -76,6 +77,7 @@ impl Plugin for EntityPlugin {
             add_dead,
             update_bounding_box,
         ))
+        .add_system(tick_active_effects.in_schedule(CoreSchedule::FixedUpdate))
         .init_resource::<EntityInfos>();
     }
 }
@@ -282,13 +284,24 @@ fn debug_detect_updates_received_on_local_entities(
 }
 
 /// Despawn entities that aren't being loaded by anything.
+#[allow(clippy::type_complexity)]
 fn remove_despawned_entities_from_indexes(
     mut commands: Commands,
     mut entity_infos: ResMut<EntityInfos>,
     world_container: Res<InstanceContainer>,
-    query: Query<(Entity, &EntityUuid, &Position, &WorldName, &LoadedBy), Changed<LoadedBy>>,
+    query: Query<
+        (
+            Entity,
+            &EntityUuid,
+            &MinecraftEntityId,
+            &Position,
+            &WorldName,
+            &LoadedBy,
+        ),
+        Changed<LoadedBy>,
+    >,
 ) {
-    for (entity, uuid, position, world_name, loaded_by) in &query {
+    for (entity, uuid, id, position, world_name, loaded_by) in &query {
         let world_lock = world_container.get(world_name).unwrap();
         let mut world = world_lock.write();
 
@@ -315,10 +328,13 @@ fn remove_despawned_entities_from_indexes(
         if entity_infos.entity_by_uuid.remove(uuid).is_none() {
             warn!("Tried to remove entity {entity:?} from the uuid index but it was not there.");
         }
+        // remove it from the id index, unless the id was already reused
+        if world.entity_by_id.get(id) == Some(&entity) {
+            world.entity_by_id.remove(id);
+        }
         // and now remove the entity from the ecs
         commands.entity(entity).despawn();
         debug!("Despawned entity {entity:?} because it was not loaded by anything.");
-        return;
     }
 }
 
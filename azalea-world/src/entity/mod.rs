@@ -3,12 +3,13 @@
 pub mod attributes;
 mod data;
 mod dimensions;
+pub mod effects;
 mod info;
 pub mod metadata;
 
 use crate::ChunkStorage;
 
-use self::{attributes::AttributeInstance, metadata::Health};
+use self::metadata::Health;
 pub use attributes::Attributes;
 use azalea_block::BlockState;
 use azalea_core::{BlockPos, ChunkPos, ResourceLocation, Vec3, AABB};
@@ -22,10 +23,11 @@ use bevy_ecs::{
 pub use data::*;
 use derive_more::{Deref, DerefMut};
 pub use dimensions::{update_bounding_box, EntityDimensions};
+pub use effects::{ActiveEffects, MobEffectInstance};
 pub use info::{
     EntityInfos, EntityPlugin, EntityUpdateSet, LoadedBy, PartialEntityInfos, RelativeEntityUpdate,
 };
-use std::fmt::Debug;
+use std::{f64::consts::PI, fmt::Debug};
 use uuid::Uuid;
 
 /// An entity ID used by Minecraft. These are not guaranteed to be unique in
@@ -45,6 +47,29 @@ pub fn set_rotation(physics: &mut Physics, y_rot: f32, x_rot: f32) {
     // idk what they're used for so
 }
 
+/// Get the rotation (`y_rot`, `x_rot`) that an entity at `current` would need
+/// to look at `target`.
+pub fn direction_looking_at(current: &Vec3, target: &Vec3) -> (f32, f32) {
+    // borrowed from mineflayer's Bot.lookAt because i didn't want to do math
+    let delta = target - current;
+    let y_rot = (PI - f64::atan2(-delta.x, -delta.z)) * (180.0 / PI);
+    let ground_distance = f64::sqrt(delta.x * delta.x + delta.z * delta.z);
+    let x_rot = f64::atan2(delta.y, ground_distance) * -(180.0 / PI);
+    (y_rot as f32, x_rot as f32)
+}
+
+/// Get the unit vector in the direction that the entity is looking.
+pub fn view_vector(physics: &Physics) -> Vec3 {
+    let x_rot = physics.x_rot * 0.017453292;
+    let y_rot = -physics.y_rot * 0.017453292;
+    let x_rot_cos = f32::cos(x_rot);
+    Vec3 {
+        x: (f32::sin(y_rot) * x_rot_cos) as f64,
+        y: -f32::sin(x_rot) as f64,
+        z: (f32::cos(y_rot) * x_rot_cos) as f64,
+    }
+}
+
 pub fn move_relative(physics: &mut Physics, speed: f32, acceleration: &Vec3) {
     let input_vector = input_vector(physics, speed, acceleration);
     physics.delta += input_vector;
@@ -185,9 +210,17 @@ pub struct WorldName(pub ResourceLocation);
 #[derive(Debug, Component, Deref, DerefMut)]
 pub struct Jumping(bool);
 
+/// A component for entities that can sneak.
+///
+/// If this is true, the entity moves slowly, won't walk off the edges of
+/// blocks, and doesn't slide down ladders. (It's equivalent to the shift key
+/// being held in vanilla.)
+#[derive(Debug, Component, Deref, DerefMut)]
+pub struct Sneaking(bool);
+
 /// The physics data relating to the entity, such as position, velocity, and
 /// bounding box.
-#[derive(Debug, Component)]
+#[derive(Clone, Debug, Component)]
 pub struct Physics {
     pub delta: Vec3,
 
@@ -214,8 +247,37 @@ pub struct Physics {
     pub bounding_box: AABB,
 
     pub has_impulse: bool,
+
+    /// Whether the entity ran into a wall the last time it moved.
+    pub horizontal_collision: bool,
+    /// What the entity's next movement will be multiplied by because it's
+    /// stuck in a block like a cobweb. This is zero if it's not stuck.
+    pub stuck_speed_multiplier: Vec3,
+
+    /// Whether the entity is touching water. This is updated at the start of
+    /// every physics tick.
+    pub is_in_water: bool,
+    /// Whether the entity is touching lava.
+    pub is_in_lava: bool,
+    /// How deep the entity is in water, measured from the bottom of its
+    /// bounding box. This is 0 if it's not in water.
+    pub water_height: f64,
+    /// How deep the entity is in lava, measured from the bottom of its
+    /// bounding box. This is 0 if it's not in lava.
+    pub lava_height: f64,
 }
 
+/// The height of an entity's eyes above its feet. This is used for things like
+/// figuring out what an entity is looking at.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Deref, DerefMut)]
+pub struct EyeHeight(pub f32);
+
+/// The direction an entity's head is facing horizontally, in degrees. This can
+/// be different from the `y_rot` in [`Physics`], which is the direction the
+/// entity's body is facing.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Deref, DerefMut)]
+pub struct HeadRotation(pub f32);
+
 /// Marker component for entities that are dead.
 ///
 /// "Dead" means that the entity has 0 health.
@@ -256,6 +318,10 @@ pub struct EntityBundle {
     pub physics: Physics,
     pub attributes: Attributes,
     pub jumping: Jumping,
+    pub sneaking: Sneaking,
+    pub eye_height: EyeHeight,
+    pub head_rotation: HeadRotation,
+    pub active_effects: ActiveEffects,
 }
 
 impl EntityBundle {
@@ -265,11 +331,8 @@ impl EntityBundle {
         kind: azalea_registry::EntityKind,
         world_name: ResourceLocation,
     ) -> Self {
-        // TODO: get correct entity dimensions by having them codegened somewhere
-        let dimensions = EntityDimensions {
-            width: 0.6,
-            height: 1.8,
-        };
+        let dimensions = EntityDimensions::from(kind);
+        let dimensions_height = dimensions.height;
 
         Self {
             kind: EntityKind(kind),
@@ -293,20 +356,37 @@ impl EntityBundle {
                 on_ground: false,
                 last_on_ground: false,
 
-                // TODO: have this be based on the entity type
                 bounding_box: dimensions.make_bounding_box(&pos),
                 dimensions,
 
                 has_impulse: false,
-            },
 
-            attributes: Attributes {
-                // TODO: do the correct defaults for everything, some
-                // entities have different defaults
-                speed: AttributeInstance::new(0.1),
+                horizontal_collision: false,
+                stuck_speed_multiplier: Vec3::default(),
+
+                is_in_water: false,
+                is_in_lava: false,
+                water_height: 0.,
+                lava_height: 0.,
             },
 
+            // TODO: do the correct defaults for everything, some
+            // entities have different defaults
+            attributes: Attributes::default(),
+
             jumping: Jumping(false),
+            sneaking: Sneaking(false),
+
+            // players have a special eye height, every other entity has it at
+            // 85% of their height
+            eye_height: EyeHeight(if kind == azalea_registry::EntityKind::Player {
+                1.62
+            } else {
+                dimensions_height * 0.85
+            }),
+
+            head_rotation: HeadRotation::default(),
+            active_effects: ActiveEffects::default(),
         }
     }
 }
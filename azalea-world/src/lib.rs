@@ -4,20 +4,25 @@
 #![feature(provide_any)]
 
 mod bit_storage;
+mod block_entity;
 mod chunk_storage;
 mod container;
 pub mod entity;
 pub mod iterators;
+pub mod light;
 pub mod palette;
 mod world;
+mod world_border;
 
 use std::backtrace::Backtrace;
 
 pub use bit_storage::BitStorage;
+pub use block_entity::BlockEntity;
 pub use chunk_storage::{Chunk, ChunkStorage, PartialChunkStorage, Section};
 pub use container::*;
 use thiserror::Error;
 pub use world::*;
+pub use world_border::WorldBorder;
 
 #[derive(Error, Debug)]
 pub enum MoveEntityError {
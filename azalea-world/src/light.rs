@@ -0,0 +1,187 @@
+//! Block light and sky light levels. See
+//! <https://minecraft.fandom.com/wiki/Light>.
+
+use std::fmt::{self, Debug};
+
+use azalea_core::{BitSet, ChunkSectionBlockPos};
+use log::warn;
+
+/// The number of bytes in a [`DataLayer`]. Every block in a section gets half
+/// a byte.
+pub const DATA_LAYER_SIZE: usize = 2048;
+
+/// The two kinds of light in Minecraft.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LightLayer {
+    /// Light that comes from the sky. This is always 15 in the open air during
+    /// the day, but it's affected by the time of day and weather.
+    Sky,
+    /// Light that comes from blocks like torches and lava.
+    Block,
+}
+
+/// The light levels of every block in a section, stored as an array of
+/// nibbles.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DataLayer {
+    data: Box<[u8]>,
+}
+
+impl DataLayer {
+    /// Create a data layer where every light level is 0.
+    pub fn new() -> Self {
+        Self {
+            data: vec![0; DATA_LAYER_SIZE].into_boxed_slice(),
+        }
+    }
+
+    /// Create a data layer from the bytes that were sent in a light update.
+    /// Returns `None` if there's the wrong number of bytes.
+    pub fn from_bytes(data: Vec<u8>) -> Option<Self> {
+        if data.len() != DATA_LAYER_SIZE {
+            return None;
+        }
+        Some(Self {
+            data: data.into_boxed_slice(),
+        })
+    }
+
+    /// Get the light level of a block, from 0 to 15.
+    pub fn get(&self, pos: ChunkSectionBlockPos) -> u8 {
+        let index = Self::index(pos);
+        // even indexes are in the low nibble
+        let shift = (index & 1) * 4;
+        (self.data[index >> 1] >> shift) & 0xf
+    }
+
+    /// Set the light level of a block. Only the lowest 4 bits of the level are
+    /// used.
+    pub fn set(&mut self, pos: ChunkSectionBlockPos, level: u8) {
+        let index = Self::index(pos);
+        let shift = (index & 1) * 4;
+        let byte = &mut self.data[index >> 1];
+        *byte = (*byte & !(0xf << shift)) | ((level & 0xf) << shift);
+    }
+
+    fn index(pos: ChunkSectionBlockPos) -> usize {
+        (pos.y as usize) << 8 | (pos.z as usize) << 4 | pos.x as usize
+    }
+}
+
+impl Default for DataLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for DataLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // printing all 2048 bytes wouldn't be useful
+        f.debug_struct("DataLayer").finish_non_exhaustive()
+    }
+}
+
+/// The light data for a section. A layer is `None` if the server didn't send
+/// us any data for it.
+#[derive(Clone, Debug, Default)]
+pub struct SectionLight {
+    pub sky: Option<DataLayer>,
+    pub block: Option<DataLayer>,
+}
+
+impl SectionLight {
+    pub fn layer(&self, layer: LightLayer) -> Option<&DataLayer> {
+        match layer {
+            LightLayer::Sky => self.sky.as_ref(),
+            LightLayer::Block => self.block.as_ref(),
+        }
+    }
+
+    pub fn layer_mut(&mut self, layer: LightLayer) -> &mut Option<DataLayer> {
+        match layer {
+            LightLayer::Sky => &mut self.sky,
+            LightLayer::Block => &mut self.block,
+        }
+    }
+}
+
+/// Apply the light data for one layer from a light update to a list of
+/// sections. `sections` has to include the extra sections above and below the
+/// world, in the same order as the bits in the masks.
+///
+/// If a bit is set in `mask`, the section gets the next array from `updates`.
+/// If it's set in `empty_mask`, the section is cleared. Otherwise it's left
+/// alone.
+pub(crate) fn apply_light_updates<'a>(
+    sections: impl Iterator<Item = &'a mut SectionLight>,
+    layer: LightLayer,
+    mask: &BitSet,
+    empty_mask: &BitSet,
+    updates: &[Vec<u8>],
+) {
+    let mut updates = updates.iter();
+    for (i, section) in sections.enumerate() {
+        if mask.index(i) {
+            let Some(data) = updates.next() else {
+                warn!("Light update has fewer {layer:?} arrays than its mask says");
+                return;
+            };
+            match DataLayer::from_bytes(data.clone()) {
+                Some(data_layer) => *section.layer_mut(layer) = Some(data_layer),
+                None => warn!(
+                    "Got {layer:?} light array with {} bytes instead of {DATA_LAYER_SIZE}",
+                    data.len()
+                ),
+            }
+        } else if empty_mask.index(i) {
+            *section.layer_mut(layer) = Some(DataLayer::new());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_layer_nibbles() {
+        let mut data_layer = DataLayer::new();
+        data_layer.set(ChunkSectionBlockPos::new(0, 0, 0), 15);
+        data_layer.set(ChunkSectionBlockPos::new(1, 0, 0), 7);
+        data_layer.set(ChunkSectionBlockPos::new(3, 15, 2), 20);
+        assert_eq!(data_layer.get(ChunkSectionBlockPos::new(0, 0, 0)), 15);
+        assert_eq!(data_layer.get(ChunkSectionBlockPos::new(1, 0, 0)), 7);
+        assert_eq!(data_layer.get(ChunkSectionBlockPos::new(3, 15, 2)), 4);
+        assert_eq!(data_layer.data[0], 0x7f);
+    }
+
+    #[test]
+    fn test_apply_light_updates() {
+        let mut sections = vec![SectionLight::default(); 4];
+        sections[3].block = Some(DataLayer::from_bytes(vec![0xff; DATA_LAYER_SIZE]).unwrap());
+
+        let mut mask = BitSet::new(4);
+        mask.set(1);
+        let mut empty_mask = BitSet::new(4);
+        empty_mask.set(3);
+        apply_light_updates(
+            sections.iter_mut(),
+            LightLayer::Block,
+            &mask,
+            &empty_mask,
+            &[vec![0x11; DATA_LAYER_SIZE]],
+        );
+
+        let get = |section: &SectionLight| {
+            section
+                .block
+                .as_ref()
+                .map(|data_layer| data_layer.get(ChunkSectionBlockPos::new(5, 5, 5)))
+        };
+        assert_eq!(get(&sections[0]), None);
+        assert_eq!(get(&sections[1]), Some(1));
+        assert_eq!(get(&sections[2]), None);
+        assert_eq!(get(&sections[3]), Some(0));
+        assert!(sections.iter().all(|section| section.sky.is_none()));
+    }
+}
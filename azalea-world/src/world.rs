@@ -4,10 +4,10 @@ use crate::{
     },
     iterators::ChunkIterator,
     palette::Palette,
-    ChunkStorage, InstanceContainer, PartialChunkStorage,
+    BlockEntity, ChunkStorage, InstanceContainer, PartialChunkStorage, WorldBorder,
 };
-use azalea_block::{BlockState, BlockStates};
-use azalea_core::{BlockPos, ChunkPos};
+use azalea_block::{BlockState, BlockStates, FluidState};
+use azalea_core::{BlockPos, ChunkPos, ResourceLocation};
 use bevy_ecs::{
     entity::Entity,
     query::{Changed, With, Without},
@@ -183,6 +183,12 @@ pub struct Instance {
 
     /// An index of Minecraft entity IDs to Azalea ECS entities.
     pub entity_by_id: IntMap<MinecraftEntityId, Entity>,
+
+    pub world_border: WorldBorder,
+
+    /// The names of the biomes that the server told us about, indexed by the
+    /// ids that are used for them in chunk data.
+    pub biome_registry: HashMap<u32, ResourceLocation>,
 }
 
 impl Instance {
@@ -260,6 +266,57 @@ impl Instance {
 
         None
     }
+
+    /// Get the fluid (water or lava) at the given position, or `None` if the
+    /// chunk isn't loaded. Waterlogged blocks count as water.
+    pub fn get_fluid_state(&self, pos: &BlockPos) -> Option<FluidState> {
+        self.chunks.get_block_state(pos).map(FluidState::from)
+    }
+
+    /// Get a copy of the block entity (like a chest or sign) at the given
+    /// position, or `None` if there isn't one or the chunk isn't loaded.
+    pub fn block_entity(&self, pos: &BlockPos) -> Option<BlockEntity> {
+        self.chunks.get_block_entity(pos)
+    }
+
+    /// Get the name of the biome at the given position, like
+    /// `minecraft:plains`. Returns `None` if the chunk isn't loaded or the
+    /// biome isn't in the registry.
+    ///
+    /// Biomes are stored in 4x4x4 cells, so this gives the biome of the cell
+    /// that the block is in. Note that vanilla adds some noise to the
+    /// boundaries between cells, so this can be different from what the F3
+    /// screen shows near a biome border.
+    pub fn get_biome(&self, pos: &BlockPos) -> Option<ResourceLocation> {
+        let biome_id = self.chunks.get_biome(pos)?;
+        self.biome_registry.get(&biome_id).cloned()
+    }
+
+    /// Get the light level from blocks like torches at the given position, or
+    /// `None` if the chunk isn't loaded.
+    pub fn get_block_light(&self, pos: &BlockPos) -> Option<u8> {
+        self.chunks.get_block_light(pos)
+    }
+
+    /// Get the light level from the sky at the given position, or `None` if
+    /// the chunk isn't loaded. This doesn't take the time of day or weather
+    /// into account, use [`Self::get_brightness`] for that.
+    pub fn get_sky_light(&self, pos: &BlockPos) -> Option<u8> {
+        self.chunks.get_sky_light(pos)
+    }
+
+    /// Get the light level at the given position, which is the higher of the
+    /// block light and the sky light minus `sky_darken`. `sky_darken` depends
+    /// on the time of day and weather, and is 0 during a clear day and 11 at
+    /// midnight.
+    ///
+    /// Hostile mobs can only spawn where the block light is 0 and the
+    /// brightness is low enough, so this is useful for finding dark spots.
+    pub fn get_brightness(&self, pos: &BlockPos, sky_darken: u8) -> Option<u8> {
+        let sky_light = self.get_sky_light(pos)?.saturating_sub(sky_darken);
+        let block_light = self.get_block_light(pos)?;
+        Some(sky_light.max(block_light))
+    }
 }
 
 impl Debug for PartialInstance {
@@ -317,6 +374,8 @@ impl From<ChunkStorage> for Instance {
             chunks,
             entities_by_chunk: HashMap::new(),
             entity_by_id: IntMap::default(),
+            world_border: WorldBorder::default(),
+            biome_registry: HashMap::new(),
         }
     }
 }
@@ -0,0 +1,174 @@
+//! The border around a world that entities can't go past. See
+//! <https://minecraft.fandom.com/wiki/World_border>.
+
+use std::time::{Duration, Instant};
+
+use azalea_core::{BlockPos, Vec3, AABB};
+
+/// The border of an [`Instance`](crate::Instance).
+///
+/// The size of the border can change smoothly over time, which is why you
+/// should use [`WorldBorder::size`] instead of reading the size directly.
+#[derive(Debug, Clone)]
+pub struct WorldBorder {
+    pub center_x: f64,
+    pub center_z: f64,
+    /// The border can never go further than this many blocks from 0, 0.
+    pub absolute_max_size: u32,
+    /// How many blocks away from the border the screen starts turning red.
+    pub warning_blocks: u32,
+    /// How many seconds before a shrinking border reaches a player the screen
+    /// starts turning red.
+    pub warning_time: u32,
+
+    /// The size that the border is changing from.
+    old_size: f64,
+    /// The size that the border is changing to, or the current size if it's
+    /// not changing.
+    new_size: f64,
+    lerp_start: Instant,
+    lerp_duration: Duration,
+}
+
+impl Default for WorldBorder {
+    fn default() -> Self {
+        Self {
+            center_x: 0.,
+            center_z: 0.,
+            absolute_max_size: 29999984,
+            warning_blocks: 5,
+            warning_time: 15,
+            old_size: 5.9999968E7,
+            new_size: 5.9999968E7,
+            lerp_start: Instant::now(),
+            lerp_duration: Duration::ZERO,
+        }
+    }
+}
+
+impl WorldBorder {
+    /// The current diameter of the border.
+    pub fn size(&self) -> f64 {
+        self.size_at(Instant::now())
+    }
+
+    /// The diameter that the border will have at the given time.
+    pub fn size_at(&self, time: Instant) -> f64 {
+        let elapsed = time.saturating_duration_since(self.lerp_start);
+        if elapsed >= self.lerp_duration {
+            return self.new_size;
+        }
+        let progress = elapsed.as_secs_f64() / self.lerp_duration.as_secs_f64();
+        self.old_size + (self.new_size - self.old_size) * progress
+    }
+
+    /// The size that the border is moving towards, or the current size if it's
+    /// not moving.
+    pub fn target_size(&self) -> f64 {
+        self.new_size
+    }
+
+    /// Whether the border is currently growing or shrinking.
+    pub fn is_lerping(&self) -> bool {
+        self.lerp_start.elapsed() < self.lerp_duration
+    }
+
+    /// Immediately set the size of the border.
+    pub fn set_size(&mut self, size: f64) {
+        self.lerp_size_between(size, size, Duration::ZERO);
+    }
+
+    /// Make the border smoothly change size from `old_size` to `new_size`,
+    /// starting now.
+    pub fn lerp_size_between(&mut self, old_size: f64, new_size: f64, duration: Duration) {
+        self.old_size = old_size;
+        self.new_size = new_size;
+        self.lerp_start = Instant::now();
+        self.lerp_duration = duration;
+    }
+
+    pub fn min_x(&self) -> f64 {
+        self.clamp_to_max_size(self.center_x - self.size() / 2.)
+    }
+    pub fn max_x(&self) -> f64 {
+        self.clamp_to_max_size(self.center_x + self.size() / 2.)
+    }
+    pub fn min_z(&self) -> f64 {
+        self.clamp_to_max_size(self.center_z - self.size() / 2.)
+    }
+    pub fn max_z(&self) -> f64 {
+        self.clamp_to_max_size(self.center_z + self.size() / 2.)
+    }
+
+    fn clamp_to_max_size(&self, coordinate: f64) -> f64 {
+        let absolute_max_size = self.absolute_max_size as f64;
+        coordinate.clamp(-absolute_max_size, absolute_max_size)
+    }
+
+    /// Whether any part of the block is inside the border. Vanilla won't let
+    /// you interact with blocks that are completely outside of it.
+    pub fn is_within_border(&self, pos: &BlockPos) -> bool {
+        (pos.x + 1) as f64 > self.min_x()
+            && (pos.x as f64) < self.max_x()
+            && (pos.z + 1) as f64 > self.min_z()
+            && (pos.z as f64) < self.max_z()
+    }
+
+    /// Whether any part of the bounding box is inside the border.
+    pub fn is_aabb_within_border(&self, aabb: &AABB) -> bool {
+        aabb.max_x > self.min_x()
+            && aabb.min_x < self.max_x()
+            && aabb.max_z > self.min_z()
+            && aabb.min_z < self.max_z()
+    }
+
+    /// The distance from the position to the closest side of the border. This
+    /// is negative if the position is outside of the border.
+    pub fn distance_to_border(&self, position: &Vec3) -> f64 {
+        let to_min_z = position.z - self.min_z();
+        let to_max_z = self.max_z() - position.z;
+        let to_min_x = position.x - self.min_x();
+        let to_max_x = self.max_x() - position.x;
+        to_min_x.min(to_max_x).min(to_min_z.min(to_max_z))
+    }
+
+    /// Whether an entity at the given position is inside the border and close
+    /// enough to it that the border should be checked for collisions.
+    /// `movement_aabb` is the entity's bounding box expanded by how far it's
+    /// trying to move.
+    pub fn is_inside_close_to_border(&self, position: &Vec3, movement_aabb: &AABB) -> bool {
+        let x_size = movement_aabb.max_x - movement_aabb.min_x;
+        let z_size = movement_aabb.max_z - movement_aabb.min_z;
+        let margin = f64::max(x_size.abs().max(z_size.abs()), 1.);
+        self.distance_to_border(position) < margin * 2.
+            && position.x >= self.min_x() - margin
+            && position.x < self.max_x() + margin
+            && position.z >= self.min_z() - margin
+            && position.z < self.max_z() + margin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_within_border() {
+        let mut border = WorldBorder::default();
+        border.set_size(10.);
+        assert!(border.is_within_border(&BlockPos::new(0, 0, 0)));
+        assert!(border.is_within_border(&BlockPos::new(-5, 0, 4)));
+        assert!(!border.is_within_border(&BlockPos::new(5, 0, 0)));
+        assert!(!border.is_within_border(&BlockPos::new(0, 0, -6)));
+    }
+
+    #[test]
+    fn test_lerp_size() {
+        let mut border = WorldBorder::default();
+        border.lerp_size_between(100., 50., Duration::from_secs(10));
+        let start = border.lerp_start;
+        assert_eq!(border.size_at(start), 100.);
+        assert_eq!(border.size_at(start + Duration::from_secs(5)), 75.);
+        assert_eq!(border.size_at(start + Duration::from_secs(20)), 50.);
+    }
+}
@@ -119,7 +119,9 @@ async fn handle(mut bot: Client, event: Event, _state: State) -> anyhow::Result<
                         let entity_pos = bot.entity_component::<Position>(entity);
                         let target_pos: BlockPos = entity_pos.into();
                         println!("going to {target_pos:?}");
-                        bot.goto(BlockPosGoal::from(target_pos));
+                        if let Err(err) = bot.goto(BlockPosGoal::from(target_pos)).await {
+                            bot.chat(&format!("I couldn't get to you: {err}"));
+                        }
                     }
                     "look" => {
                         let entity_pos = bot.entity_component::<Position>(entity);
@@ -154,7 +156,9 @@ async fn handle(mut bot: Client, event: Event, _state: State) -> anyhow::Result<
                         );
                         if let Some(target_pos) = target_pos {
                             // +1 to stand on top of the block
-                            bot.goto(BlockPosGoal::from(target_pos.up(1)));
+                            if let Err(err) = bot.goto(BlockPosGoal::from(target_pos.up(1))).await {
+                                bot.chat(&format!("I couldn't get to the block: {err}"));
+                            }
                         } else {
                             bot.chat("no diamond block found");
                         }
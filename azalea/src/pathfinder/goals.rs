@@ -0,0 +1,292 @@
+//! The goals that the pathfinder can try to reach.
+//!
+//! Goals can be combined with [`AnyGoal`], [`AllGoal`] and [`InverseGoal`], so
+//! for example "get within 4 blocks of any of these chests" is an [`AnyGoal`]
+//! of [`RadiusGoal`]s.
+
+use std::sync::Arc;
+
+use azalea_client::interact::MAX_INTERACTION_DISTANCE;
+use azalea_core::{BlockPos, Vec3};
+use azalea_world::Instance;
+use parking_lot::RwLock;
+
+use super::{
+    moves::{is_block_passable, FALL_ONE_BLOCK_COST, WALK_DIAGONALLY_COST, WALK_ONE_BLOCK_COST},
+    Goal, Node,
+};
+
+/// The cost of moving one block in a straight line the cheapest way we can,
+/// which is falling straight down.
+const MIN_COST_PER_BLOCK: f32 = FALL_ONE_BLOCK_COST;
+
+/// The height of a player's eyes when they're standing.
+const EYE_HEIGHT: f64 = 1.62;
+
+/// The lowest possible cost of walking by this many blocks horizontally,
+/// walking diagonally for as long as we can and then straight.
+fn xz_heuristic(dx: f32, dz: f32) -> f32 {
+    let dx = dx.abs();
+    let dz = dz.abs();
+    let (straight, diagonal) = if dx > dz {
+        (dx - dz, dz)
+    } else {
+        (dz - dx, dx)
+    };
+    straight * WALK_ONE_BLOCK_COST + diagonal * WALK_DIAGONALLY_COST
+}
+
+/// The lowest possible cost of going up or down by this many blocks.
+fn y_heuristic(dy: f32) -> f32 {
+    dy.abs() * FALL_ONE_BLOCK_COST
+}
+
+/// Stand at an exact block position.
+pub struct BlockPosGoal {
+    pub pos: BlockPos,
+}
+impl Goal for BlockPosGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        let dx = (self.pos.x - n.pos.x) as f32;
+        let dy = (self.pos.y - n.pos.y) as f32;
+        let dz = (self.pos.z - n.pos.z) as f32;
+        xz_heuristic(dx, dz).max(y_heuristic(dy))
+    }
+    fn success(&self, n: &Node) -> bool {
+        n.pos == self.pos
+    }
+}
+
+impl From<BlockPos> for BlockPosGoal {
+    fn from(pos: BlockPos) -> Self {
+        Self { pos }
+    }
+}
+
+/// Stand anywhere in a column, at any y coordinate.
+pub struct XZGoal {
+    pub x: i32,
+    pub z: i32,
+}
+impl Goal for XZGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        xz_heuristic((self.x - n.pos.x) as f32, (self.z - n.pos.z) as f32)
+    }
+    fn success(&self, n: &Node) -> bool {
+        n.pos.x == self.x && n.pos.z == self.z
+    }
+}
+
+/// Stand anywhere at a y coordinate.
+pub struct YGoal {
+    pub y: i32,
+}
+impl Goal for YGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        y_heuristic((self.y - n.pos.y) as f32)
+    }
+    fn success(&self, n: &Node) -> bool {
+        n.pos.y == self.y
+    }
+}
+
+/// Stand anywhere that's within a distance of a block position.
+pub struct RadiusGoal {
+    pub pos: BlockPos,
+    pub radius: f32,
+}
+impl Goal for RadiusGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        let distance = (n.pos.distance_to_sqr(&self.pos) as f32).sqrt();
+        (distance - self.radius).max(0.) * MIN_COST_PER_BLOCK
+    }
+    fn success(&self, n: &Node) -> bool {
+        n.pos.distance_to_sqr(&self.pos) as f32 <= self.radius * self.radius
+    }
+}
+
+/// Stand somewhere we can reach a block from and see it, so we can mine it or
+/// interact with it.
+pub struct ReachBlockGoal {
+    pub pos: BlockPos,
+    /// The world the block is in, which is used for checking whether there's
+    /// anything in the way.
+    pub world: Arc<RwLock<Instance>>,
+}
+impl ReachBlockGoal {
+    pub fn new(pos: BlockPos, world: Arc<RwLock<Instance>>) -> Self {
+        Self { pos, world }
+    }
+}
+impl Goal for ReachBlockGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        let distance = eye_position(n).distance_to_sqr(&self.pos.center()).sqrt() as f32;
+        (distance - MAX_INTERACTION_DISTANCE as f32).max(0.) * MIN_COST_PER_BLOCK
+    }
+    fn success(&self, n: &Node) -> bool {
+        let eye_position = eye_position(n);
+        eye_position.distance_to_sqr(&self.pos.center())
+            <= MAX_INTERACTION_DISTANCE * MAX_INTERACTION_DISTANCE
+            && has_line_of_sight(&self.world.read(), eye_position, &self.pos)
+    }
+}
+
+fn eye_position(n: &Node) -> Vec3 {
+    Vec3::new(
+        n.pos.x as f64 + 0.5,
+        n.pos.y as f64 + EYE_HEIGHT,
+        n.pos.z as f64 + 0.5,
+    )
+}
+
+/// Whether there aren't any blocks with a collision shape between the eye
+/// position and the center of the target block, not counting the target block
+/// itself.
+fn has_line_of_sight(world: &Instance, eye_position: Vec3, target: &BlockPos) -> bool {
+    let delta = target.center() - eye_position;
+    let distance = delta.length_sqr().sqrt();
+    // checking every tenth of a block is close enough to not skip any blocks
+    // unless the line only clips their corner
+    let steps = (distance * 10.).ceil() as usize;
+    for step in 0..steps {
+        let point = eye_position + delta * (step as f64 / steps as f64);
+        let pos = BlockPos::from(point);
+        if pos == *target {
+            return true;
+        }
+        if !is_block_passable(&pos, world) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Satisfied when any of the goals are.
+pub struct AnyGoal(pub Vec<Box<dyn Goal + Send + Sync>>);
+impl Goal for AnyGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        self.0
+            .iter()
+            .map(|goal| goal.heuristic(n))
+            .fold(f32::INFINITY, f32::min)
+    }
+    fn success(&self, n: &Node) -> bool {
+        self.0.iter().any(|goal| goal.success(n))
+    }
+}
+
+/// Satisfied when all of the goals are at the same time.
+pub struct AllGoal(pub Vec<Box<dyn Goal + Send + Sync>>);
+impl Goal for AllGoal {
+    fn heuristic(&self, n: &Node) -> f32 {
+        self.0
+            .iter()
+            .map(|goal| goal.heuristic(n))
+            .fold(0., f32::max)
+    }
+    fn success(&self, n: &Node) -> bool {
+        self.0.iter().all(|goal| goal.success(n))
+    }
+}
+
+/// Satisfied when the inner goal isn't, which is useful for getting away from
+/// something. For example, `InverseGoal(RadiusGoal { pos, radius: 16. })`
+/// runs at least 16 blocks away from `pos`.
+///
+/// There's no way to know how far the closest node outside of the inner goal
+/// is, so the heuristic is always 0. That means the search is uninformed and
+/// explores in every direction equally, which can be slow if the inner goal
+/// covers a large area.
+pub struct InverseGoal<T: Goal>(pub T);
+impl<T: Goal> Goal for InverseGoal<T> {
+    fn heuristic(&self, _n: &Node) -> f32 {
+        0.
+    }
+    fn success(&self, n: &Node) -> bool {
+        !self.0.success(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pathfinder::{moves::make_world, VerticalVel};
+
+    fn node_at(pos: BlockPos) -> Node {
+        Node {
+            pos,
+            vertical_vel: VerticalVel::None,
+        }
+    }
+
+    #[test]
+    fn test_block_pos_goal_heuristic() {
+        let goal = BlockPosGoal::from(BlockPos::new(0, 64, 0));
+        assert_eq!(goal.heuristic(&node_at(BlockPos::new(0, 64, 0))), 0.);
+        // walking 3 blocks straight
+        assert_eq!(
+            goal.heuristic(&node_at(BlockPos::new(0, 64, 3))),
+            3. * WALK_ONE_BLOCK_COST
+        );
+        // walking 2 blocks diagonally and then 1 block straight
+        assert_eq!(
+            goal.heuristic(&node_at(BlockPos::new(2, 64, 3))),
+            2. * WALK_DIAGONALLY_COST + WALK_ONE_BLOCK_COST
+        );
+        // digging straight down
+        assert_eq!(
+            goal.heuristic(&node_at(BlockPos::new(0, 70, 0))),
+            6. * FALL_ONE_BLOCK_COST
+        );
+    }
+
+    #[test]
+    fn test_composite_goals() {
+        let any = AnyGoal(vec![
+            Box::new(RadiusGoal {
+                pos: BlockPos::new(10, 64, 0),
+                radius: 4.,
+            }),
+            Box::new(RadiusGoal {
+                pos: BlockPos::new(-10, 64, 0),
+                radius: 4.,
+            }),
+        ]);
+        assert!(any.success(&node_at(BlockPos::new(7, 64, 0))));
+        assert!(any.success(&node_at(BlockPos::new(-7, 64, 0))));
+        assert!(!any.success(&node_at(BlockPos::new(0, 64, 0))));
+
+        let all = AllGoal(vec![
+            Box::new(XZGoal { x: 1, z: 2 }),
+            Box::new(YGoal { y: 3 }),
+        ]);
+        assert!(all.success(&node_at(BlockPos::new(1, 3, 2))));
+        assert!(!all.success(&node_at(BlockPos::new(1, 4, 2))));
+
+        let inverse = InverseGoal(RadiusGoal {
+            pos: BlockPos::new(0, 64, 0),
+            radius: 16.,
+        });
+        assert!(!inverse.success(&node_at(BlockPos::new(5, 64, 5))));
+        assert!(inverse.success(&node_at(BlockPos::new(20, 64, 0))));
+        assert_eq!(inverse.heuristic(&node_at(BlockPos::new(5, 64, 5))), 0.);
+    }
+
+    #[test]
+    fn test_reach_block_goal_line_of_sight() {
+        let (partial_world, world) = make_world(&[BlockPos::new(0, 64, 0)]);
+        let world_lock = Arc::new(RwLock::new(world));
+
+        let goal = ReachBlockGoal::new(BlockPos::new(0, 64, 0), world_lock.clone());
+        assert!(goal.success(&node_at(BlockPos::new(0, 64, 3))));
+        // there's a wall between us and the block
+        partial_world.chunks.set_block_state(
+            &BlockPos::new(0, 65, 1),
+            azalea_registry::Block::Stone.into(),
+            &mut world_lock.write().chunks,
+        );
+        assert!(!goal.success(&node_at(BlockPos::new(0, 64, 3))));
+        // too far away
+        assert!(!goal.success(&node_at(BlockPos::new(0, 64, 10))));
+    }
+}
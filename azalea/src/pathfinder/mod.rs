@@ -1,3 +1,4 @@
+pub mod goals;
 mod moves;
 mod mtdstarlite;
 
@@ -10,25 +11,51 @@ use crate::ecs::{
     entity::Entity,
     event::{EventReader, EventWriter},
     query::{With, Without},
-    schedule::IntoSystemConfig,
+    schedule::{IntoSystemConfig, IntoSystemConfigs},
     system::{Commands, Query, Res},
 };
+use azalea_client::disconnect::DisconnectEvent;
+use azalea_client::inventory::{Inventory, SetSelectedHotbarSlotEvent};
+use azalea_client::packet_handling::PacketEvent;
+use azalea_client::{
+    interact::{BlockInteractEvent, PlaceBlockEvent},
+    mining::MineTarget,
+};
 use azalea_client::{StartSprintEvent, StartWalkEvent};
-use azalea_core::{BlockPos, CardinalDirection};
+use azalea_core::{BlockPos, Slot, Vec3};
 use azalea_physics::PhysicsSet;
+use azalea_protocol::packets::game::ClientboundGamePacket;
+use azalea_registry::Item;
 use azalea_world::entity::metadata::Player;
 use azalea_world::entity::Local;
 use azalea_world::{
-    entity::{Physics, Position, WorldName},
-    InstanceContainer,
+    entity::{ActiveEffects, Physics, Position, WorldName},
+    Instance, InstanceContainer,
 };
 use bevy_tasks::{AsyncComputeTaskPool, Task};
 use futures_lite::future;
-use log::{debug, error};
-use mtdstarlite::Edge;
-pub use mtdstarlite::MTDStarLite;
-use std::collections::VecDeque;
+pub use goals::BlockPosGoal;
+use log::{debug, error, warn};
+use moves::{MiningCosts, Move, MoveContext, ParkourSimulator};
+use mtdstarlite::{ChangedEdge, Edge};
+pub use mtdstarlite::{MTDStarLite, Path, SearchError};
+use parking_lot::{Mutex, RwLock};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::oneshot;
+
+/// How many ticks we can go without reaching the next node in the path
+/// before we decide that we're stuck and look for a different path. Ticks
+/// spent mining don't count.
+const STUCK_TICKS: u32 = 100;
+/// How far away we can get from the line between the node we're coming from
+/// and the one we're going to, squared, before we decide that we're not on the
+/// path anymore.
+const MAX_DEVIATION_SQR: f64 = 2. * 2.;
 
 #[derive(Clone, Default)]
 pub struct PathfinderPlugin;
@@ -36,6 +63,8 @@ impl Plugin for PathfinderPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<GotoEvent>()
             .add_event::<PathFoundEvent>()
+            .add_event::<StopPathfindingEvent>()
+            .add_event::<GotoResultEvent>()
             .add_system(
                 // Adding `.in_schedule(CoreSchedule::FixedUpdate)` makes a system run every
                 // Minecraft tick (every 50 milliseconds).
@@ -43,10 +72,20 @@ impl Plugin for PathfinderPlugin {
                     .in_schedule(CoreSchedule::FixedUpdate)
                     .before(PhysicsSet),
             )
-            .add_system(goto_listener)
-            .add_system(add_default_pathfinder)
-            .add_system(handle_tasks.before(path_found_listener))
-            .add_system(path_found_listener);
+            // the systems that start searches have to run after we check
+            // whether the previous search finished, so its `ComputePath`
+            // doesn't get removed after the new one is inserted
+            .add_systems(
+                (
+                    handle_tasks,
+                    handle_stop_pathfinding_event,
+                    handle_disconnect_event,
+                    goto_listener,
+                    handle_block_updates,
+                )
+                    .chain(),
+            )
+            .add_system(add_default_pathfinder);
     }
 }
 
@@ -54,28 +93,225 @@ impl Plugin for PathfinderPlugin {
 #[derive(Component, Default)]
 pub struct Pathfinder {
     pub path: VecDeque<Node>,
+    /// The last node in the path that we reached, which is where the move to
+    /// the first node in `path` starts from. Until we reach the first node,
+    /// this is where the search for the path started.
+    pub last_node: Option<Node>,
+    /// Whether `path` only goes as close to the goal as we could get. We look
+    /// for the rest of the path once we get to the end of it.
+    pub is_path_partial: bool,
+    /// The goal we're going to, if any.
+    goal: Option<Arc<dyn Goal + Send + Sync>>,
+    /// The search for the goal we're going to, which we keep so we can update
+    /// the path when something changes instead of starting over.
+    search: Option<Arc<Search>>,
+    /// The moves that the search chose for getting from one node in `path`
+    /// to the next, so we don't have to figure out which one is cheapest
+    /// again while we're following it.
+    path_moves: HashMap<(Node, Node), PathMove>,
+    /// Edges that we got stuck on since the search last looked for a path.
+    changed_edges: Vec<ChangedEdge<Node, f32>>,
+    /// Blocks that changed since the search last looked for a path. The
+    /// search rechecks the moves that look at them before it looks again.
+    changed_blocks: HashSet<BlockPos>,
+    /// How many ticks we've spent trying to get to the next node in the path.
+    ticks_without_progress: u32,
+    /// The door, fence gate or trapdoor we last clicked to open, so we don't
+    /// click it again and close it before the server tells us it opened.
+    opened_door: Option<BlockPos>,
+    /// The goals that someone is awaiting [`PathfinderClientExt::goto`] for,
+    /// and where to send the result once we stop going to them.
+    goto_waiters: Vec<(Arc<dyn Goal + Send + Sync>, oneshot::Sender<GotoResult>)>,
+}
+
+impl Pathfinder {
+    /// Forget about the goal and the path to it.
+    fn reset(&mut self) {
+        *self = Self {
+            goto_waiters: std::mem::take(&mut self.goto_waiters),
+            ..Default::default()
+        };
+    }
+}
+
+/// A component with the settings for how a bot is allowed to get to its goal.
+/// You can change them with [`PathfinderClientExt::set_pathfinder_settings`].
+#[derive(Component, Clone, Debug)]
+pub struct PathfinderSettings {
+    /// Whether the bot can break blocks that are in its way.
+    pub allow_breaking_blocks: bool,
+    /// Whether the bot can place blocks to pillar up and bridge across gaps.
+    pub allow_placing_blocks: bool,
+    /// The blocks that the bot is allowed to place. They have to be in its
+    /// hotbar to be used.
+    pub scaffolding_blocks: Vec<Item>,
+    /// The most nodes the pathfinder will look at before giving up, or `None`
+    /// for no limit.
+    pub max_search_nodes: Option<usize>,
+    /// How long the pathfinder will look for a path before giving up, or
+    /// `None` for no limit.
+    pub search_timeout: Option<Duration>,
+    /// Whether the bot should go as close to the goal as it can if it can't
+    /// find a path all the way there. It looks for the rest of the path once
+    /// it gets there, and only gives up once it can't get any closer, so with
+    /// this enabled [`GotoError::TimedOut`] is only returned if the search
+    /// ran out of time or nodes without finding anywhere closer to go.
+    pub allow_partial_paths: bool,
+    /// Whether the bot can swim through water.
+    pub allow_swimming: bool,
+    /// Whether the bot can climb up and down ladders and vines.
+    pub allow_climbing: bool,
+    /// Whether the bot can open wooden doors, fence gates and trapdoors that
+    /// are in its way instead of breaking them.
+    pub allow_opening_doors: bool,
+    /// Whether the bot can sprint and jump over gaps that are up to 3 blocks
+    /// wide.
+    pub allow_parkour: bool,
+    /// The furthest the bot is allowed to fall, in blocks. Falls of more than
+    /// 3 blocks hurt, so they're only taken if they save a lot of time, unless
+    /// they land in water.
+    pub max_fall_distance: u32,
+}
+
+impl Default for PathfinderSettings {
+    fn default() -> Self {
+        Self {
+            allow_breaking_blocks: true,
+            allow_placing_blocks: true,
+            scaffolding_blocks: vec![
+                Item::Dirt,
+                Item::Cobblestone,
+                Item::CobbledDeepslate,
+                Item::Netherrack,
+                Item::Stone,
+                Item::Andesite,
+                Item::Diorite,
+                Item::Granite,
+            ],
+            max_search_nodes: Some(100_000),
+            search_timeout: Some(Duration::from_secs(2)),
+            allow_partial_paths: true,
+            allow_swimming: true,
+            allow_climbing: true,
+            allow_opening_doors: true,
+            allow_parkour: true,
+            max_fall_distance: 3,
+        }
+    }
+}
+
+impl PathfinderSettings {
+    /// The first hotbar slot, from 0 to 8, that has one of our scaffolding
+    /// blocks in it.
+    pub fn scaffolding_hotbar_slot(&self, inventory: &Inventory) -> Option<u8> {
+        let hotbar = &inventory.inventory_menu.slots[inventory.inventory_menu.hotbar_slots()];
+        hotbar
+            .iter()
+            .position(|slot| match slot {
+                Slot::Present(item) => Item::try_from(item.id)
+                    .is_ok_and(|item| self.scaffolding_blocks.contains(&item)),
+                Slot::Empty => false,
+            })
+            .map(|slot| slot as u8)
+    }
 }
+
 #[allow(clippy::type_complexity)]
 fn add_default_pathfinder(
     mut commands: Commands,
-    mut query: Query<Entity, (Without<Pathfinder>, With<Local>, With<Player>)>,
+    mut query: Query<
+        (Entity, Option<&PathfinderSettings>),
+        (Without<Pathfinder>, With<Local>, With<Player>),
+    >,
 ) {
-    for entity in &mut query {
-        commands.entity(entity).insert(Pathfinder::default());
+    for (entity, settings) in &mut query {
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(Pathfinder::default());
+        if settings.is_none() {
+            entity_commands.insert(PathfinderSettings::default());
+        }
     }
 }
 
 pub trait PathfinderClientExt {
-    fn goto(&self, goal: impl Goal + Send + Sync + 'static);
+    /// Start going to the goal, and stop going to the previous one if we were
+    /// going somewhere else.
+    ///
+    /// The returned future resolves once we get to the goal or give up, but
+    /// we start going there as soon as this is called, so you don't have to
+    /// await it if you don't care when we get there. A [`GotoResultEvent`] is
+    /// also sent when we stop going to the goal.
+    ///
+    /// ```no_run
+    /// # use azalea::prelude::*;
+    /// # use azalea::pathfinder::goals::BlockPosGoal;
+    /// # use azalea_core::BlockPos;
+    /// # async fn example(bot: Client) {
+    /// match bot.goto(BlockPosGoal::from(BlockPos::new(0, 70, 0))).await {
+    ///     Ok(()) => bot.chat("I'm here!"),
+    ///     Err(err) => bot.chat(&format!("I couldn't get there: {err}")),
+    /// }
+    /// # }
+    /// ```
+    fn goto(
+        &self,
+        goal: impl Goal + Send + Sync + 'static,
+    ) -> Pin<Box<dyn Future<Output = Result<(), GotoError>> + Send>>;
+    /// Stop going to the goal that we're going to, if any.
+    fn stop_pathfinding(&self);
+    /// Change how the bot is allowed to get to its goal, like whether it can
+    /// break and place blocks. This only affects paths that are found after
+    /// it's called.
+    fn set_pathfinder_settings(&self, settings: PathfinderSettings);
 }
 
 impl PathfinderClientExt for azalea_client::Client {
-    fn goto(&self, goal: impl Goal + Send + Sync + 'static) {
-        self.ecs.lock().send_event(GotoEvent {
+    fn goto(
+        &self,
+        goal: impl Goal + Send + Sync + 'static,
+    ) -> Pin<Box<dyn Future<Output = Result<(), GotoError>> + Send>> {
+        let goal: Arc<dyn Goal + Send + Sync> = Arc::new(goal);
+        let (sender, receiver) = oneshot::channel();
+        {
+            let mut ecs = self.ecs.lock();
+            let mut entity = ecs.entity_mut(self.entity);
+            if !entity.contains::<Pathfinder>() {
+                // add_default_pathfinder hasn't run for this entity yet
+                entity.insert(Pathfinder::default());
+                if !entity.contains::<PathfinderSettings>() {
+                    entity.insert(PathfinderSettings::default());
+                }
+            }
+            entity
+                .get_mut::<Pathfinder>()
+                .expect("We just made sure the entity has a pathfinder")
+                .goto_waiters
+                .push((goal.clone(), sender));
+            ecs.send_event(GotoEvent {
+                entity: self.entity,
+                goal,
+            });
+        }
+
+        Box::pin(async move {
+            receiver
+                .await
+                // the sender is dropped without sending anything if the
+                // entity is despawned
+                .unwrap_or(GotoResult::Disconnected)
+                .into_goto_result()
+        })
+    }
+
+    fn stop_pathfinding(&self) {
+        self.ecs.lock().send_event(StopPathfindingEvent {
             entity: self.entity,
-            goal: Arc::new(goal),
         });
     }
+
+    fn set_pathfinder_settings(&self, settings: PathfinderSettings) {
+        self.ecs.lock().entity_mut(self.entity).insert(settings);
+    }
 }
 pub struct GotoEvent {
     pub entity: Entity,
@@ -84,23 +320,391 @@ pub struct GotoEvent {
 pub struct PathFoundEvent {
     pub entity: Entity,
     pub path: VecDeque<Node>,
+    /// Whether the path only goes as close to the goal as we could get,
+    /// because we couldn't find a path to the goal itself.
+    pub partial: bool,
+}
+/// Stop going to the goal that the entity is going to. You should usually
+/// use [`PathfinderClientExt::stop_pathfinding`] instead of sending this
+/// yourself.
+pub struct StopPathfindingEvent {
+    pub entity: Entity,
+}
+/// Sent when an entity stops going to its goal, with the reason why.
+#[derive(Clone, Debug)]
+pub struct GotoResultEvent {
+    pub entity: Entity,
+    pub result: GotoResult,
 }
 
+/// How going to a goal ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GotoResult {
+    /// We're at a node that satisfies the goal.
+    Reached,
+    /// We couldn't find a path to the goal, or to anywhere closer to it.
+    Unreachable(SearchError),
+    /// We stopped because [`PathfinderClientExt::stop_pathfinding`] was
+    /// called.
+    Cancelled,
+    /// We stopped because we started going to a different goal.
+    Interrupted,
+    /// We got disconnected from the server.
+    Disconnected,
+}
+
+impl GotoResult {
+    /// What [`PathfinderClientExt::goto`] returns when going to the goal ended
+    /// like this.
+    fn into_goto_result(self) -> Result<(), GotoError> {
+        match self {
+            GotoResult::Reached => Ok(()),
+            GotoResult::Unreachable(SearchError::NoPath) => Err(GotoError::NoPath),
+            GotoResult::Unreachable(SearchError::TimedOut | SearchError::TooManyNodes) => {
+                Err(GotoError::TimedOut)
+            }
+            GotoResult::Cancelled => Err(GotoError::Cancelled),
+            GotoResult::Interrupted => Err(GotoError::Interrupted),
+            GotoResult::Disconnected => Err(GotoError::Disconnected),
+        }
+    }
+}
+
+/// Why [`PathfinderClientExt::goto`] didn't get to the goal.
+#[derive(Error, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GotoError {
+    #[error("There's no path to the goal")]
+    NoPath,
+    /// We gave up looking for a path because it was taking too long or we
+    /// looked at too many nodes. You can change the limits with
+    /// [`PathfinderSettings`].
+    #[error("Gave up looking for a path to the goal")]
+    TimedOut,
+    #[error("Disconnected before getting to the goal")]
+    Disconnected,
+    /// [`PathfinderClientExt::goto`] was called again before we got to the
+    /// goal.
+    #[error("Started going to a different goal")]
+    Interrupted,
+    /// [`PathfinderClientExt::stop_pathfinding`] was called before we got to
+    /// the goal.
+    #[error("Stopped pathfinding")]
+    Cancelled,
+}
+
+/// The search that's running in the background for this entity.
 #[derive(Component)]
-pub struct ComputePath(Task<Option<PathFoundEvent>>);
+pub struct ComputePath(Task<Result<FoundPath, SearchError>>);
+
+/// A path that a search found, and the moves it takes.
+struct FoundPath {
+    start: Node,
+    path: Path<Node>,
+    moves: HashMap<(Node, Node), PathMove>,
+}
+
+/// The move that a search chose for going from one node to the next.
+#[derive(Clone, Copy, Debug)]
+struct PathMove {
+    /// Where the move is in [`SearchContext::moves`].
+    index: usize,
+    /// How much the move cost when the path was found.
+    cost: f32,
+}
+
+type NodeFn<T> = Box<dyn Fn(&Node) -> T + Send + Sync>;
+type EdgesFn = NodeFn<Vec<Edge<Node, f32>>>;
+type PathSearch = MTDStarLite<Node, f32, NodeFn<f32>, EdgesFn, EdgesFn, NodeFn<bool>>;
+
+/// A search for a goal that can be continued from a different start or with
+/// different edge costs.
+struct Search {
+    pf: Mutex<PathSearch>,
+    context: Arc<SearchContext>,
+}
+
+/// Everything we need to know to figure out how much it costs to move between
+/// nodes.
+struct SearchContext {
+    world_lock: Arc<RwLock<Instance>>,
+    settings: PathfinderSettings,
+    mining_costs: MiningCosts,
+    parkour: ParkourSimulator,
+    has_scaffolding: bool,
+    moves: Vec<Box<dyn Move>>,
+    /// The edges that we got stuck on, which we don't try to go along again.
+    blocked_edges: Mutex<HashSet<(Node, Node)>>,
+}
+
+impl SearchContext {
+    fn new(
+        world_lock: Arc<RwLock<Instance>>,
+        settings: PathfinderSettings,
+        inventory: &Inventory,
+        active_effects: &ActiveEffects,
+    ) -> Self {
+        Self {
+            world_lock,
+            has_scaffolding: settings.scaffolding_hotbar_slot(inventory).is_some(),
+            mining_costs: MiningCosts::new(inventory, active_effects.clone()),
+            parkour: ParkourSimulator::default(),
+            moves: moves::all_moves(&settings),
+            settings,
+            blocked_edges: Mutex::new(HashSet::new()),
+        }
+    }
 
+    fn move_context<'a>(&'a self, world: &'a Instance) -> MoveContext<'a> {
+        MoveContext {
+            world,
+            settings: &self.settings,
+            mining_costs: &self.mining_costs,
+            parkour: &self.parkour,
+            has_scaffolding: self.has_scaffolding,
+        }
+    }
+
+    /// The cheapest of our moves that goes from one node to the other, and how
+    /// much it costs.
+    fn cheapest_move(&self, ctx: &MoveContext, from: &Node, to: &Node) -> Option<PathMove> {
+        self.moves
+            .iter()
+            .enumerate()
+            .filter(|(_, movement)| movement.next_node(from) == *to)
+            .map(|(index, movement)| PathMove {
+                index,
+                cost: movement.cost(ctx, from),
+            })
+            .min_by(|a, b| a.cost.total_cmp(&b.cost))
+    }
+
+    /// The cheapest move for every step of a path that starts at `start`.
+    fn path_moves(&self, start: Node, nodes: &[Node]) -> HashMap<(Node, Node), PathMove> {
+        let world = self.world_lock.read();
+        let ctx = self.move_context(&world);
+        [start]
+            .iter()
+            .chain(nodes)
+            .zip(nodes)
+            .filter_map(|(from, to)| {
+                let path_move = self.cheapest_move(&ctx, from, to)?;
+                Some(((*from, *to), path_move))
+            })
+            .collect()
+    }
+
+    /// Calculate the cost of the cheapest move from one node to the other.
+    fn edge(&self, ctx: &MoveContext, from: &Node, to: &Node) -> Edge<Node, f32> {
+        let cost = if self.blocked_edges.lock().contains(&(*from, *to)) {
+            f32::INFINITY
+        } else {
+            match self.cheapest_move(ctx, from, to) {
+                Some(path_move) => path_move.cost,
+                None => f32::INFINITY,
+            }
+        };
+        Edge { target: *to, cost }
+    }
+
+    /// The nodes that our moves can take us to from the node, without any
+    /// duplicates.
+    fn neighbors(&self, node: &Node) -> Vec<Node> {
+        let mut neighbors = Vec::new();
+        for movement in &self.moves {
+            let target = movement.next_node(node);
+            if !neighbors.contains(&target) {
+                neighbors.push(target);
+            }
+        }
+        neighbors
+    }
+
+    fn successors(&self, node: &Node) -> Vec<Edge<Node, f32>> {
+        let world = self.world_lock.read();
+        let ctx = self.move_context(&world);
+        self.neighbors(node)
+            .iter()
+            .map(|target| self.edge(&ctx, node, target))
+            .collect()
+    }
+
+    /// The nodes that we can get to this node from, and the cost of getting
+    /// here from them.
+    fn predecessors(&self, node: &Node) -> Vec<Edge<Node, f32>> {
+        let world = self.world_lock.read();
+        let ctx = self.move_context(&world);
+        let mut predecessors = Vec::new();
+        for movement in &self.moves {
+            let from = Node {
+                pos: node.pos - movement.offset(),
+                vertical_vel: VerticalVel::None,
+            };
+            if movement.next_node(&from) == *node && !predecessors.contains(&from) {
+                predecessors.push(from);
+            }
+        }
+        predecessors
+            .into_iter()
+            .map(|from| Edge {
+                target: from,
+                cost: self.edge(&ctx, &from, node).cost,
+            })
+            .collect()
+    }
+
+    /// Recalculate the costs of the edges that could have been affected by the
+    /// blocks at the positions changing. Edges that start at nodes that
+    /// `is_visited` returns false for are skipped, since the search doesn't
+    /// care about them.
+    fn changed_edges(
+        &self,
+        positions: &HashSet<BlockPos>,
+        is_visited: impl Fn(&Node) -> bool,
+    ) -> Vec<ChangedEdge<Node, f32>> {
+        // only the moves that look at a block can be affected by it
+        let mut edges = HashSet::new();
+        for pos in positions {
+            self.parkour.forget(pos);
+            for movement in &self.moves {
+                for offset in movement.footprint() {
+                    let from = Node {
+                        pos: *pos - offset,
+                        vertical_vel: VerticalVel::None,
+                    };
+                    if is_visited(&from) {
+                        edges.insert((from, movement.next_node(&from)));
+                    }
+                }
+            }
+        }
+
+        let world = self.world_lock.read();
+        let ctx = self.move_context(&world);
+        edges
+            .into_iter()
+            .map(|(from, target)| ChangedEdge {
+                predecessor: from,
+                successor: target,
+                cost: self.edge(&ctx, &from, &target).cost,
+            })
+            .collect()
+    }
+
+    /// Stop the search from going along the edge, because we got stuck on it.
+    fn block_edge(&self, from: Node, to: Node) -> ChangedEdge<Node, f32> {
+        self.blocked_edges.lock().insert((from, to));
+        ChangedEdge {
+            predecessor: from,
+            successor: to,
+            cost: f32::INFINITY,
+        }
+    }
+}
+
+/// Clear the path and start looking for a new one from `start` in the
+/// background. This reuses as much of the previous search for the goal as it
+/// can.
+fn search_from(commands: &mut Commands, entity: Entity, pathfinder: &mut Pathfinder, start: Node) {
+    let Some(search) = pathfinder.search.clone() else {
+        return;
+    };
+    pathfinder.path.clear();
+    pathfinder.path_moves.clear();
+    pathfinder.last_node = None;
+    pathfinder.ticks_without_progress = 0;
+    pathfinder.opened_door = None;
+    let mut changed_edges = std::mem::take(&mut pathfinder.changed_edges);
+    let changed_blocks = std::mem::take(&mut pathfinder.changed_blocks);
+
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        debug!("start: {start:?}");
+        let mut pf = search.pf.lock();
+        pf.set_start(start);
+        changed_edges.extend(
+            search
+                .context
+                .changed_edges(&changed_blocks, |node| pf.has_visited(node)),
+        );
+        pf.update_edge_costs(changed_edges);
+
+        let start_time = Instant::now();
+        let result = pf.find_path();
+        debug!("path: {result:?}");
+        debug!("time: {:?}", start_time.elapsed());
+        result.map(|path| FoundPath {
+            start,
+            moves: search.context.path_moves(start, &path.nodes),
+            path,
+        })
+    });
+    commands.entity(entity).insert(ComputePath(task));
+}
+
+/// Stop going to the goal we're going to, if any, and send a
+/// [`GotoResultEvent`] if we were going somewhere.
+fn finish_pathfinding(
+    commands: &mut Commands,
+    entity: Entity,
+    pathfinder: &mut Pathfinder,
+    result: GotoResult,
+    walk_events: &mut EventWriter<StartWalkEvent>,
+    goto_result_events: &mut EventWriter<GotoResultEvent>,
+) {
+    if pathfinder.search.is_none() && pathfinder.path.is_empty() {
+        return;
+    }
+    if let Some(goal) = pathfinder.goal.take() {
+        goto_result_events.send(GotoResultEvent { entity, result });
+        let (finished, waiting) = std::mem::take(&mut pathfinder.goto_waiters)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(waiter_goal, _)| Arc::ptr_eq(waiter_goal, &goal));
+        pathfinder.goto_waiters = waiting;
+        for (_, sender) in finished {
+            // it's fine if nobody is awaiting the result anymore
+            let _ = sender.send(result);
+        }
+    }
+    pathfinder.reset();
+    commands
+        .entity(entity)
+        .remove::<ComputePath>()
+        .remove::<MineTarget>();
+    walk_events.send(StartWalkEvent {
+        entity,
+        direction: WalkDirection::None,
+    });
+}
+
+#[allow(clippy::type_complexity)]
 fn goto_listener(
     mut commands: Commands,
     mut events: EventReader<GotoEvent>,
-    mut query: Query<(&Position, &WorldName)>,
+    mut query: Query<(
+        &mut Pathfinder,
+        &Position,
+        &WorldName,
+        &Inventory,
+        &ActiveEffects,
+        Option<&PathfinderSettings>,
+    )>,
     world_container: Res<InstanceContainer>,
+    mut walk_events: EventWriter<StartWalkEvent>,
+    mut goto_result_events: EventWriter<GotoResultEvent>,
 ) {
-    let thread_pool = AsyncComputeTaskPool::get();
-
     for event in events.iter() {
-        let (position, world_name) = query
+        let (mut pathfinder, position, world_name, inventory, active_effects, settings) = query
             .get_mut(event.entity)
             .expect("Called goto on an entity that's not in the world");
+        // we're going somewhere else now
+        finish_pathfinding(
+            &mut commands,
+            event.entity,
+            &mut pathfinder,
+            GotoResult::Interrupted,
+            &mut walk_events,
+            &mut goto_result_events,
+        );
+
         let start = Node {
             pos: BlockPos::from(position),
             vertical_vel: VerticalVel::None,
@@ -109,156 +713,541 @@ fn goto_listener(
         let world_lock = world_container
             .get(world_name)
             .expect("Entity tried to pathfind but the entity isn't in a valid world");
-        let end = event.goal.goal_node();
-
-        let goal = event.goal.clone();
-        let entity = event.entity;
-
-        let task = thread_pool.spawn(async move {
-            debug!("start: {start:?}, end: {end:?}");
-
-            let possible_moves: Vec<&dyn moves::Move> = vec![
-                &moves::ForwardMove(CardinalDirection::North),
-                &moves::ForwardMove(CardinalDirection::East),
-                &moves::ForwardMove(CardinalDirection::South),
-                &moves::ForwardMove(CardinalDirection::West),
-                //
-                &moves::AscendMove(CardinalDirection::North),
-                &moves::AscendMove(CardinalDirection::East),
-                &moves::AscendMove(CardinalDirection::South),
-                &moves::AscendMove(CardinalDirection::West),
-                //
-                &moves::DescendMove(CardinalDirection::North),
-                &moves::DescendMove(CardinalDirection::East),
-                &moves::DescendMove(CardinalDirection::South),
-                &moves::DescendMove(CardinalDirection::West),
-                //
-                &moves::DiagonalMove(CardinalDirection::North),
-                &moves::DiagonalMove(CardinalDirection::East),
-                &moves::DiagonalMove(CardinalDirection::South),
-                &moves::DiagonalMove(CardinalDirection::West),
-            ];
-
-            let successors = |node: &Node| {
-                let mut edges = Vec::new();
-
-                let world = world_lock.read();
-                for possible_move in &possible_moves {
-                    edges.push(Edge {
-                        target: possible_move.next_node(node),
-                        cost: possible_move.cost(&world, node),
-                    });
-                }
-                edges
-            };
 
-            let mut pf = MTDStarLite::new(
-                start,
-                end,
-                |n| goal.heuristic(n),
-                successors,
-                successors,
-                |n| goal.success(n),
-            );
+        let settings = settings.cloned().unwrap_or_default();
+        let context = Arc::new(SearchContext::new(
+            world_lock,
+            settings,
+            inventory,
+            active_effects,
+        ));
 
-            let start_time = std::time::Instant::now();
-            let p = pf.find_path();
-            let end_time = std::time::Instant::now();
-            debug!("path: {p:?}");
-            debug!("time: {:?}", end_time - start_time);
-
-            // convert the Option<Vec<Node>> to a VecDeque<Node>
-            if let Some(p) = p {
-                let path = p.into_iter().collect::<VecDeque<_>>();
-                // commands.entity(event.entity).insert(Pathfinder { path: p });
-                Some(PathFoundEvent { entity, path })
-            } else {
-                error!("no path found");
-                None
-            }
-        });
+        let heuristic = {
+            let goal = event.goal.clone();
+            move |n: &Node| goal.heuristic(n)
+        };
+        let successors = {
+            let context = context.clone();
+            move |n: &Node| context.successors(n)
+        };
+        let predecessors = {
+            let context = context.clone();
+            move |n: &Node| context.predecessors(n)
+        };
+        let success = {
+            let goal = event.goal.clone();
+            move |n: &Node| goal.success(n)
+        };
+        let mut pf: PathSearch = MTDStarLite::new(
+            start,
+            Box::new(heuristic),
+            Box::new(successors),
+            Box::new(predecessors),
+            Box::new(success),
+        );
+        pf.max_expansions = context.settings.max_search_nodes;
+        pf.timeout = context.settings.search_timeout;
+        pf.allow_partial_paths = context.settings.allow_partial_paths;
 
-        commands.spawn(ComputePath(task));
+        pathfinder.goal = Some(event.goal.clone());
+        pathfinder.search = Some(Arc::new(Search {
+            pf: Mutex::new(pf),
+            context,
+        }));
+        search_from(&mut commands, event.entity, &mut pathfinder, start);
     }
 }
 
-// poll the tasks and send the PathFoundEvent if they're done
+fn handle_stop_pathfinding_event(
+    mut commands: Commands,
+    mut events: EventReader<StopPathfindingEvent>,
+    mut query: Query<&mut Pathfinder>,
+    mut walk_events: EventWriter<StartWalkEvent>,
+    mut goto_result_events: EventWriter<GotoResultEvent>,
+) {
+    for event in events.iter() {
+        let Ok(mut pathfinder) = query.get_mut(event.entity) else {
+            continue;
+        };
+        finish_pathfinding(
+            &mut commands,
+            event.entity,
+            &mut pathfinder,
+            GotoResult::Cancelled,
+            &mut walk_events,
+            &mut goto_result_events,
+        );
+    }
+}
+
+fn handle_disconnect_event(
+    mut commands: Commands,
+    mut events: EventReader<DisconnectEvent>,
+    mut query: Query<&mut Pathfinder>,
+    mut walk_events: EventWriter<StartWalkEvent>,
+    mut goto_result_events: EventWriter<GotoResultEvent>,
+) {
+    for event in events.iter() {
+        let Ok(mut pathfinder) = query.get_mut(event.entity) else {
+            continue;
+        };
+        finish_pathfinding(
+            &mut commands,
+            event.entity,
+            &mut pathfinder,
+            GotoResult::Disconnected,
+            &mut walk_events,
+            &mut goto_result_events,
+        );
+    }
+}
+
+// poll the tasks and use the path once they're done
 fn handle_tasks(
     mut commands: Commands,
-    mut transform_tasks: Query<(Entity, &mut ComputePath)>,
+    mut query: Query<(Entity, &mut ComputePath, &mut Pathfinder, &Position)>,
     mut path_found_events: EventWriter<PathFoundEvent>,
+    mut walk_events: EventWriter<StartWalkEvent>,
+    mut goto_result_events: EventWriter<GotoResultEvent>,
 ) {
-    for (entity, mut task) in &mut transform_tasks {
-        if let Some(optional_path_found_event) = future::block_on(future::poll_once(&mut task.0)) {
-            if let Some(path_found_event) = optional_path_found_event {
-                path_found_events.send(path_found_event);
-            }
+    for (entity, mut task, mut pathfinder, position) in &mut query {
+        let Some(result) = future::block_on(future::poll_once(&mut task.0)) else {
+            continue;
+        };
+        // Task is complete, so remove task component from entity
+        commands.entity(entity).remove::<ComputePath>();
 
-            // Task is complete, so remove task component from entity
-            commands.entity(entity).remove::<ComputePath>();
+        match result {
+            Ok(found) if found.path.nodes.is_empty() => {
+                // we're already at the goal
+                finish_pathfinding(
+                    &mut commands,
+                    entity,
+                    &mut pathfinder,
+                    GotoResult::Reached,
+                    &mut walk_events,
+                    &mut goto_result_events,
+                );
+            }
+            Ok(FoundPath { start, path, moves }) => {
+                // blocks that changed while we were searching could be in the
+                // way, and the search doesn't know about them yet
+                let is_blocked = moves
+                    .values()
+                    .any(|path_move| path_move.cost == f32::INFINITY);
+                if is_blocked && !pathfinder.changed_blocks.is_empty() {
+                    debug!(
+                        "blocks changed on the path while we were looking for it, looking again"
+                    );
+                    let current_node = Node {
+                        pos: BlockPos::from(position),
+                        vertical_vel: VerticalVel::None,
+                    };
+                    search_from(&mut commands, entity, &mut pathfinder, current_node);
+                    continue;
+                }
+                if path.partial {
+                    // we look for the rest of the path once we get to the end
+                    // of it, so this has to get us closer or we'd never stop
+                    let end = path
+                        .nodes
+                        .last()
+                        .expect("Paths that aren't empty have an end");
+                    if let Some(goal) = &pathfinder.goal {
+                        if goal.heuristic(end) >= goal.heuristic(&start) {
+                            error!("no path found: can't get any closer to the goal");
+                            finish_pathfinding(
+                                &mut commands,
+                                entity,
+                                &mut pathfinder,
+                                GotoResult::Unreachable(SearchError::NoPath),
+                                &mut walk_events,
+                                &mut goto_result_events,
+                            );
+                            continue;
+                        }
+                    }
+                    warn!("couldn't find a path to the goal, going as close as we can");
+                }
+                pathfinder.path = path.nodes.into_iter().collect();
+                pathfinder.path_moves = moves;
+                pathfinder.last_node = Some(start);
+                pathfinder.is_path_partial = path.partial;
+                pathfinder.ticks_without_progress = 0;
+                path_found_events.send(PathFoundEvent {
+                    entity,
+                    path: pathfinder.path.clone(),
+                    partial: path.partial,
+                });
+            }
+            Err(err) => {
+                error!("no path found: {err:?}");
+                finish_pathfinding(
+                    &mut commands,
+                    entity,
+                    &mut pathfinder,
+                    GotoResult::Unreachable(err),
+                    &mut walk_events,
+                    &mut goto_result_events,
+                );
+            }
         }
     }
 }
 
-// set the path for the target entity when we get the PathFoundEvent
-fn path_found_listener(mut events: EventReader<PathFoundEvent>, mut query: Query<&mut Pathfinder>) {
-    for event in events.iter() {
-        let mut pathfinder = query
-            .get_mut(event.entity)
-            .expect("Path found for an entity that doesn't have a pathfinder");
-        pathfinder.path = event.path.clone();
+/// Tell the searches about blocks that changed, and look for a new path if
+/// any of them made the path we're following more expensive.
+fn handle_block_updates(
+    mut commands: Commands,
+    mut packet_events: EventReader<PacketEvent>,
+    mut query: Query<(&mut Pathfinder, &Position, Option<&ComputePath>)>,
+    mut walk_events: EventWriter<StartWalkEvent>,
+) {
+    for event in packet_events.iter() {
+        let changed_blocks = match &event.packet {
+            ClientboundGamePacket::BlockUpdate(p) => HashSet::from([p.pos]),
+            ClientboundGamePacket::SectionBlocksUpdate(p) => p
+                .states
+                .iter()
+                .map(|state| p.section_pos + state.pos.clone())
+                .collect(),
+            _ => continue,
+        };
+        let Ok((mut pathfinder, position, compute_path)) = query.get_mut(event.entity) else {
+            continue;
+        };
+        let Some(search) = pathfinder.search.clone() else {
+            continue;
+        };
+        // figuring out which edges changed is slow, so the search does it in
+        // the background before it looks for a path again
+        pathfinder.changed_blocks.extend(&changed_blocks);
+
+        // if we're still looking for a path, we check whether it's blocked
+        // once it's found instead of starting over, or else the search might
+        // never finish while blocks keep changing
+        if compute_path.is_some() || !path_got_worse(&pathfinder, &search.context, &changed_blocks)
+        {
+            continue;
+        }
+        debug!("blocks changed on the path, looking for a new one");
+        walk_events.send(StartWalkEvent {
+            entity: event.entity,
+            direction: WalkDirection::None,
+        });
+        let current_node = Node {
+            pos: BlockPos::from(position),
+            vertical_vel: VerticalVel::None,
+        };
+        search_from(&mut commands, event.entity, &mut pathfinder, current_node);
     }
 }
 
+/// Whether any of the moves left in the path look at the blocks that changed,
+/// and are more expensive now. Moves getting cheaper is usually because we
+/// broke or placed a block for the path, so we don't care about that.
+fn path_got_worse(
+    pathfinder: &Pathfinder,
+    context: &SearchContext,
+    changed_blocks: &HashSet<BlockPos>,
+) -> bool {
+    let Some(last_node) = pathfinder.last_node else {
+        return false;
+    };
+    let world = context.world_lock.read();
+    let ctx = context.move_context(&world);
+    [last_node]
+        .iter()
+        .chain(&pathfinder.path)
+        .zip(&pathfinder.path)
+        .any(|(from, to)| {
+            let Some(path_move) = pathfinder.path_moves.get(&(*from, *to)) else {
+                return false;
+            };
+            let movement = &context.moves[path_move.index];
+            let is_affected = movement
+                .footprint()
+                .into_iter()
+                .any(|offset| changed_blocks.contains(&(from.pos + offset)));
+            if !is_affected {
+                return false;
+            }
+            // it might've been a jump that doesn't work anymore
+            context.parkour.forget_jump(&from.pos, &to.pos);
+            movement.cost(&ctx, from) > path_move.cost
+        })
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn tick_execute_path(
-    mut query: Query<(Entity, &mut Pathfinder, &Position, &Physics)>,
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut Pathfinder,
+        &PathfinderSettings,
+        &Position,
+        &Physics,
+        &Inventory,
+        &ActiveEffects,
+        &WorldName,
+        Option<&MineTarget>,
+    )>,
+    world_container: Res<InstanceContainer>,
     mut look_at_events: EventWriter<LookAtEvent>,
     mut sprint_events: EventWriter<StartSprintEvent>,
     mut walk_events: EventWriter<StartWalkEvent>,
     mut jump_events: EventWriter<JumpEvent>,
+    mut place_block_events: EventWriter<PlaceBlockEvent>,
+    mut block_interact_events: EventWriter<BlockInteractEvent>,
+    mut set_selected_hotbar_slot_events: EventWriter<SetSelectedHotbarSlotEvent>,
+    mut goto_result_events: EventWriter<GotoResultEvent>,
 ) {
-    for (entity, mut pathfinder, position, physics) in &mut query {
-        loop {
-            let Some(target) = pathfinder.path.front() else {
-                break;
-            };
-            let center = target.pos.center();
-            // println!("going to {center:?} (at {pos:?})", pos = bot.entity().pos());
-            look_at_events.send(LookAtEvent {
-                entity,
-                position: center,
-            });
+    for (
+        entity,
+        mut pathfinder,
+        settings,
+        position,
+        physics,
+        inventory,
+        active_effects,
+        world_name,
+        mine_target,
+    ) in &mut query
+    {
+        let Some(world_lock) = world_container.get(world_name) else {
+            continue;
+        };
+        let world = world_lock.read();
+
+        let current_node = Node {
+            pos: BlockPos::from(position),
+            vertical_vel: VerticalVel::None,
+        };
+
+        while let Some(target) = pathfinder.path.front().copied() {
             debug!(
                 "tick: pathfinder {entity:?}; going to {:?}; currently at {position:?}",
                 target.pos
             );
-            sprint_events.send(StartSprintEvent {
-                entity,
-                direction: SprintDirection::Forward,
-            });
-            // check if we should jump
-            if target.pos.y > position.y.floor() as i32 {
-                jump_events.send(JumpEvent(entity));
-            }
 
-            if target.is_reached(position, physics) {
-                // println!("reached target");
-                pathfinder.path.pop_front();
+            if target.is_reached(position, physics, &world) {
+                pathfinder.last_node = pathfinder.path.pop_front();
+                pathfinder.ticks_without_progress = 0;
+                pathfinder.opened_door = None;
                 if pathfinder.path.is_empty() {
-                    // println!("reached goal");
                     walk_events.send(StartWalkEvent {
                         entity,
                         direction: WalkDirection::None,
                     });
+                    if pathfinder.is_path_partial {
+                        // look for the rest of the path from here
+                        search_from(&mut commands, entity, &mut pathfinder, target);
+                    } else {
+                        finish_pathfinding(
+                            &mut commands,
+                            entity,
+                            &mut pathfinder,
+                            GotoResult::Reached,
+                            &mut walk_events,
+                            &mut goto_result_events,
+                        );
+                    }
                 }
                 // tick again, maybe we already reached the next node!
-            } else {
+                continue;
+            }
+
+            let from = pathfinder.last_node.unwrap_or(current_node);
+
+            if let Some(search) = pathfinder.search.clone() {
+                if mine_target.is_none() {
+                    pathfinder.ticks_without_progress += 1;
+                }
+                let is_stuck = pathfinder.ticks_without_progress > STUCK_TICKS;
+                let is_off_path =
+                    distance_from_move_sqr(**position, &from, &target) > MAX_DEVIATION_SQR;
+                if is_stuck || is_off_path {
+                    if is_stuck {
+                        warn!(
+                            "Got stuck going to {:?}, looking for another path",
+                            target.pos
+                        );
+                        let blocked_edge = search.context.block_edge(from, target);
+                        pathfinder.changed_edges.push(blocked_edge);
+                    } else {
+                        debug!("We're not on the path anymore, looking for a new one");
+                    }
+                    walk_events.send(StartWalkEvent {
+                        entity,
+                        direction: WalkDirection::None,
+                    });
+                    search_from(&mut commands, entity, &mut pathfinder, current_node);
+                    break;
+                }
+            }
+
+            // do the move that was chosen when the path was found, if it was
+            // found by us
+            let search = pathfinder.search.clone();
+            let movement = search
+                .as_ref()
+                .zip(pathfinder.path_moves.get(&(from, target)))
+                .map(|(search, path_move)| {
+                    (
+                        &search.context,
+                        search.context.moves[path_move.index].as_ref(),
+                    )
+                });
+            let offset = target.pos - from.pos;
+
+            if let Some((context, movement)) = movement {
+                if mine_target.is_some() {
+                    // wait until we're done breaking the block
+                    walk_events.send(StartWalkEvent {
+                        entity,
+                        direction: WalkDirection::None,
+                    });
+                    break;
+                }
+
+                let block_to_break = movement.blocks_to_break(&from).into_iter().find(|pos| {
+                    let direction = moves::direction_through(&from.pos, pos);
+                    !moves::is_block_passable_going(pos, &world, direction)
+                });
+                if let Some(block_to_break) = block_to_break {
+                    let direction = moves::direction_through(&from.pos, &block_to_break);
+                    if context
+                        .move_context(&world)
+                        .can_open(&block_to_break, direction)
+                    {
+                        walk_events.send(StartWalkEvent {
+                            entity,
+                            direction: WalkDirection::None,
+                        });
+                        if pathfinder.opened_door != Some(block_to_break) {
+                            block_interact_events.send(BlockInteractEvent {
+                                entity,
+                                position: block_to_break,
+                            });
+                            pathfinder.opened_door = Some(block_to_break);
+                        }
+                        break;
+                    }
+                    let mining_costs = MiningCosts::new(inventory, active_effects.clone());
+                    if let Some((slot, _)) = world
+                        .chunks
+                        .get_block_state(&block_to_break)
+                        .and_then(|block_state| mining_costs.best_tool(block_state))
+                    {
+                        set_selected_hotbar_slot_events
+                            .send(SetSelectedHotbarSlotEvent { entity, slot });
+                    }
+                    commands.entity(entity).insert(MineTarget(block_to_break));
+                    walk_events.send(StartWalkEvent {
+                        entity,
+                        direction: WalkDirection::None,
+                    });
+                    break;
+                }
+
+                if let Some((against, face)) = movement.block_to_place(&from) {
+                    let place_at = against + face.normal();
+                    if !moves::is_block_solid(&place_at, &world) {
+                        walk_events.send(StartWalkEvent {
+                            entity,
+                            direction: WalkDirection::None,
+                        });
+                        let Some(slot) = settings.scaffolding_hotbar_slot(inventory) else {
+                            warn!("Ran out of blocks to place, stopping pathfinding");
+                            finish_pathfinding(
+                                &mut commands,
+                                entity,
+                                &mut pathfinder,
+                                GotoResult::Unreachable(SearchError::NoPath),
+                                &mut walk_events,
+                                &mut goto_result_events,
+                            );
+                            break;
+                        };
+                        set_selected_hotbar_slot_events
+                            .send(SetSelectedHotbarSlotEvent { entity, slot });
+
+                        let in_the_way = physics.bounding_box.intersects_vec3(
+                            &Vec3::new(place_at.x as f64, place_at.y as f64, place_at.z as f64),
+                            &Vec3::new(
+                                place_at.x as f64 + 1.,
+                                place_at.y as f64 + 1.,
+                                place_at.z as f64 + 1.,
+                            ),
+                        );
+                        if in_the_way {
+                            // we're pillaring, so jump and place the block once
+                            // we're above it
+                            jump_events.send(JumpEvent(entity));
+                        } else {
+                            place_block_events.send(PlaceBlockEvent {
+                                entity,
+                                against,
+                                face,
+                            });
+                        }
+                        break;
+                    }
+                }
+            }
+
+            let should_jump = match movement {
+                Some((_, movement)) => movement.should_jump(&from, **position, physics),
+                None => target.pos.y > position.y.floor() as i32,
+            };
+
+            if offset.x == 0 && offset.z == 0 {
+                // we're going straight up or down, so there's nowhere to walk
+                walk_events.send(StartWalkEvent {
+                    entity,
+                    direction: WalkDirection::None,
+                });
+                if should_jump {
+                    jump_events.send(JumpEvent(entity));
+                }
                 break;
             }
+
+            look_at_events.send(LookAtEvent {
+                entity,
+                position: target.pos.center(),
+            });
+            sprint_events.send(StartSprintEvent {
+                entity,
+                direction: SprintDirection::Forward,
+            });
+            if should_jump {
+                jump_events.send(JumpEvent(entity));
+            }
+            break;
         }
     }
 }
 
+/// The squared distance from the position to the closest point on the line
+/// between the middles of the two nodes.
+fn distance_from_move_sqr(position: Vec3, from: &Node, to: &Node) -> f64 {
+    let start = from.pos.center();
+    let line = to.pos.center() - start;
+    let relative_position = position - start;
+    let length_sqr = line.length_sqr();
+    let t = if length_sqr == 0. {
+        0.
+    } else {
+        ((relative_position.x * line.x
+            + relative_position.y * line.y
+            + relative_position.z * line.z)
+            / length_sqr)
+            .clamp(0., 1.)
+    };
+    (start + line * t).distance_to_sqr(&position)
+}
+
 /// Information about our vertical velocity
 #[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
 pub enum VerticalVel {
@@ -276,18 +1265,20 @@ pub struct Node {
 }
 
 pub trait Goal {
+    /// An estimate of the cost to get from the node to the goal. This must
+    /// never be more than the actual cost, or else the paths we find won't be
+    /// the best ones.
     fn heuristic(&self, n: &Node) -> f32;
+    /// Whether the node satisfies the goal. The pathfinder stops at the first
+    /// node this returns true for.
     fn success(&self, n: &Node) -> bool;
-    // TODO: this should be removed and mtdstarlite should stop depending on
-    // being given a goal node
-    fn goal_node(&self) -> Node;
 }
 
 impl Node {
     /// Returns whether the entity is at the node and should start going to the
     /// next node.
     #[must_use]
-    pub fn is_reached(&self, position: &Position, physics: &Physics) -> bool {
+    pub fn is_reached(&self, position: &Position, physics: &Physics, world: &Instance) -> bool {
         // println!(
         //     "entity.delta.y: {} {:?}=={:?}, self.vertical_vel={:?}",
         //     entity.delta.y,
@@ -298,35 +1289,175 @@ impl Node {
         BlockPos::from(position) == self.pos
             && match self.vertical_vel {
                 VerticalVel::NoneMidair => physics.delta.y > -0.1 && physics.delta.y < 0.1,
-                VerticalVel::None => physics.on_ground,
+                // we don't stand on anything while swimming or climbing
+                VerticalVel::None => {
+                    physics.on_ground
+                        || physics.is_in_water
+                        || azalea_physics::on_climbable(world, position)
+                }
                 VerticalVel::FallingLittle => physics.delta.y < -0.1,
             }
     }
 }
 
-pub struct BlockPosGoal {
-    pub pos: BlockPos,
-}
-impl Goal for BlockPosGoal {
-    fn heuristic(&self, n: &Node) -> f32 {
-        let dx = (self.pos.x - n.pos.x) as f32;
-        let dy = (self.pos.y - n.pos.y) as f32;
-        let dz = (self.pos.z - n.pos.z) as f32;
-        dx * dx + dy * dy + dz * dz
-    }
-    fn success(&self, n: &Node) -> bool {
-        n.pos == self.pos
-    }
-    fn goal_node(&self) -> Node {
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azalea_core::ResourceLocation;
+    use azalea_world::entity::EntityBundle;
+    use bevy_tasks::TaskPool;
+    use uuid::Uuid;
+
+    fn node_at(pos: BlockPos) -> Node {
         Node {
-            pos: self.pos,
+            pos,
             vertical_vel: VerticalVel::None,
         }
     }
-}
 
-impl From<BlockPos> for BlockPosGoal {
-    fn from(pos: BlockPos) -> Self {
-        Self { pos }
+    #[test]
+    fn test_changed_edges() {
+        let mut floor = Vec::new();
+        for x in 0..16 {
+            for z in 0..16 {
+                floor.push(BlockPos::new(x, 64, z));
+            }
+        }
+        let (partial_world, world) = moves::make_world(&floor);
+        let world_lock = Arc::new(RwLock::new(world));
+
+        let settings = PathfinderSettings {
+            allow_breaking_blocks: false,
+            allow_placing_blocks: false,
+            ..Default::default()
+        };
+        let context = SearchContext::new(
+            world_lock.clone(),
+            settings,
+            &Inventory::default(),
+            &ActiveEffects::default(),
+        );
+        let start = node_at(BlockPos::new(8, 65, 8));
+        let target = node_at(BlockPos::new(8, 65, 9));
+
+        let edge = context
+            .successors(&start)
+            .into_iter()
+            .find(|edge| edge.target == target)
+            .unwrap();
+        assert!(edge.cost.is_finite());
+        let path_moves = context.path_moves(start, &[target]);
+        let path_move = path_moves[&(start, target)];
+        assert_eq!(path_move.cost, edge.cost);
+        assert_eq!(context.moves[path_move.index].next_node(&start), target);
+        assert!(context
+            .predecessors(&target)
+            .iter()
+            .any(|edge| edge.target == start && edge.cost.is_finite()));
+
+        // build a wall that's too tall to jump over
+        for y in [65, 66] {
+            partial_world.chunks.set_block_state(
+                &BlockPos::new(8, y, 9),
+                azalea_registry::Block::Stone.into(),
+                &mut world_lock.write().chunks,
+            );
+        }
+        let changed_blocks = HashSet::from([BlockPos::new(8, 66, 9)]);
+        let changed_edges = context.changed_edges(&changed_blocks, |_| true);
+        assert!(changed_edges.iter().any(|changed_edge| {
+            changed_edge.predecessor == start
+                && changed_edge.successor == target
+                && changed_edge.cost == f32::INFINITY
+        }));
+        // the search hasn't looked at any of the nodes
+        assert!(context.changed_edges(&changed_blocks, |_| false).is_empty());
+        // the block is too far away to matter
+        let far_away = HashSet::from([BlockPos::new(8, 66, 15)]);
+        assert!(!context
+            .changed_edges(&far_away, |_| true)
+            .iter()
+            .any(|changed_edge| changed_edge.predecessor == start));
+
+        // getting stuck on an edge stops us from going along it
+        let other_target = node_at(BlockPos::new(9, 65, 8));
+        context.block_edge(start, other_target);
+        assert!(context
+            .successors(&start)
+            .iter()
+            .any(|edge| edge.target == other_target && edge.cost == f32::INFINITY));
+    }
+
+    #[test]
+    fn test_goto_unreachable_goal() {
+        // we're stuck in a bedrock box with room to take one step, so the best
+        // we can do is stay where we are
+        let (partial_world, world) = moves::make_world(&[]);
+        let world_lock = Arc::new(RwLock::new(world));
+        for x in 7..=9 {
+            for y in 64..=67 {
+                for z in 7..=10 {
+                    let is_inside = x == 8 && (65..=66).contains(&y) && (8..=9).contains(&z);
+                    if !is_inside {
+                        partial_world.chunks.set_block_state(
+                            &BlockPos::new(x, y, z),
+                            azalea_registry::Block::Bedrock.into(),
+                            &mut world_lock.write().chunks,
+                        );
+                    }
+                }
+            }
+        }
+        let world_name = ResourceLocation::new("minecraft:overworld");
+        let mut instance_container = InstanceContainer::new();
+        instance_container
+            .worlds
+            .insert(world_name.clone(), Arc::downgrade(&world_lock));
+
+        AsyncComputeTaskPool::init(TaskPool::new);
+        let mut app = App::new();
+        app.add_event::<GotoEvent>()
+            .add_event::<GotoResultEvent>()
+            .add_event::<PathFoundEvent>()
+            .add_event::<StartWalkEvent>()
+            .add_systems((handle_tasks, goto_listener).chain())
+            .insert_resource(instance_container);
+        let entity = app
+            .world
+            .spawn((
+                EntityBundle::new(
+                    Uuid::nil(),
+                    Vec3::new(8.5, 65., 9.5),
+                    azalea_registry::EntityKind::Player,
+                    world_name,
+                ),
+                Pathfinder::default(),
+                Inventory::default(),
+            ))
+            .id();
+
+        let goal: Arc<dyn Goal + Send + Sync> =
+            Arc::new(goals::BlockPosGoal::from(BlockPos::new(8, 65, 20)));
+        let (sender, mut receiver) = oneshot::channel();
+        app.world
+            .get_mut::<Pathfinder>(entity)
+            .unwrap()
+            .goto_waiters
+            .push((goal.clone(), sender));
+        app.world.send_event(GotoEvent { entity, goal });
+
+        let start_time = Instant::now();
+        let result = loop {
+            app.update();
+            if let Ok(result) = receiver.try_recv() {
+                break result;
+            }
+            assert!(
+                start_time.elapsed() < Duration::from_secs(10),
+                "goto never finished"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(result.into_goto_result(), Err(GotoError::NoPath));
     }
 }
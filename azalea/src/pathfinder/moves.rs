@@ -1,19 +1,110 @@
-use super::{Node, VerticalVel};
-use azalea_core::{BlockPos, CardinalDirection};
-use azalea_physics::collision::{self, BlockWithShape};
-use azalea_world::Instance;
+use std::collections::HashMap;
+
+use super::{Node, PathfinderSettings, VerticalVel};
+use azalea_block::{properties::FacingCardinal, Block, BlockState, FluidKind, FluidState};
+use azalea_client::{inventory::Inventory, mining::get_mine_progress};
+use azalea_core::{BlockPos, CardinalDirection, Direction, ResourceLocation, Vec3};
+use azalea_physics::{
+    collision::{self, BlockWithShape},
+    simulation::{Simulation, SimulationInput},
+};
+use azalea_registry::tags;
+use azalea_world::{
+    entity::{ActiveEffects, EntityBundle, Physics},
+    Instance,
+};
+use parking_lot::Mutex;
+use uuid::Uuid;
 
 /// whether this block is passable
-fn is_block_passable(pos: &BlockPos, world: &Instance) -> bool {
-    if let Some(block) = world.chunks.get_block_state(pos) {
-        block.shape() == &collision::empty_shape()
+pub fn is_block_passable(pos: &BlockPos, world: &Instance) -> bool {
+    world
+        .chunks
+        .get_block_state(pos)
+        .is_some_and(is_state_passable)
+}
+
+/// Whether we can get through a block with this state from any direction.
+fn is_state_passable(block_state: BlockState) -> bool {
+    // lava doesn't have a hitbox either, but we never want to go in it
+    if FluidState::from(block_state).kind == FluidKind::Lava {
+        return false;
+    }
+    // ladders have a hitbox, but it's thin enough that we can walk into the
+    // block anyways
+    block_state.shape() == &collision::empty_shape()
+        || tags::blocks::CLIMBABLE.contains(&azalea_registry::Block::from(block_state))
+}
+
+/// Whether we can get through the block going in the direction, or going up or
+/// down through it if the direction is `None`.
+///
+/// This is like [`is_block_passable`], except it also lets us past doors and
+/// trapdoors that are only in the way from other directions.
+pub fn is_block_passable_going(
+    pos: &BlockPos,
+    world: &Instance,
+    direction: Option<CardinalDirection>,
+) -> bool {
+    let Some(block_state) = world.chunks.get_block_state(pos) else {
+        return false;
+    };
+    is_state_passable(block_state)
+        || (is_openable(block_state) && !is_door_in_way(block_state, block_state.open(), direction))
+}
+
+/// The direction we go through `pos` in when we're doing a move from `from`,
+/// or `None` if it's straight above or below us.
+pub fn direction_through(from: &BlockPos, pos: &BlockPos) -> Option<CardinalDirection> {
+    match ((pos.x - from.x).signum(), (pos.z - from.z).signum()) {
+        (0, -1) => Some(CardinalDirection::North),
+        (0, 1) => Some(CardinalDirection::South),
+        (-1, 0) => Some(CardinalDirection::West),
+        (1, 0) => Some(CardinalDirection::East),
+        _ => None,
+    }
+}
+
+/// Whether a door, fence gate or trapdoor would be in our way going in the
+/// direction (or up or down if it's `None`) if it was open or closed.
+///
+/// Doors and trapdoors are only a few pixels thick, so they're only in the way
+/// when we're going straight into them.
+fn is_door_in_way(
+    block_state: BlockState,
+    open: bool,
+    direction: Option<CardinalDirection>,
+) -> bool {
+    let block = azalea_registry::Block::from(block_state);
+    if tags::blocks::FENCE_GATES.contains(&block) {
+        // open fence gates don't have a hitbox at all
+        return !open;
+    }
+    let Some(facing) = block_state.horizontal_facing() else {
+        return true;
+    };
+    let facing_x = matches!(facing, FacingCardinal::West | FacingCardinal::East);
+    // whether the thin side of the hitbox is in the way of going along the x
+    // axis, or `None` if it's lying flat
+    let blocks_x = if tags::blocks::WOODEN_DOORS.contains(&block) {
+        // doors turn sideways when they're opened, and the hinge only changes
+        // which side of the block they end up on
+        Some(facing_x != open)
+    } else if open {
+        Some(facing_x)
     } else {
-        false
+        // closed trapdoors lie flat at the top or bottom of the block
+        None
+    };
+    match (blocks_x, direction) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(blocks_x), Some(direction)) => blocks_x == (direction.x() != 0),
     }
 }
 
 /// whether this block has a solid hitbox (i.e. we can stand on it)
-fn is_block_solid(pos: &BlockPos, world: &Instance) -> bool {
+pub fn is_block_solid(pos: &BlockPos, world: &Instance) -> bool {
     if let Some(block) = world.chunks.get_block_state(pos) {
         block.shape() == &collision::block_shape()
     } else {
@@ -33,11 +124,283 @@ fn is_standable(pos: &BlockPos, world: &Instance) -> bool {
     is_block_solid(&pos.down(1), world) && is_passable(pos, world)
 }
 
-const JUMP_COST: f32 = 0.5;
-const WALK_ONE_BLOCK_COST: f32 = 1.0;
+/// Whether a block can be placed here without having to break anything first.
+fn is_replaceable(pos: &BlockPos, world: &Instance) -> bool {
+    let Some(block_state) = world.chunks.get_block_state(pos) else {
+        return false;
+    };
+    block_state.is_air()
+        || matches!(
+            Box::<dyn Block>::from(block_state).as_registry_block(),
+            azalea_registry::Block::Water | azalea_registry::Block::Lava
+        )
+}
+
+/// Whether there's water that we can swim in at this position.
+fn is_water(pos: &BlockPos, world: &Instance) -> bool {
+    is_block_passable(pos, world)
+        && world
+            .get_fluid_state(pos)
+            .is_some_and(|fluid| fluid.kind == FluidKind::Water)
+}
+
+/// Whether this block is a ladder or vines or anything else we can climb.
+fn is_climbable(pos: &BlockPos, world: &Instance) -> bool {
+    world
+        .chunks
+        .get_block_state(pos)
+        .is_some_and(|block_state| {
+            tags::blocks::CLIMBABLE.contains(&azalea_registry::Block::from(block_state))
+        })
+}
+
+/// Whether this is a door, fence gate or trapdoor that we can open by hand.
+fn is_openable(block_state: BlockState) -> bool {
+    let block = azalea_registry::Block::from(block_state);
+    tags::blocks::WOODEN_DOORS.contains(&block)
+        || tags::blocks::FENCE_GATES.contains(&block)
+        || tags::blocks::WOODEN_TRAPDOORS.contains(&block)
+}
+
+pub const JUMP_COST: f32 = 0.5;
+pub const WALK_ONE_BLOCK_COST: f32 = 1.0;
+pub const WALK_DIAGONALLY_COST: f32 = WALK_ONE_BLOCK_COST * 1.4;
+pub const FALL_ONE_BLOCK_COST: f32 = 0.5;
+/// Placing a block means we have to stop and look at where it's going, so it's
+/// a lot slower than walking.
+const PLACE_BLOCK_COST: f32 = 4.0;
+/// About how many ticks it takes to sprint one block, which is used to convert
+/// the time it takes to break a block into a cost.
+const WALK_ONE_BLOCK_TICKS: f32 = 3.6;
+/// The number of ticks we have to wait after breaking a block before we can
+/// start breaking the next one, unless it was broken instantly.
+const DESTROY_DELAY_TICKS: f32 = 5.;
+/// Swimming is less than half as fast as sprinting.
+const SWIM_ONE_BLOCK_COST: f32 = 2.5;
+/// We go up ladders at 0.2 blocks per tick.
+const CLIMB_UP_ONE_BLOCK_COST: f32 = 5. / WALK_ONE_BLOCK_TICKS;
+/// We slide down ladders at 0.15 blocks per tick.
+const CLIMB_DOWN_ONE_BLOCK_COST: f32 = (1. / 0.15) / WALK_ONE_BLOCK_TICKS;
+/// We have to stop in front of doors and wait for the server to open them.
+const OPEN_DOOR_COST: f32 = 2.;
+/// The cost of every point of damage we take from falling. Falls of up to 3
+/// blocks don't do any damage.
+const FALL_DAMAGE_COST: f32 = 2.;
+/// The widest gap that we try to jump over.
+const MAX_PARKOUR_GAP: i32 = 3;
+/// How far past the middle of the block we're jumping from we can go before
+/// we have to jump. This is when the middle of our hitbox is over the edge, so
+/// we're still standing on the block but we've run as far as we can.
+const PARKOUR_JUMP_DISTANCE: f64 = 0.5;
+/// About how far we go while we're in the air after a sprint jump. We jump
+/// early if the gap is narrow enough that we'd go past the block otherwise.
+const SPRINT_JUMP_LENGTH: f64 = 2.;
+/// How many ticks we simulate a parkour jump for before deciding that we
+/// wouldn't land on the block we're jumping to.
+const PARKOUR_SIMULATION_TICKS: usize = 40;
+/// How many chunks around the start of a parkour jump the simulation can see.
+/// We can't jump far enough to get further than this.
+const PARKOUR_SIMULATION_CHUNK_RADIUS: i32 = 1;
+
+/// Keeps track of how long it takes us to break blocks with the best tool in
+/// our hotbar.
+pub struct MiningCosts {
+    /// A copy of our inventory for every hotbar slot, with that slot selected.
+    hotbar_inventories: Vec<Inventory>,
+    active_effects: ActiveEffects,
+    /// The best hotbar slot and number of ticks for every block state we've
+    /// checked so far. `None` means the block can't be broken.
+    cache: Mutex<HashMap<BlockState, Option<(u8, f32)>>>,
+}
+
+impl MiningCosts {
+    pub fn new(inventory: &Inventory, active_effects: ActiveEffects) -> Self {
+        let hotbar_inventories = (0..9)
+            .map(|slot| {
+                let mut inventory = inventory.clone();
+                inventory.selected_hotbar_slot = slot;
+                inventory
+            })
+            .collect();
+        Self {
+            hotbar_inventories,
+            active_effects,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The hotbar slot with the best tool for breaking this block, and how many
+    /// ticks it'll take to break it with that tool. Returns `None` if the block
+    /// can't be broken.
+    pub fn best_tool(&self, block_state: BlockState) -> Option<(u8, f32)> {
+        if let Some(best_tool) = self.cache.lock().get(&block_state) {
+            return *best_tool;
+        }
+
+        let block = Box::<dyn Block>::from(block_state);
+        let mut best_tool: Option<(u8, f32)> = None;
+        for (slot, inventory) in self.hotbar_inventories.iter().enumerate() {
+            // we assume that we're standing on the ground and not underwater,
+            // since that's usually the case when we're following a path
+            let progress =
+                get_mine_progress(block.as_ref(), inventory, false, true, &self.active_effects);
+            if progress <= 0. {
+                continue;
+            }
+            let ticks = (1. / progress).ceil();
+            let is_better = match best_tool {
+                Some((_, best_ticks)) => ticks < best_ticks,
+                None => true,
+            };
+            if is_better {
+                best_tool = Some((slot as u8, ticks));
+            }
+        }
+
+        self.cache.lock().insert(block_state, best_tool);
+        best_tool
+    }
+}
+
+/// Everything a [`Move`] needs to know to figure out how much it costs.
+pub struct MoveContext<'a> {
+    pub world: &'a Instance,
+    pub settings: &'a PathfinderSettings,
+    pub mining_costs: &'a MiningCosts,
+    pub parkour: &'a ParkourSimulator,
+    /// Whether we have any of the blocks in
+    /// [`PathfinderSettings::scaffolding_blocks`] in our hotbar.
+    pub has_scaffolding: bool,
+}
+
+impl MoveContext<'_> {
+    /// The cost of breaking the block at the position so we can walk through
+    /// it. This is 0 if the block is already passable, and infinity if we
+    /// can't or aren't allowed to break it.
+    pub fn break_cost(&self, pos: &BlockPos) -> f32 {
+        if is_block_passable(pos, self.world) {
+            return 0.;
+        }
+        if !self.settings.allow_breaking_blocks {
+            return f32::INFINITY;
+        }
+        let Some(block_state) = self.world.chunks.get_block_state(pos) else {
+            return f32::INFINITY;
+        };
+        // don't let water or lava flow into the path
+        let touching_fluid = [
+            Direction::Up,
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ]
+        .into_iter()
+        .map(|direction| *pos + direction.normal())
+        .chain([*pos])
+        .any(|pos| {
+            !self
+                .world
+                .get_fluid_state(&pos)
+                .unwrap_or_default()
+                .is_empty()
+        });
+        if touching_fluid {
+            return f32::INFINITY;
+        }
+        // TODO: breaking a block under sand or gravel makes it fall into the
+        // path
+
+        match self.mining_costs.best_tool(block_state) {
+            Some((_, ticks)) if ticks <= 1. => ticks / WALK_ONE_BLOCK_TICKS,
+            Some((_, ticks)) => (ticks + DESTROY_DELAY_TICKS) / WALK_ONE_BLOCK_TICKS,
+            None => f32::INFINITY,
+        }
+    }
+
+    /// Whether the block at the position is a door, fence gate or trapdoor
+    /// that's in our way going in the direction, and that we're allowed to
+    /// click to get it out of the way. Usually this opens it, but doors and
+    /// trapdoors that are open the wrong way have to be closed instead.
+    pub fn can_open(&self, pos: &BlockPos, direction: Option<CardinalDirection>) -> bool {
+        self.settings.allow_opening_doors
+            && self
+                .world
+                .chunks
+                .get_block_state(pos)
+                .is_some_and(|block_state| {
+                    is_openable(block_state)
+                        && is_door_in_way(block_state, block_state.open(), direction)
+                        && !is_door_in_way(block_state, !block_state.open(), direction)
+                })
+    }
+
+    /// The cost of getting through the block at the position going in the
+    /// direction, by opening it if it's a door and breaking it otherwise.
+    pub fn pass_cost(&self, pos: &BlockPos, direction: Option<CardinalDirection>) -> f32 {
+        if is_block_passable_going(pos, self.world, direction) {
+            return 0.;
+        }
+        if !self.can_open(pos, direction) {
+            return self.break_cost(pos);
+        }
+        // the top half of a door opens with the bottom half, so we only pay
+        // for opening it once
+        let block = self
+            .world
+            .chunks
+            .get_block_state(pos)
+            .map(azalea_registry::Block::from);
+        let block_below = self
+            .world
+            .chunks
+            .get_block_state(&pos.down(1))
+            .map(azalea_registry::Block::from);
+        match block {
+            Some(block)
+                if block_below == Some(block) && tags::blocks::WOODEN_DOORS.contains(&block) =>
+            {
+                0.
+            }
+            _ => OPEN_DOOR_COST,
+        }
+    }
+
+    /// The cost of placing a block at the position so we can stand on it.
+    /// This is 0 if there's already a solid block there, and infinity if we
+    /// can't or aren't allowed to place one.
+    pub fn place_cost(&self, pos: &BlockPos) -> f32 {
+        if is_block_solid(pos, self.world) {
+            return 0.;
+        }
+        if self.settings.allow_placing_blocks
+            && self.has_scaffolding
+            && is_replaceable(pos, self.world)
+        {
+            PLACE_BLOCK_COST
+        } else {
+            f32::INFINITY
+        }
+    }
+}
+
+/// The blocks that [`MoveContext::pass_cost`] looks at for the position, which
+/// are the block itself, the one below it in case they're both halves of a
+/// door, and the ones that water or lava could flow in from if it's broken.
+fn pass_footprint(pos: BlockPos) -> [BlockPos; 7] {
+    [
+        pos,
+        pos.down(1),
+        pos.up(1),
+        pos + BlockPos::new(0, 0, -1),
+        pos + BlockPos::new(0, 0, 1),
+        pos + BlockPos::new(-1, 0, 0),
+        pos + BlockPos::new(1, 0, 0),
+    ]
+}
 
 pub trait Move: Send + Sync {
-    fn cost(&self, world: &Instance, node: &Node) -> f32;
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32;
     /// Returns by how much the entity's position should be changed when this
     /// move is executed.
     fn offset(&self) -> BlockPos;
@@ -47,35 +410,108 @@ pub trait Move: Send + Sync {
             vertical_vel: VerticalVel::None,
         }
     }
+    /// The blocks that might have to be broken before this move can be done
+    /// from the node, in the order they should be broken in.
+    fn blocks_to_break(&self, _node: &Node) -> Vec<BlockPos> {
+        Vec::new()
+    }
+    /// The block we might have to place against and the face of it we should
+    /// click, if this move needs a block to be placed. The block is placed at
+    /// `against + face.normal()`.
+    fn block_to_place(&self, _node: &Node) -> Option<(BlockPos, Direction)> {
+        None
+    }
+    /// The positions of the blocks that the cost of this move depends on,
+    /// relative to the node it starts at. The cost can only change when one of
+    /// these blocks does.
+    fn footprint(&self) -> Vec<BlockPos>;
+    /// Whether we should be jumping right now while doing this move from the
+    /// node. By default we jump whenever we're below the node we're going to.
+    fn should_jump(&self, node: &Node, position: Vec3, _physics: &Physics) -> bool {
+        node.pos.y + self.offset().y > position.y.floor() as i32
+    }
 }
 
+/// Every move the pathfinder can make with these settings.
+///
+/// Some moves go to the same place in different ways, like walking and
+/// swimming forward. The cheapest one is used.
+pub fn all_moves(settings: &PathfinderSettings) -> Vec<Box<dyn Move>> {
+    let mut moves: Vec<Box<dyn Move>> = Vec::new();
+    for direction in CardinalDirection::iter() {
+        moves.push(Box::new(ForwardMove(direction)));
+        moves.push(Box::new(AscendMove(direction)));
+        moves.push(Box::new(DescendMove(direction)));
+        moves.push(Box::new(DiagonalMove(direction)));
+        moves.push(Box::new(SwimMove(direction)));
+        for gap in 1..=MAX_PARKOUR_GAP {
+            moves.push(Box::new(ParkourMove { direction, gap }));
+        }
+        // falling one block is the same as descending
+        for height in 2..=settings.max_fall_distance as i32 {
+            moves.push(Box::new(FallMove { direction, height }));
+        }
+    }
+    moves.push(Box::new(PillarMove));
+    moves.push(Box::new(DigDownMove));
+    moves.push(Box::new(SwimUpMove));
+    moves.push(Box::new(ClimbUpMove));
+    moves.push(Box::new(ClimbDownMove));
+    moves
+}
+
+/// Walk one block forward, breaking the two blocks in the way and bridging
+/// over a gap if we have to.
 pub struct ForwardMove(pub CardinalDirection);
 impl Move for ForwardMove {
-    fn cost(&self, world: &Instance, node: &Node) -> f32 {
-        if is_standable(&(node.pos + self.offset()), world)
-            && node.vertical_vel == VerticalVel::None
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        if node.vertical_vel != VerticalVel::None {
+            return f32::INFINITY;
+        }
+        let target = node.pos + self.offset();
+        let mut cost = WALK_ONE_BLOCK_COST
+            + ctx.pass_cost(&target, Some(self.0))
+            + ctx.pass_cost(&target.up(1), Some(self.0))
+            + ctx.place_cost(&target.down(1));
+        // we can only bridge by placing the block against the one we're on
+        if !is_block_solid(&target.down(1), ctx.world)
+            && !is_block_solid(&node.pos.down(1), ctx.world)
         {
-            WALK_ONE_BLOCK_COST
-        } else {
-            f32::INFINITY
+            cost = f32::INFINITY;
         }
+        cost
     }
     fn offset(&self) -> BlockPos {
         BlockPos::new(self.0.x(), 0, self.0.z())
     }
+    fn blocks_to_break(&self, node: &Node) -> Vec<BlockPos> {
+        let target = node.pos + self.offset();
+        vec![target.up(1), target]
+    }
+    fn block_to_place(&self, node: &Node) -> Option<(BlockPos, Direction)> {
+        Some((node.pos.down(1), self.0.into()))
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        let target = self.offset();
+        let mut footprint = vec![target.down(1), BlockPos::new(0, -1, 0)];
+        footprint.extend(pass_footprint(target));
+        footprint.extend(pass_footprint(target.up(1)));
+        footprint
+    }
 }
 
 pub struct AscendMove(pub CardinalDirection);
 impl Move for AscendMove {
-    fn cost(&self, world: &Instance, node: &Node) -> f32 {
-        if node.vertical_vel == VerticalVel::None
-            && is_block_passable(&node.pos.up(2), world)
-            && is_standable(&(node.pos + self.offset()), world)
-        {
-            WALK_ONE_BLOCK_COST + JUMP_COST
-        } else {
-            f32::INFINITY
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        if node.vertical_vel != VerticalVel::None || !is_block_solid(&target.down(1), ctx.world) {
+            return f32::INFINITY;
         }
+        WALK_ONE_BLOCK_COST
+            + JUMP_COST
+            + ctx.pass_cost(&node.pos.up(2), None)
+            + ctx.pass_cost(&target, Some(self.0))
+            + ctx.pass_cost(&target.up(1), Some(self.0))
     }
     fn offset(&self) -> BlockPos {
         BlockPos::new(self.0.x(), 1, self.0.z())
@@ -86,19 +522,35 @@ impl Move for AscendMove {
             vertical_vel: VerticalVel::None,
         }
     }
+    fn blocks_to_break(&self, node: &Node) -> Vec<BlockPos> {
+        let target = node.pos + self.offset();
+        vec![node.pos.up(2), target.up(1), target]
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        let target = self.offset();
+        let mut footprint = vec![target.down(1)];
+        footprint.extend(pass_footprint(BlockPos::new(0, 2, 0)));
+        footprint.extend(pass_footprint(target));
+        footprint.extend(pass_footprint(target.up(1)));
+        footprint
+    }
 }
 pub struct DescendMove(pub CardinalDirection);
 impl Move for DescendMove {
-    fn cost(&self, world: &Instance, node: &Node) -> f32 {
-        // check whether 3 blocks vertically forward are passable
-        if node.vertical_vel == VerticalVel::None
-            && is_standable(&(node.pos + self.offset()), world)
-            && is_block_passable(&(node.pos + self.offset().up(2)), world)
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        // we can land on a block, or in water or on a ladder
+        if node.vertical_vel != VerticalVel::None
+            || !(is_block_solid(&target.down(1), ctx.world)
+                || is_water(&target, ctx.world)
+                || is_climbable(&target, ctx.world))
         {
-            WALK_ONE_BLOCK_COST
-        } else {
-            f32::INFINITY
+            return f32::INFINITY;
         }
+        WALK_ONE_BLOCK_COST
+            + ctx.pass_cost(&target.up(2), Some(self.0))
+            + ctx.pass_cost(&target.up(1), Some(self.0))
+            + ctx.pass_cost(&target, Some(self.0))
     }
     fn offset(&self) -> BlockPos {
         BlockPos::new(self.0.x(), -1, self.0.z())
@@ -109,10 +561,23 @@ impl Move for DescendMove {
             vertical_vel: VerticalVel::None,
         }
     }
+    fn blocks_to_break(&self, node: &Node) -> Vec<BlockPos> {
+        let target = node.pos + self.offset();
+        vec![target.up(2), target.up(1), target]
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        let target = self.offset();
+        let mut footprint = vec![target.down(1)];
+        footprint.extend(pass_footprint(target));
+        footprint.extend(pass_footprint(target.up(1)));
+        footprint.extend(pass_footprint(target.up(2)));
+        footprint
+    }
 }
 pub struct DiagonalMove(pub CardinalDirection);
 impl Move for DiagonalMove {
-    fn cost(&self, world: &Instance, node: &Node) -> f32 {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let world = ctx.world;
         if node.vertical_vel != VerticalVel::None {
             return f32::INFINITY;
         }
@@ -132,7 +597,7 @@ impl Move for DiagonalMove {
         if !is_standable(&(node.pos + self.offset()), world) {
             return f32::INFINITY;
         }
-        WALK_ONE_BLOCK_COST * 1.4
+        WALK_DIAGONALLY_COST
     }
     fn offset(&self) -> BlockPos {
         let right = self.0.right();
@@ -144,13 +609,429 @@ impl Move for DiagonalMove {
             vertical_vel: VerticalVel::None,
         }
     }
+    fn footprint(&self) -> Vec<BlockPos> {
+        let forward = BlockPos::new(self.0.x(), 0, self.0.z());
+        let right = BlockPos::new(self.0.right().x(), 0, self.0.right().z());
+        let target = self.offset();
+        vec![
+            forward,
+            forward.up(1),
+            right,
+            right.up(1),
+            target.down(1),
+            target,
+            target.up(1),
+        ]
+    }
+}
+
+/// Jump and place a block under ourselves.
+pub struct PillarMove;
+impl Move for PillarMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        if node.vertical_vel != VerticalVel::None || !is_block_solid(&node.pos.down(1), ctx.world) {
+            return f32::INFINITY;
+        }
+        JUMP_COST + ctx.pass_cost(&node.pos.up(2), None) + ctx.place_cost(&node.pos)
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(0, 1, 0)
+    }
+    fn blocks_to_break(&self, node: &Node) -> Vec<BlockPos> {
+        vec![node.pos.up(2)]
+    }
+    fn block_to_place(&self, node: &Node) -> Option<(BlockPos, Direction)> {
+        Some((node.pos.down(1), Direction::Up))
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        let mut footprint = vec![BlockPos::new(0, -1, 0), BlockPos::new(0, 0, 0)];
+        footprint.extend(pass_footprint(BlockPos::new(0, 2, 0)));
+        footprint
+    }
+}
+
+/// Break the block we're standing on and fall down one block.
+pub struct DigDownMove;
+impl Move for DigDownMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let below = node.pos.down(1);
+        if node.vertical_vel != VerticalVel::None
+            || is_block_passable(&below, ctx.world)
+            || !is_block_solid(&below.down(1), ctx.world)
+        {
+            return f32::INFINITY;
+        }
+        FALL_ONE_BLOCK_COST + ctx.break_cost(&below)
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(0, -1, 0)
+    }
+    fn blocks_to_break(&self, node: &Node) -> Vec<BlockPos> {
+        vec![node.pos.down(1)]
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        let mut footprint = vec![BlockPos::new(0, -2, 0)];
+        footprint.extend(pass_footprint(BlockPos::new(0, -1, 0)));
+        footprint
+    }
+}
+
+/// Swim one block forward along the top of the water.
+///
+/// We don't keep track of how much air we have left, so we never swim
+/// underwater in case we'd drown. If we end up underwater anyways, like after
+/// falling in, we have to swim back up first.
+pub struct SwimMove(pub CardinalDirection);
+impl Move for SwimMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        if !ctx.settings.allow_swimming
+            || node.vertical_vel != VerticalVel::None
+            || !is_water(&target, ctx.world)
+            || is_water(&target.up(1), ctx.world)
+        {
+            return f32::INFINITY;
+        }
+        SWIM_ONE_BLOCK_COST + ctx.pass_cost(&target.up(1), Some(self.0))
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(self.0.x(), 0, self.0.z())
+    }
+    fn blocks_to_break(&self, node: &Node) -> Vec<BlockPos> {
+        vec![(node.pos + self.offset()).up(1)]
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        let target = self.offset();
+        let mut footprint = vec![target];
+        footprint.extend(pass_footprint(target.up(1)));
+        footprint
+    }
+    fn should_jump(&self, node: &Node, position: Vec3, physics: &Physics) -> bool {
+        // swim up whenever we're in the bottom half of the block so we don't
+        // sink out of it
+        physics.is_in_water && position.y < node.pos.y as f64 + 0.5
+    }
+}
+
+/// Swim up one block.
+pub struct SwimUpMove;
+impl Move for SwimUpMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        if !ctx.settings.allow_swimming
+            || node.vertical_vel != VerticalVel::None
+            || !is_water(&node.pos, ctx.world)
+            || !is_water(&target, ctx.world)
+        {
+            return f32::INFINITY;
+        }
+        SWIM_ONE_BLOCK_COST + ctx.pass_cost(&target.up(1), None)
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(0, 1, 0)
+    }
+    fn blocks_to_break(&self, node: &Node) -> Vec<BlockPos> {
+        vec![node.pos.up(2)]
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        let mut footprint = vec![BlockPos::new(0, 0, 0), BlockPos::new(0, 1, 0)];
+        footprint.extend(pass_footprint(BlockPos::new(0, 2, 0)));
+        footprint
+    }
+}
+
+/// Climb up one block of a ladder or vines.
+pub struct ClimbUpMove;
+impl Move for ClimbUpMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let target = node.pos + self.offset();
+        if !ctx.settings.allow_climbing
+            || node.vertical_vel != VerticalVel::None
+            || !is_climbable(&node.pos, ctx.world)
+            || !is_climbable(&target, ctx.world)
+        {
+            return f32::INFINITY;
+        }
+        CLIMB_UP_ONE_BLOCK_COST + ctx.pass_cost(&target.up(1), None)
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(0, 1, 0)
+    }
+    fn blocks_to_break(&self, node: &Node) -> Vec<BlockPos> {
+        vec![node.pos.up(2)]
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        let mut footprint = vec![BlockPos::new(0, 0, 0), BlockPos::new(0, 1, 0)];
+        footprint.extend(pass_footprint(BlockPos::new(0, 2, 0)));
+        footprint
+    }
+}
+
+/// Slide down one block of a ladder or vines.
+pub struct ClimbDownMove;
+impl Move for ClimbDownMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        if !ctx.settings.allow_climbing
+            || node.vertical_vel != VerticalVel::None
+            || !is_climbable(&(node.pos + self.offset()), ctx.world)
+        {
+            return f32::INFINITY;
+        }
+        CLIMB_DOWN_ONE_BLOCK_COST
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(0, -1, 0)
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        vec![self.offset()]
+    }
+}
+
+/// Sprint and jump over a gap to a block at the same height.
+pub struct ParkourMove {
+    pub direction: CardinalDirection,
+    /// How many blocks wide the gap is. We only try to jump over gaps of up
+    /// to 3 blocks, since wider ones are too far to jump.
+    pub gap: i32,
+}
+impl Move for ParkourMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let world = ctx.world;
+        if !ctx.settings.allow_parkour
+            || node.vertical_vel != VerticalVel::None
+            || !is_block_solid(&node.pos.down(1), world)
+            || !is_block_passable(&node.pos.up(2), world)
+        {
+            return f32::INFINITY;
+        }
+        for distance in 1..=self.gap {
+            let pos =
+                node.pos + BlockPos::new(self.direction.x(), 0, self.direction.z()) * distance;
+            // if there's something to stand on then we should just walk
+            if is_block_solid(&pos.down(1), world)
+                || !is_passable(&pos, world)
+                || !is_block_passable(&pos.up(2), world)
+            {
+                return f32::INFINITY;
+            }
+        }
+        let target = node.pos + self.offset();
+        if !is_standable(&target, world)
+            || !is_block_passable(&target.up(2), world)
+            || !ctx.parkour.can_jump(world, node, &target)
+        {
+            return f32::INFINITY;
+        }
+        WALK_ONE_BLOCK_COST * (self.gap + 1) as f32 + JUMP_COST
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(self.direction.x(), 0, self.direction.z()) * (self.gap + 1)
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        // we can get about 3 blocks high while we're jumping, so the
+        // simulation can bump into anything up to there
+        let forward = BlockPos::new(self.direction.x(), 0, self.direction.z());
+        (0..=self.gap + 1)
+            .flat_map(|distance| (-1..=3).map(move |y| (forward * distance).up(y)))
+            .collect()
+    }
+    fn should_jump(&self, node: &Node, position: Vec3, _physics: &Physics) -> bool {
+        should_parkour_jump(node, &(node.pos + self.offset()), position)
+    }
+}
+
+/// Whether we've run far enough from the node towards the target that we
+/// should jump.
+fn should_parkour_jump(node: &Node, target: &BlockPos, position: Vec3) -> bool {
+    let center = node.pos.center();
+    let direction = BlockPos::new(
+        (target.x - node.pos.x).signum(),
+        0,
+        (target.z - node.pos.z).signum(),
+    );
+    let length = ((target.x - node.pos.x).abs() + (target.z - node.pos.z).abs()) as f64;
+    let distance =
+        (position.x - center.x) * direction.x as f64 + (position.z - center.z) * direction.z as f64;
+    distance >= PARKOUR_JUMP_DISTANCE.min(length - SPRINT_JUMP_LENGTH)
+}
+
+/// Simulates parkour jumps to find out whether they work, and remembers the
+/// results since simulating is a lot slower than looking at blocks.
+#[derive(Default)]
+pub struct ParkourSimulator {
+    /// The simulation is reused for every jump, since making a new one is
+    /// slow.
+    simulation: Mutex<Option<Simulation>>,
+    /// Whether jumping from the first position lands us on the second one.
+    results: Mutex<HashMap<(BlockPos, BlockPos), bool>>,
+}
+
+impl ParkourSimulator {
+    /// Whether sprinting from the middle of the node and jumping at the edge
+    /// gets us onto the target. This should only be checked once we know that
+    /// nothing's in the way.
+    pub fn can_jump(&self, world: &Instance, node: &Node, target: &BlockPos) -> bool {
+        if let Some(result) = self.results.lock().get(&(node.pos, *target)) {
+            return *result;
+        }
+        let result = self.simulate(world, node, target);
+        self.results.lock().insert((node.pos, *target), result);
+        result
+    }
+
+    /// Forget whether the jumps that go past the block work, since they might
+    /// not anymore now that it changed.
+    pub fn forget(&self, pos: &BlockPos) {
+        let mut results = self.results.lock();
+        for direction in CardinalDirection::iter() {
+            for gap in 1..=MAX_PARKOUR_GAP {
+                let movement = ParkourMove { direction, gap };
+                for offset in movement.footprint() {
+                    let from = *pos - offset;
+                    results.remove(&(from, from + movement.offset()));
+                }
+            }
+        }
+    }
+
+    /// Forget whether jumping from one position to the other works, so it's
+    /// simulated again next time.
+    pub fn forget_jump(&self, from: &BlockPos, to: &BlockPos) {
+        self.results.lock().remove(&(*from, *to));
+    }
+
+    fn simulate(&self, world: &Instance, node: &Node, target: &BlockPos) -> bool {
+        let start = Vec3::new(
+            node.pos.x as f64 + 0.5,
+            node.pos.y as f64,
+            node.pos.z as f64 + 0.5,
+        );
+        let mut player = EntityBundle::new(
+            Uuid::nil(),
+            start,
+            azalea_registry::EntityKind::Player,
+            ResourceLocation::new("minecraft:overworld"),
+        );
+        player.physics.on_ground = true;
+        let mut simulation = self.simulation.lock();
+        let simulation = match &mut *simulation {
+            Some(simulation) => {
+                simulation.reset(
+                    world,
+                    &player.physics,
+                    &player.position,
+                    &player.attributes,
+                    PARKOUR_SIMULATION_CHUNK_RADIUS,
+                );
+                simulation
+            }
+            None => simulation.insert(Simulation::new(
+                world,
+                &player.physics,
+                &player.position,
+                &player.attributes,
+            )),
+        };
+
+        let target_center = target.center();
+        let mut jumped = false;
+        for _ in 0..PARKOUR_SIMULATION_TICKS {
+            let position = simulation.position();
+            let jumping = !jumped && should_parkour_jump(node, target, position);
+            // we look at the middle of the target the whole time like we do
+            // when following the path, so we turn around if we jump too far
+            let y_rot = (-(target_center.x - position.x))
+                .atan2(target_center.z - position.z)
+                .to_degrees() as f32;
+            let tick = simulation.tick(&SimulationInput {
+                forward_impulse: 1.,
+                jumping,
+                sprinting: true,
+                rotation: Some((y_rot, 0.)),
+                ..Default::default()
+            });
+            jumped |= jumping;
+            if jumped && tick.on_ground && BlockPos::from(tick.position) == *target {
+                return true;
+            }
+            if tick.position.y < (target.y - 1) as f64 {
+                // we fell and can't get back up
+                return false;
+            }
+        }
+        false
+    }
+}
+
+/// Walk off the edge of a block and fall down to the block that's `height`
+/// blocks below it.
+pub struct FallMove {
+    pub direction: CardinalDirection,
+    pub height: i32,
+}
+impl Move for FallMove {
+    fn cost(&self, ctx: &MoveContext, node: &Node) -> f32 {
+        let world = ctx.world;
+        let target = node.pos + self.offset();
+        // lava isn't passable, so this also makes sure we don't fall into it
+        if node.vertical_vel != VerticalVel::None
+            || !(0..=self.height + 1).all(|y| is_block_passable(&target.up(y), world))
+        {
+            return f32::INFINITY;
+        }
+        // landing in water or grabbing onto a ladder stops us from taking any
+        // fall damage
+        let damage = if is_water(&target, world) || is_climbable(&target, world) {
+            0
+        } else if is_block_solid(&target.down(1), world) {
+            (self.height - 3).max(0)
+        } else {
+            return f32::INFINITY;
+        };
+        WALK_ONE_BLOCK_COST
+            + self.height as f32 * FALL_ONE_BLOCK_COST
+            + damage as f32 * FALL_DAMAGE_COST
+    }
+    fn offset(&self) -> BlockPos {
+        BlockPos::new(self.direction.x(), -self.height, self.direction.z())
+    }
+    fn footprint(&self) -> Vec<BlockPos> {
+        let target = self.offset();
+        (-1..=self.height + 1).map(|y| target.up(y)).collect()
+    }
+}
+
+/// Make a world for tests with one empty chunk at 0, 0, and stone at the
+/// positions. The partial world has to be kept around or else the chunk gets
+/// dropped.
+#[cfg(test)]
+pub(super) fn make_world(stone: &[BlockPos]) -> (azalea_world::PartialInstance, Instance) {
+    use azalea_core::ChunkPos;
+    use azalea_world::{Chunk, ChunkStorage, PartialInstance};
+
+    let mut partial_world = PartialInstance::default();
+    let mut chunk_storage = ChunkStorage::default();
+    partial_world.chunks.set(
+        &ChunkPos { x: 0, z: 0 },
+        Some(Chunk::default()),
+        &mut chunk_storage,
+    );
+    for pos in stone {
+        partial_world.chunks.set_block_state(
+            pos,
+            azalea_registry::Block::Stone.into(),
+            &mut chunk_storage,
+        );
+    }
+    (partial_world, chunk_storage.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use azalea_block::BlockState;
-    use azalea_core::ChunkPos;
+    use azalea_core::{ChunkPos, Slot, SlotData};
+    use azalea_registry::Item;
     use azalea_world::{Chunk, ChunkStorage, PartialInstance};
 
     #[test]
@@ -239,4 +1120,512 @@ mod tests {
         assert!(!is_standable(&BlockPos::new(0, 0, 0), &world));
         assert!(!is_standable(&BlockPos::new(0, 2, 0), &world));
     }
+
+    fn make_ctx<'a>(
+        world: &'a Instance,
+        settings: &'a PathfinderSettings,
+        mining_costs: &'a MiningCosts,
+        parkour: &'a ParkourSimulator,
+    ) -> MoveContext<'a> {
+        MoveContext {
+            world,
+            settings,
+            mining_costs,
+            parkour,
+            has_scaffolding: false,
+        }
+    }
+
+    fn inventory_with_dirt() -> Inventory {
+        let mut inventory = Inventory::default();
+        let hotbar_start = inventory.inventory_menu.hotbar_slots().start;
+        inventory.inventory_menu.slots[hotbar_start + 3] = Slot::Present(SlotData {
+            id: Item::Dirt as u32,
+            count: 64,
+            nbt: Default::default(),
+        });
+        inventory
+    }
+
+    #[test]
+    fn test_mine_through_wall() {
+        let (_partial_world, world) = make_world(&[
+            BlockPos::new(0, 0, 0),
+            BlockPos::new(1, 0, 0),
+            BlockPos::new(1, 1, 0),
+            BlockPos::new(1, 2, 0),
+        ]);
+        let node = Node {
+            pos: BlockPos::new(0, 1, 0),
+            vertical_vel: VerticalVel::None,
+        };
+        let mining_costs = MiningCosts::new(&Inventory::default(), ActiveEffects::default());
+        let parkour = ParkourSimulator::default();
+        let mut settings = PathfinderSettings::default();
+
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        let cost = ForwardMove(CardinalDirection::East).cost(&ctx, &node);
+        assert!(cost.is_finite());
+        assert!(cost > WALK_ONE_BLOCK_COST);
+
+        settings.allow_breaking_blocks = false;
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert_eq!(
+            ForwardMove(CardinalDirection::East).cost(&ctx, &node),
+            f32::INFINITY
+        );
+    }
+
+    #[test]
+    fn test_bridge_and_pillar() {
+        let (_partial_world, world) = make_world(&[BlockPos::new(0, 0, 0)]);
+        let node = Node {
+            pos: BlockPos::new(0, 1, 0),
+            vertical_vel: VerticalVel::None,
+        };
+        let settings = PathfinderSettings::default();
+        let inventory = inventory_with_dirt();
+        let mining_costs = MiningCosts::new(&inventory, ActiveEffects::default());
+        let parkour = ParkourSimulator::default();
+        assert_eq!(settings.scaffolding_hotbar_slot(&inventory), Some(3));
+
+        let ctx = MoveContext {
+            has_scaffolding: true,
+            ..make_ctx(&world, &settings, &mining_costs, &parkour)
+        };
+        assert_eq!(
+            ForwardMove(CardinalDirection::East).cost(&ctx, &node),
+            WALK_ONE_BLOCK_COST + PLACE_BLOCK_COST
+        );
+        assert_eq!(PillarMove.cost(&ctx, &node), JUMP_COST + PLACE_BLOCK_COST);
+
+        let ctx = MoveContext {
+            has_scaffolding: false,
+            ..ctx
+        };
+        assert_eq!(
+            ForwardMove(CardinalDirection::East).cost(&ctx, &node),
+            f32::INFINITY
+        );
+        assert_eq!(PillarMove.cost(&ctx, &node), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_best_tool() {
+        let mut inventory = inventory_with_dirt();
+        let hotbar_start = inventory.inventory_menu.hotbar_slots().start;
+        inventory.inventory_menu.slots[hotbar_start + 7] = Slot::Present(SlotData {
+            id: Item::IronPickaxe as u32,
+            count: 1,
+            nbt: Default::default(),
+        });
+        let mining_costs = MiningCosts::new(&inventory, ActiveEffects::default());
+
+        let (slot, ticks) = mining_costs
+            .best_tool(azalea_registry::Block::Stone.into())
+            .unwrap();
+        assert_eq!(slot, 7);
+        assert!(ticks < 10.);
+        assert_eq!(
+            mining_costs.best_tool(azalea_registry::Block::Bedrock.into()),
+            None
+        );
+    }
+
+    fn set_block(
+        partial_world: &mut PartialInstance,
+        world: &mut Instance,
+        pos: BlockPos,
+        block_state: BlockState,
+    ) {
+        partial_world
+            .chunks
+            .set_block_state(&pos, block_state, &mut world.chunks);
+    }
+
+    fn node_at(pos: BlockPos) -> Node {
+        Node {
+            pos,
+            vertical_vel: VerticalVel::None,
+        }
+    }
+
+    #[test]
+    fn test_swim_and_climb() {
+        let (mut partial_world, mut world) = make_world(&[BlockPos::new(0, 0, 0)]);
+        for x in 1..=2 {
+            for y in -2..=1 {
+                set_block(
+                    &mut partial_world,
+                    &mut world,
+                    BlockPos::new(x, y, 0),
+                    azalea_registry::Block::Water.into(),
+                );
+            }
+        }
+        for y in 1..=3 {
+            set_block(
+                &mut partial_world,
+                &mut world,
+                BlockPos::new(0, y, 1),
+                azalea_registry::Block::Ladder.into(),
+            );
+        }
+        let mining_costs = MiningCosts::new(&Inventory::default(), ActiveEffects::default());
+        let parkour = ParkourSimulator::default();
+        let mut settings = PathfinderSettings::default();
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+
+        // there's nothing to walk on in the water
+        let start = node_at(BlockPos::new(0, 1, 0));
+        assert_eq!(
+            ForwardMove(CardinalDirection::East).cost(&ctx, &start),
+            f32::INFINITY
+        );
+        assert_eq!(
+            SwimMove(CardinalDirection::East).cost(&ctx, &start),
+            SWIM_ONE_BLOCK_COST
+        );
+        let in_water = node_at(BlockPos::new(1, 1, 0));
+        assert_eq!(
+            SwimMove(CardinalDirection::East).cost(&ctx, &in_water),
+            SWIM_ONE_BLOCK_COST
+        );
+        // we'd be underwater and might run out of air
+        let underwater = node_at(BlockPos::new(1, 0, 0));
+        assert_eq!(
+            SwimMove(CardinalDirection::East).cost(&ctx, &underwater),
+            f32::INFINITY
+        );
+        // but we can always swim back up
+        assert_eq!(SwimUpMove.cost(&ctx, &underwater), SWIM_ONE_BLOCK_COST);
+        assert_eq!(
+            SwimUpMove.cost(&ctx, &node_at(BlockPos::new(1, -1, 0))),
+            SWIM_ONE_BLOCK_COST
+        );
+        // the top of the water isn't water
+        assert_eq!(SwimUpMove.cost(&ctx, &in_water), f32::INFINITY);
+
+        let on_ladder = node_at(BlockPos::new(0, 2, 1));
+        assert!(is_block_passable(&on_ladder.pos, &world));
+        assert_eq!(ClimbUpMove.cost(&ctx, &on_ladder), CLIMB_UP_ONE_BLOCK_COST);
+        assert_eq!(
+            ClimbDownMove.cost(&ctx, &on_ladder),
+            CLIMB_DOWN_ONE_BLOCK_COST
+        );
+        // there's no more ladder above the top of it
+        assert_eq!(
+            ClimbUpMove.cost(&ctx, &node_at(BlockPos::new(0, 3, 1))),
+            f32::INFINITY
+        );
+
+        settings.allow_swimming = false;
+        settings.allow_climbing = false;
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert_eq!(
+            SwimMove(CardinalDirection::East).cost(&ctx, &start),
+            f32::INFINITY
+        );
+        assert_eq!(ClimbUpMove.cost(&ctx, &on_ladder), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_open_door() {
+        let (mut partial_world, mut world) =
+            make_world(&[BlockPos::new(0, 0, 0), BlockPos::new(1, 0, 0)]);
+        let mut set_door = |world: &mut Instance, facing, open| {
+            for (y, half) in [
+                (1, azalea_block::properties::Half::Lower),
+                (2, azalea_block::properties::Half::Upper),
+            ] {
+                let door = azalea_block::blocks::OakDoor {
+                    facing,
+                    half,
+                    open,
+                    ..Default::default()
+                }
+                .as_block_state();
+                set_block(&mut partial_world, world, BlockPos::new(1, y, 0), door);
+            }
+        };
+        // the door is across the way we're going
+        set_door(&mut world, FacingCardinal::East, false);
+        let node = node_at(BlockPos::new(0, 1, 0));
+        let east = ForwardMove(CardinalDirection::East);
+        let mining_costs = MiningCosts::new(&Inventory::default(), ActiveEffects::default());
+        let parkour = ParkourSimulator::default();
+        let mut settings = PathfinderSettings::default();
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert!(ctx.can_open(&BlockPos::new(1, 2, 0), Some(CardinalDirection::East)));
+        // both halves open together, so we only pay for one of them
+        assert_eq!(east.cost(&ctx, &node), WALK_ONE_BLOCK_COST + OPEN_DOOR_COST);
+
+        settings.allow_opening_doors = false;
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert!(!ctx.can_open(&BlockPos::new(1, 2, 0), Some(CardinalDirection::East)));
+        // we have to break it instead
+        assert!(east.cost(&ctx, &node) > WALK_ONE_BLOCK_COST + OPEN_DOOR_COST);
+
+        set_door(&mut world, FacingCardinal::East, true);
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert_eq!(east.cost(&ctx, &node), WALK_ONE_BLOCK_COST);
+        // but now it's in the way of going north or south through it
+        assert!(!is_block_passable_going(
+            &BlockPos::new(1, 1, 0),
+            &world,
+            Some(CardinalDirection::North)
+        ));
+
+        // a closed door that we're going past edge-on isn't in the way
+        set_door(&mut world, FacingCardinal::North, false);
+        settings.allow_opening_doors = true;
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert!(!ctx.can_open(&BlockPos::new(1, 1, 0), Some(CardinalDirection::East)));
+        assert_eq!(east.cost(&ctx, &node), WALK_ONE_BLOCK_COST);
+
+        // and if it's open then it's in the way, so we close it
+        set_door(&mut world, FacingCardinal::North, true);
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert!(ctx.can_open(&BlockPos::new(1, 1, 0), Some(CardinalDirection::East)));
+        assert_eq!(east.cost(&ctx, &node), WALK_ONE_BLOCK_COST + OPEN_DOOR_COST);
+    }
+
+    #[test]
+    fn test_trapdoor_and_fence_gate() {
+        let (_partial_world, world) = make_world(&[]);
+        let world_with = |block_state: BlockState| {
+            let (mut partial_world, mut world) = make_world(&[]);
+            set_block(
+                &mut partial_world,
+                &mut world,
+                BlockPos::new(0, 1, 0),
+                block_state,
+            );
+            (partial_world, world)
+        };
+        let pos = BlockPos::new(0, 1, 0);
+        let east = Some(CardinalDirection::East);
+        let north = Some(CardinalDirection::North);
+        assert!(is_block_passable_going(&pos, &world, None));
+
+        let (_partial_world, world) = world_with(
+            azalea_block::blocks::OakTrapdoor {
+                facing: FacingCardinal::North,
+                open: true,
+                ..Default::default()
+            }
+            .as_block_state(),
+        );
+        // it's standing up against the south side of the block
+        assert!(is_block_passable_going(&pos, &world, None));
+        assert!(is_block_passable_going(&pos, &world, east));
+        assert!(!is_block_passable_going(&pos, &world, north));
+
+        let (_partial_world, world) = world_with(azalea_registry::Block::OakTrapdoor.into());
+        assert!(!is_block_passable_going(&pos, &world, None));
+        assert!(!is_block_passable_going(&pos, &world, east));
+
+        let (_partial_world, world) = world_with(azalea_registry::Block::OakFenceGate.into());
+        assert!(!is_block_passable_going(&pos, &world, east));
+        assert!(!is_block_passable_going(&pos, &world, north));
+        let (_partial_world, world) = world_with(
+            azalea_block::blocks::OakFenceGate {
+                open: true,
+                ..Default::default()
+            }
+            .as_block_state(),
+        );
+        assert!(is_block_passable_going(&pos, &world, east));
+        assert!(is_block_passable_going(&pos, &world, north));
+    }
+
+    #[test]
+    fn test_parkour() {
+        let (mut partial_world, mut world) = make_world(&[
+            BlockPos::new(5, 0, 5),
+            // one block gap to the east
+            BlockPos::new(7, 0, 5),
+            // two block gap to the south
+            BlockPos::new(5, 0, 8),
+            // four block gap to the north
+            BlockPos::new(5, 0, 0),
+        ]);
+        let node = node_at(BlockPos::new(5, 1, 5));
+        let mining_costs = MiningCosts::new(&Inventory::default(), ActiveEffects::default());
+        let parkour = ParkourSimulator::default();
+        let mut settings = PathfinderSettings::default();
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        let east = ParkourMove {
+            direction: CardinalDirection::East,
+            gap: 1,
+        };
+        assert_eq!(east.cost(&ctx, &node), 2. * WALK_ONE_BLOCK_COST + JUMP_COST);
+        let south = ParkourMove {
+            direction: CardinalDirection::South,
+            gap: 2,
+        };
+        assert_eq!(
+            south.cost(&ctx, &node),
+            3. * WALK_ONE_BLOCK_COST + JUMP_COST
+        );
+        // nothing's in the way, but it's too far to jump
+        let north = ParkourMove {
+            direction: CardinalDirection::North,
+            gap: 4,
+        };
+        assert_eq!(north.cost(&ctx, &node), f32::INFINITY);
+        // there's nothing to land on
+        let west = ParkourMove {
+            direction: CardinalDirection::West,
+            gap: 1,
+        };
+        assert_eq!(west.cost(&ctx, &node), f32::INFINITY);
+
+        settings.allow_parkour = false;
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert_eq!(east.cost(&ctx, &node), f32::INFINITY);
+
+        // we remember whether the jump works until a block near it changes
+        let target = BlockPos::new(7, 1, 5);
+        set_block(
+            &mut partial_world,
+            &mut world,
+            target.down(1),
+            BlockState::AIR,
+        );
+        assert!(parkour.can_jump(&world, &node, &target));
+        parkour.forget(&target.down(1));
+        assert!(!parkour.can_jump(&world, &node, &target));
+    }
+
+    #[test]
+    fn test_footprint() {
+        let mut floor = Vec::new();
+        for x in 0..16 {
+            for z in 0..16 {
+                floor.push(BlockPos::new(x, -1, z));
+                floor.push(BlockPos::new(x, 0, z));
+            }
+        }
+        let (mut partial_world, mut world) = make_world(&floor);
+        let node = node_at(BlockPos::new(8, 1, 8));
+        let mining_costs = MiningCosts::new(&Inventory::default(), ActiveEffects::default());
+        let parkour = ParkourSimulator::default();
+        let settings = PathfinderSettings::default();
+        let moves = all_moves(&settings);
+        let costs = |world: &Instance| {
+            let ctx = make_ctx(world, &settings, &mining_costs, &parkour);
+            moves
+                .iter()
+                .map(|movement| movement.cost(&ctx, &node))
+                .collect::<Vec<_>>()
+        };
+        let original_costs = costs(&world);
+
+        // changing any block that isn't in a move's footprint doesn't change
+        // its cost
+        for x in -5..=5 {
+            for y in -3..=5 {
+                for z in -5..=5 {
+                    let offset = BlockPos::new(x, y, z);
+                    let pos = node.pos + offset;
+                    let original = world.chunks.get_block_state(&pos).unwrap();
+                    let changed = if original.is_air() {
+                        azalea_registry::Block::Stone.into()
+                    } else {
+                        BlockState::AIR
+                    };
+                    set_block(&mut partial_world, &mut world, pos, changed);
+                    for ((movement, cost), original_cost) in
+                        moves.iter().zip(costs(&world)).zip(&original_costs)
+                    {
+                        if !movement.footprint().contains(&offset) {
+                            assert_eq!(
+                                cost.to_bits(),
+                                original_cost.to_bits(),
+                                "changing the block at {offset:?} changed the cost of the move \
+                                to {:?}",
+                                movement.offset()
+                            );
+                        }
+                    }
+                    set_block(&mut partial_world, &mut world, pos, original);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fall() {
+        let (mut partial_world, mut world) =
+            make_world(&[BlockPos::new(5, 5, 5), BlockPos::new(6, 0, 5)]);
+        let node = node_at(BlockPos::new(5, 6, 5));
+        let fall = FallMove {
+            direction: CardinalDirection::East,
+            height: 5,
+        };
+        let mining_costs = MiningCosts::new(&Inventory::default(), ActiveEffects::default());
+        let parkour = ParkourSimulator::default();
+        let mut settings = PathfinderSettings::default();
+        // too far to fall by default
+        let has_fall = |settings: &PathfinderSettings| {
+            all_moves(settings)
+                .iter()
+                .any(|movement| movement.offset() == fall.offset())
+        };
+        assert!(!has_fall(&settings));
+        settings.max_fall_distance = 5;
+        assert!(has_fall(&settings));
+
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert_eq!(
+            fall.cost(&ctx, &node),
+            WALK_ONE_BLOCK_COST + 5. * FALL_ONE_BLOCK_COST + 2. * FALL_DAMAGE_COST
+        );
+
+        // landing in water doesn't hurt
+        set_block(
+            &mut partial_world,
+            &mut world,
+            BlockPos::new(6, 1, 5),
+            azalea_registry::Block::Water.into(),
+        );
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert_eq!(
+            fall.cost(&ctx, &node),
+            WALK_ONE_BLOCK_COST + 5. * FALL_ONE_BLOCK_COST
+        );
+
+        // but landing in lava or falling past it does
+        for lava in [BlockPos::new(6, 1, 5), BlockPos::new(6, 3, 5)] {
+            set_block(
+                &mut partial_world,
+                &mut world,
+                lava,
+                azalea_registry::Block::Lava.into(),
+            );
+            let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+            assert_eq!(fall.cost(&ctx, &node), f32::INFINITY);
+            set_block(&mut partial_world, &mut world, lava, BlockState::AIR);
+        }
+
+        // descending one block into water works too, since that's too short to
+        // be a fall
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert_eq!(
+            DescendMove(CardinalDirection::East).cost(&ctx, &node_at(BlockPos::new(5, 3, 5))),
+            f32::INFINITY
+        );
+        set_block(
+            &mut partial_world,
+            &mut world,
+            BlockPos::new(6, 2, 5),
+            azalea_registry::Block::Water.into(),
+        );
+        let ctx = make_ctx(&world, &settings, &mining_costs, &parkour);
+        assert_eq!(
+            DescendMove(CardinalDirection::East).cost(&ctx, &node_at(BlockPos::new(5, 3, 5))),
+            WALK_ONE_BLOCK_COST
+        );
+    }
 }
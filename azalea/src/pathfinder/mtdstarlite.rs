@@ -9,7 +9,13 @@
 //! - Store edge costs in their own map
 
 use priority_queue::DoublePriorityQueue;
-use std::{collections::HashMap, fmt::Debug, hash::Hash, ops::Add};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    ops::Add,
+    time::{Duration, Instant},
+};
 
 /// Nodes are coordinates.
 pub struct MTDStarLite<
@@ -29,16 +35,26 @@ pub struct MTDStarLite<
     /// isn't directed (i.e. you can always return to the previous node), this
     /// can be the same as `successors`.
     pub predecessors: PredecessorsFn,
-    /// Returns true if the given node is at the goal.
-    /// A simple implementation is to check if the given node is equal to the
-    /// goal.
+    /// Returns true if the given node is at the goal. The search stops at the
+    /// first node that this returns true for.
     pub success: SuccessFn,
 
+    /// The most nodes that can be expanded in one call to
+    /// [`Self::find_path`] before we give up, or `None` for no limit.
+    pub max_expansions: Option<usize>,
+    /// How long one call to [`Self::find_path`] can take before we give up,
+    /// or `None` for no limit.
+    pub timeout: Option<Duration>,
+    /// Whether [`Self::find_path`] should return a path to the node that
+    /// looked closest to the goal if it can't find a path to the goal itself.
+    pub allow_partial_paths: bool,
+
     start: N,
-    goal: N,
+    /// The node that we found that satisfies `success`, if any.
+    goal: Option<N>,
 
     old_start: N,
-    old_goal: N,
+    old_goal: Option<N>,
 
     k_m: W,
     open: DoublePriorityQueue<N, Priority<W>>,
@@ -74,7 +90,6 @@ impl<
 
     pub fn new(
         start: N,
-        goal: N,
         heuristic: HeuristicFn,
         successors: SuccessorsFn,
         predecessors: PredecessorsFn,
@@ -83,7 +98,7 @@ impl<
         let open = DoublePriorityQueue::default();
         let k_m = W::default();
 
-        let known_nodes = vec![start, goal];
+        let known_nodes = vec![start];
 
         let mut pf = MTDStarLite {
             heuristic,
@@ -91,11 +106,15 @@ impl<
             predecessors,
             success,
 
+            max_expansions: None,
+            timeout: None,
+            allow_partial_paths: false,
+
             start,
-            goal,
+            goal: None,
 
             old_start: start,
-            old_goal: goal,
+            old_goal: None,
 
             k_m,
             open,
@@ -127,17 +146,30 @@ impl<
         }
     }
 
-    fn compute_cost_minimal_path(&mut self) {
-        while {
-            if let Some((_, top_key)) = self.open.peek_min() {
-                (top_key < &self.calculate_key(&self.goal)) || {
-                    let goal_state = self.state(&self.goal);
-                    goal_state.rhs > goal_state.g
+    fn compute_cost_minimal_path(&mut self) -> Result<(), SearchError> {
+        let start_time = Instant::now();
+        let mut expansions = 0;
+
+        while let Some((_, top_key)) = self.open.peek_min() {
+            if let Some(goal) = self.goal {
+                // stop once nothing in the open list could give us a better
+                // path to the goal we already found
+                let goal_state = self.state(&goal);
+                if top_key >= &self.calculate_key(&goal) && goal_state.rhs <= goal_state.g {
+                    return Ok(());
                 }
-            } else {
-                false
             }
-        } {
+            if self.max_expansions.is_some_and(|max| expansions >= max) {
+                return Err(SearchError::TooManyNodes);
+            }
+            if self
+                .timeout
+                .is_some_and(|timeout| start_time.elapsed() >= timeout)
+            {
+                return Err(SearchError::TimedOut);
+            }
+            expansions += 1;
+
             let (u_node, k_old) = self.open.pop_min().unwrap();
             let k_new = self.calculate_key(&u_node);
             if k_old < k_new {
@@ -160,6 +192,11 @@ impl<
                         self.update_state(&s_node);
                     }
                 }
+
+                if (self.success)(&u_node) {
+                    self.goal = Some(u_node);
+                    return Ok(());
+                }
             } else {
                 u.g = W::max_value();
                 let u_edge = Edge {
@@ -173,39 +210,59 @@ impl<
                     let s_node = edge.target;
                     let s = self.state(&s_node);
                     if s_node != self.start && s.par == Some(u_node) {
-                        let mut min_pred = u_node;
-                        let mut min_score = W::max_value();
-
-                        for edge in (self.predecessors)(&s_node) {
-                            let s = self.state(&edge.target);
-                            let score = s.g + edge.cost;
-                            if score < min_score {
-                                min_score = score;
-                                min_pred = edge.target;
-                            }
-                        }
-
-                        let s = self.state_mut(&s_node);
-                        s.rhs = min_score;
-                        if s.rhs == W::max_value() {
-                            s.par = None;
-                        } else {
-                            s.par = Some(min_pred);
-                        }
+                        self.reconnect(&s_node);
                     }
                     self.update_state(&s_node);
                 }
             }
         }
+
+        // there's nothing left to search
+        if self.goal.is_some() {
+            Ok(())
+        } else {
+            Err(SearchError::NoPath)
+        }
+    }
+
+    /// Move the start of the search, for example because we moved along the
+    /// path or got knocked off of it. The next call to [`Self::find_path`]
+    /// reuses the parts of the previous search that are still valid.
+    pub fn set_start(&mut self, start: N) {
+        self.start = start;
+    }
+
+    /// Tell the search that the costs of some edges changed since the last
+    /// call to [`Self::find_path`], so it can repair the path instead of
+    /// starting over.
+    pub fn update_edge_costs(&mut self, edges: impl IntoIterator<Item = ChangedEdge<N, W>>) {
+        self.updated_edge_costs.extend(edges);
+    }
+
+    /// Whether the search has looked at the node. Changes to edges that start
+    /// at nodes it hasn't looked at don't affect it.
+    pub fn has_visited(&self, node: &N) -> bool {
+        self.node_states.contains_key(node)
     }
 
-    pub fn find_path(&mut self) -> Option<Vec<N>> {
+    /// Find a path from the start to a node that satisfies `success`, or
+    /// update the path we found last time if the start or edge costs changed.
+    ///
+    /// If we can't find a path to the goal and `allow_partial_paths` is
+    /// enabled, this returns the path to the node that looked closest to the
+    /// goal instead.
+    pub fn find_path(&mut self) -> Result<Path<N>, SearchError> {
         if (self.success)(&self.start) {
-            return None;
+            return Ok(Path {
+                nodes: Vec::new(),
+                partial: false,
+            });
         }
 
         //
-        self.k_m = self.k_m + (self.heuristic)(&self.old_goal);
+        if let Some(old_goal) = self.old_goal {
+            self.k_m = self.k_m + (self.heuristic)(&old_goal);
+        }
 
         if self.old_start != self.start {
             self.optimized_deletion();
@@ -214,34 +271,26 @@ impl<
         while let Some(edge) = self.updated_edge_costs.pop() {
             let (u_node, v_node) = (edge.predecessor, edge.successor);
             // update the edge cost c(u, v);
-            if edge.old_cost > edge.cost {
+            if v_node == self.start {
+                continue;
+            }
+            // we don't know whether the edge got cheaper or more expensive, but
+            // if v came from u then reconnecting it handles both, and otherwise
+            // the edge can only matter if v is cheaper to get to from u now
+            if self.state(&v_node).par == Some(u_node) {
+                self.reconnect(&v_node);
+                self.update_state(&v_node);
+            } else {
                 let u_g = self.state(&u_node).g;
-                if v_node != self.start && self.state(&v_node).rhs > u_g + edge.cost {
+                if u_g != W::max_value()
+                    && edge.cost != W::max_value()
+                    && self.state(&v_node).rhs > u_g + edge.cost
+                {
                     let v = self.state_mut(&v_node);
                     v.par = Some(u_node);
                     v.rhs = u_g + edge.cost;
+                    self.update_state(&v_node);
                 }
-            } else if v_node != self.start && self.state(&v_node).par == Some(u_node) {
-                let mut min_pred = u_node;
-                let mut min_score = W::max_value();
-
-                for edge in (self.predecessors)(&v_node) {
-                    let s = self.state(&edge.target);
-                    let score = s.g + edge.cost;
-                    if score < min_score {
-                        min_score = score;
-                        min_pred = edge.target;
-                    }
-                }
-
-                let v = self.state_mut(&v_node);
-                v.rhs = min_score;
-                if v.rhs == W::max_value() {
-                    v.par = None;
-                } else {
-                    v.par = Some(min_pred);
-                }
-                self.update_state(&v_node);
             }
         }
         //
@@ -249,59 +298,148 @@ impl<
         self.old_start = self.start;
         self.old_goal = self.goal;
 
-        self.compute_cost_minimal_path();
-        if self.state(&self.goal).rhs == W::max_value() {
-            // no path exists
-            return None;
-        }
+        let result = self.compute_cost_minimal_path();
+        let goal = match (result, self.goal) {
+            (Ok(()), Some(goal)) if self.state(&goal).rhs != W::max_value() => goal,
+            (result, _) => {
+                let error = result.err().unwrap_or(SearchError::NoPath);
+                if !self.allow_partial_paths {
+                    return Err(error);
+                }
+                match self.best_node() {
+                    Some(best_node) => {
+                        return Ok(Path {
+                            nodes: self.path_to(&best_node).ok_or(error)?,
+                            partial: true,
+                        });
+                    }
+                    None => return Err(error),
+                }
+            }
+        };
 
-        let mut reverse_path = vec![self.goal];
+        Ok(Path {
+            nodes: self.path_to(&goal).ok_or(SearchError::NoPath)?,
+            partial: false,
+        })
+    }
 
-        // identify a path from sstart to sgoal using the parent pointers
-        let mut target = self.state(&self.goal).par;
-        while !(Some(self.start) == target) {
-            let Some(this_target) = target else {
-                break;
-            };
-            // hunter follows path from start to goal;
+    /// Follow the parent pointers back from the node to the start. Returns
+    /// `None` if the node isn't connected to the start.
+    fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut reverse_path = vec![*node];
+
+        let mut target = self.state(node).par;
+        while Some(self.start) != target {
+            // the path can't be longer than the number of nodes we know about,
+            // so if it is then the parent pointers must have a cycle
+            if reverse_path.len() > self.node_states.len() {
+                return None;
+            }
+            let this_target = target?;
             reverse_path.push(this_target);
             target = self.state(&this_target).par;
         }
 
-        // if hunter caught target {
-        //     return None;
-        // }
-
-        let path: Vec<N> = reverse_path.into_iter().rev().collect();
+        Some(reverse_path.into_iter().rev().collect())
+    }
 
-        Some(path)
+    /// The node with the lowest heuristic that we know a path to, which is
+    /// where partial paths go to. Returns `None` if none of them are closer to
+    /// the goal than the start, since going there wouldn't help.
+    fn best_node(&self) -> Option<N> {
+        let mut best: Option<(N, W)> = None;
+        let start_h = (self.heuristic)(&self.start);
+        for (node, state) in &self.node_states {
+            if *node == self.start || state.g == W::max_value() {
+                continue;
+            }
+            let h = (self.heuristic)(node);
+            let is_best = match best {
+                Some((_, best_h)) => h < best_h,
+                None => h < start_h,
+            };
+            if is_best && self.path_to(node).is_some() {
+                best = Some((*node, h));
+            }
+        }
+        best.map(|(node, _)| node)
     }
 
+    /// Remove the nodes that aren't reachable through the new start from the
+    /// search tree, and reconnect them to it if they can be.
     fn optimized_deletion(&mut self) {
         let start = self.start;
         self.state_mut(&start).par = None;
+        if self.state(&start).rhs == W::max_value() {
+            // we're somewhere the last search never got to, so this is the
+            // root of a new tree
+            self.state_mut(&start).rhs = W::default();
+            self.update_state(&start);
+        }
 
-        let mut min_pred = self.old_start;
-        let mut min_score = W::max_value();
+        // a node stays in the tree if following its parents gets us to the
+        // new start
+        let mut in_subtree = HashMap::<N, bool>::new();
+        in_subtree.insert(start, true);
+        let nodes = self.node_states.keys().copied().collect::<Vec<_>>();
+        for node in &nodes {
+            let mut chain = Vec::new();
+            let mut current = *node;
+            let is_in_subtree = loop {
+                if let Some(&known) = in_subtree.get(&current) {
+                    break known;
+                }
+                // the parents can't go on for longer than the number of
+                // nodes unless they have a cycle
+                if chain.len() > nodes.len() {
+                    break false;
+                }
+                chain.push(current);
+                match self.state(&current).par {
+                    Some(par) => current = par,
+                    None => break false,
+                }
+            };
+            for node in chain {
+                in_subtree.insert(node, is_in_subtree);
+            }
+        }
+
+        let mut deleted = Vec::new();
+        for node in nodes {
+            if in_subtree[&node] {
+                continue;
+            }
+            *self.state_mut(&node) = NodeState::default();
+            self.open.remove(&node);
+            deleted.push(node);
+        }
+        for node in deleted {
+            self.reconnect(&node);
+            self.update_state(&node);
+        }
+    }
 
-        for edge in (self.predecessors)(&self.old_start) {
+    /// Set the parent of the node to whichever of its predecessors gives it
+    /// the cheapest path from the start.
+    fn reconnect(&mut self, n: &N) {
+        let mut min_pred = None;
+        let mut min_score = W::max_value();
+        for edge in (self.predecessors)(n) {
             let s = self.state(&edge.target);
+            if s.g == W::max_value() {
+                continue;
+            }
             let score = s.g + edge.cost;
             if score < min_score {
                 min_score = score;
-                min_pred = edge.target;
+                min_pred = Some(edge.target);
             }
         }
-
-        let old_start = self.old_start;
-        let s = self.state_mut(&old_start);
+        let s = self.state_mut(n);
         s.rhs = min_score;
-        if s.rhs == W::max_value() {
-            s.par = None;
-        } else {
-            s.par = Some(min_pred);
-        }
-        self.update_state(&old_start);
+        s.par = min_pred;
     }
 
     fn state(&self, n: &N) -> &NodeState<N, W> {
@@ -313,6 +451,27 @@ impl<
     }
 }
 
+/// A path found by [`MTDStarLite::find_path`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N> {
+    /// The nodes in the path, not including the start.
+    pub nodes: Vec<N>,
+    /// Whether this path only goes to the node that looked closest to the
+    /// goal, because we couldn't find a path to the goal itself.
+    pub partial: bool,
+}
+
+/// Why [`MTDStarLite::find_path`] couldn't find a path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchError {
+    /// We searched every node we can get to and none of them were the goal.
+    NoPath,
+    /// We expanded `max_expansions` nodes without finding the goal.
+    TooManyNodes,
+    /// We spent longer than `timeout` looking for the goal.
+    TimedOut,
+}
+
 #[derive(PartialEq, Debug)]
 pub struct Priority<W>(W, W)
 where
@@ -369,7 +528,7 @@ pub struct Edge<N: Eq + Hash + Copy, W: PartialOrd + Copy> {
 pub struct ChangedEdge<N: Eq + Hash + Clone, W: PartialOrd + Copy> {
     pub predecessor: N,
     pub successor: N,
-    pub old_cost: W,
+    /// The new cost of the edge.
     pub cost: W,
 }
 
@@ -429,12 +588,11 @@ mod tests {
         let predecessors =
             |n: &(usize, usize)| -> Vec<Edge<(usize, usize), usize>> { successors(n) };
 
-        let mut pf = MTDStarLite::new((0, 0), goal, heuristic, successors, predecessors, |n| {
-            n == &goal
-        });
+        let mut pf = MTDStarLite::new((0, 0), heuristic, successors, predecessors, |n| n == &goal);
         let path = pf.find_path().unwrap();
+        assert!(!path.partial);
         assert_eq!(
-            path,
+            path.nodes,
             vec![
                 (0, 1),
                 (0, 2),
@@ -451,4 +609,176 @@ mod tests {
             ]
         );
     }
+
+    type Maze = [[u8; 5]; 5];
+
+    fn maze_successors(maze: &Maze, n: &(usize, usize)) -> Vec<Edge<(usize, usize), usize>> {
+        let (x, y) = *n;
+        let mut neighbors = Vec::with_capacity(4);
+        if x > 0 {
+            neighbors.push((x - 1, y));
+        }
+        if x < 4 {
+            neighbors.push((x + 1, y));
+        }
+        if y > 0 {
+            neighbors.push((x, y - 1));
+        }
+        if y < 4 {
+            neighbors.push((x, y + 1));
+        }
+        neighbors
+            .into_iter()
+            .filter(|(x, y)| maze[*y][*x] == 0)
+            .map(|target| Edge { target, cost: 1 })
+            .collect()
+    }
+
+    #[test]
+    fn test_open_ended_goal() {
+        let maze = [
+            [0, 1, 0, 0, 0],
+            [0, 1, 0, 1, 0],
+            [0, 0, 0, 1, 0],
+            [0, 1, 0, 1, 0],
+            [0, 0, 1, 0, 0],
+        ];
+        let successors = |n: &(usize, usize)| maze_successors(&maze, n);
+
+        // get to any node in the last column
+        let mut pf = MTDStarLite::new(
+            (0, 0),
+            |n: &(usize, usize)| 4 - n.0,
+            successors,
+            successors,
+            |n| n.0 == 4,
+        );
+        let path = pf.find_path().unwrap();
+        assert!(!path.partial);
+        assert_eq!(path.nodes.len(), 8);
+        assert_eq!(path.nodes.last(), Some(&(4, 0)));
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let maze = [
+            [0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 1],
+            [0, 0, 0, 1, 0],
+        ];
+        let successors = |n: &(usize, usize)| maze_successors(&maze, n);
+        let heuristic = |n: &(usize, usize)| (4 - n.0) + (4 - n.1);
+
+        let mut pf = MTDStarLite::new((0, 0), heuristic, successors, successors, |n| n == &(4, 4));
+        assert_eq!(pf.find_path(), Err(SearchError::NoPath));
+
+        let mut pf = MTDStarLite::new((0, 0), heuristic, successors, successors, |n| n == &(4, 4));
+        pf.allow_partial_paths = true;
+        let path = pf.find_path().unwrap();
+        assert!(path.partial);
+        assert_eq!(heuristic(path.nodes.last().unwrap()), 2);
+
+        // we're as close as we can get, so going anywhere else doesn't help
+        pf.set_start(*path.nodes.last().unwrap());
+        assert_eq!(pf.find_path(), Err(SearchError::NoPath));
+
+        let mut pf = MTDStarLite::new((0, 0), heuristic, successors, successors, |n| n == &(4, 4));
+        pf.max_expansions = Some(3);
+        assert_eq!(pf.find_path(), Err(SearchError::TooManyNodes));
+    }
+
+    /// Check that the path goes from the start to the goal through open cells
+    /// and is as short as it can be.
+    fn assert_shortest_path(
+        maze: &Maze,
+        start: (usize, usize),
+        goal: (usize, usize),
+        path: &[(usize, usize)],
+    ) {
+        let mut previous = start;
+        for node in path {
+            assert!(maze_successors(maze, &previous)
+                .iter()
+                .any(|edge| edge.target == *node));
+            previous = *node;
+        }
+        assert_eq!(previous, goal);
+
+        // breadth-first search to find how long the shortest path is
+        let mut distances = HashMap::from([(start, 0)]);
+        let mut queue = std::collections::VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for edge in maze_successors(maze, &node) {
+                if !distances.contains_key(&edge.target) {
+                    distances.insert(edge.target, distances[&node] + 1);
+                    queue.push_back(edge.target);
+                }
+            }
+        }
+        assert_eq!(path.len(), distances[&goal]);
+    }
+
+    #[test]
+    fn test_replan() {
+        let maze = std::cell::RefCell::new([
+            [0, 0, 0, 0, 0],
+            [0, 0, 1, 0, 0],
+            [0, 0, 1, 0, 0],
+            [0, 0, 1, 0, 0],
+            [0, 0, 0, 0, 0],
+        ]);
+        let successors = |n: &(usize, usize)| maze_successors(&maze.borrow(), n);
+        let goal = (4, 1);
+        let heuristic = |n: &(usize, usize)| n.0.abs_diff(goal.0) + n.1.abs_diff(goal.1);
+
+        let mut pf = MTDStarLite::new((0, 1), heuristic, successors, successors, |n| n == &goal);
+        let path = pf.find_path().unwrap();
+        assert_shortest_path(&maze.borrow(), (0, 1), goal, &path.nodes);
+        assert!(path.nodes.contains(&(2, 0)));
+
+        // walk a bit along the path, and then the gap in the wall gets blocked
+        let start = path.nodes[1];
+        pf.set_start(start);
+        maze.borrow_mut()[0][2] = 1;
+        let mut changed_edges = Vec::new();
+        for edge in maze_successors(&maze.borrow(), &(2, 0)) {
+            for (predecessor, successor) in [(edge.target, (2, 0)), ((2, 0), edge.target)] {
+                changed_edges.push(ChangedEdge {
+                    predecessor,
+                    successor,
+                    cost: usize::MAX,
+                });
+            }
+        }
+        pf.update_edge_costs(changed_edges);
+        let path = pf.find_path().unwrap();
+        assert_shortest_path(&maze.borrow(), start, goal, &path.nodes);
+
+        // get knocked somewhere that isn't on the path
+        let start = (1, 3);
+        pf.set_start(start);
+        let path = pf.find_path().unwrap();
+        assert_shortest_path(&maze.borrow(), start, goal, &path.nodes);
+
+        // the gap opens up again, so going through it is shorter
+        let start = (1, 1);
+        pf.set_start(start);
+        maze.borrow_mut()[0][2] = 0;
+        let mut changed_edges = Vec::new();
+        for edge in maze_successors(&maze.borrow(), &(2, 0)) {
+            for (predecessor, successor) in [(edge.target, (2, 0)), ((2, 0), edge.target)] {
+                changed_edges.push(ChangedEdge {
+                    predecessor,
+                    successor,
+                    cost: edge.cost,
+                });
+            }
+        }
+        pf.update_edge_costs(changed_edges);
+        let path = pf.find_path().unwrap();
+        assert_shortest_path(&maze.borrow(), start, goal, &path.nodes);
+        assert!(path.nodes.contains(&(2, 0)));
+    }
 }
@@ -13,7 +13,7 @@ BLOCKS_RS_DIR = get_dir_location('../azalea-block/src/blocks.rs')
 # - Block: Has properties and states.
 
 
-def generate_blocks(blocks_burger: dict, blocks_report: dict, ordered_blocks: list[str], mappings: Mappings):
+def generate_blocks(blocks_burger: dict, blocks_report: dict, pixlyzer_block_datas: dict, ordered_blocks: list[str], mappings: Mappings):
     with open(BLOCKS_RS_DIR, 'r') as f:
         existing_code = f.read().splitlines()
 
@@ -134,9 +134,10 @@ def generate_blocks(blocks_burger: dict, blocks_report: dict, ordered_blocks: li
         else:
             properties_code += '\n        }'
 
-        # TODO: use burger to generate the blockbehavior
+        behavior_code = get_block_behavior_code(
+            pixlyzer_block_datas[f'minecraft:{block_id}'])
         new_make_block_states_macro_code.append(
-            f'        {block_id} => BlockBehavior::default(), {properties_code},')
+            f'        {block_id} => {behavior_code}, {properties_code},')
 
     new_make_block_states_macro_code.append('    }')
     new_make_block_states_macro_code.append('}')
@@ -161,6 +162,37 @@ def generate_blocks(blocks_burger: dict, blocks_report: dict, ordered_blocks: li
         f.write('\n'.join(new_code))
 
 
+def get_block_behavior_code(block_data_pixlyzer: dict) -> str:
+    behavior_code = 'BlockBehavior::default()'
+
+    default_state_id = block_data_pixlyzer['default_state']
+    default_state = block_data_pixlyzer['states'][str(default_state_id)]
+
+    destroy_time = default_state.get('hardness', 0.)
+    explosion_resistance = block_data_pixlyzer.get('explosion_resistance', 0.)
+    if destroy_time != 0. or explosion_resistance != 0.:
+        behavior_code += f'.strength({format_float(destroy_time)}, {format_float(explosion_resistance)})'
+    if default_state.get('requires_tool'):
+        behavior_code += '.requires_correct_tool_for_drops()'
+    speed_factor = block_data_pixlyzer.get('velocity_multiplier', 1.)
+    if speed_factor != 1.:
+        behavior_code += f'.speed_factor({format_float(speed_factor)})'
+    jump_factor = block_data_pixlyzer.get('jump_velocity_multiplier', 1.)
+    if jump_factor != 1.:
+        behavior_code += f'.jump_factor({format_float(jump_factor)})'
+
+    return behavior_code
+
+
+def format_float(n: float) -> str:
+    # write whole numbers like `1.` instead of `1.0` to match the rest of the
+    # code
+    s = repr(float(n))
+    if s.endswith('.0'):
+        s = s[:-1]
+    return s
+
+
 def get_property_struct_name(property: Optional[dict], block_data_burger: dict, property_variants: list[str], mappings: Mappings) -> str:
     # these are hardcoded because otherwise they cause conflicts
     # some names inspired by https://github.com/feather-rs/feather/blob/main/feather/blocks/src/generated/table.rs